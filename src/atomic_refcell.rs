@@ -59,6 +59,16 @@ impl<T: ?Sized> AtomicRefCell<T> {
             inner: ManuallyDrop::new(UnsafeCell::new(value)),
         }
     }
+    /// Creates a new `AtomicRefCell` containing `value` with the same thread restrictions as `self`.
+    pub(crate) fn new_like<U>(&self, value: U) -> AtomicRefCell<U> {
+        AtomicRefCell::new(
+            value,
+            #[cfg(feature = "std")]
+            self.send,
+            #[cfg(feature = "std")]
+            self.is_sync,
+        )
+    }
     /// Immutably borrows the wrapped value, returning an error if the value is currently mutably
     /// borrowed.
     ///
@@ -1,6 +1,7 @@
 //use super::FakeBorrow;
 use crate::error;
-use crate::storage::AllStorages;
+use crate::event::EventWriter;
+use crate::storage::{AllStorages, Commands};
 use crate::view::{EntitiesView, EntitiesViewMut, UniqueView, UniqueViewMut, View, ViewMut};
 #[cfg(feature = "non_send")]
 use crate::NonSend;
@@ -37,6 +38,18 @@ impl<'a> AllStoragesBorrow<'a> for EntitiesViewMut<'a> {
     }
 }
 
+impl<'a> AllStoragesBorrow<'a> for Commands<'a> {
+    fn try_borrow(all_storages: &'a AllStorages) -> Result<Self, error::GetStorage> {
+        all_storages.try_into()
+    }
+}
+
+impl<'a, T: 'static + Send + Sync> AllStoragesBorrow<'a> for EventWriter<'a, T> {
+    fn try_borrow(all_storages: &'a AllStorages) -> Result<Self, error::GetStorage> {
+        all_storages.try_into().map(EventWriter::new)
+    }
+}
+
 impl<'a, T: 'static + Send + Sync> AllStoragesBorrow<'a> for View<'a, T> {
     fn try_borrow(all_storages: &'a AllStorages) -> Result<Self, error::GetStorage> {
         all_storages.try_into()
@@ -2,7 +2,8 @@ use core::marker::PhantomData;
 
 /// Mimics an exclusive borrow of `T` without actually doing it.
 ///
-/// Can be useful to correctly schedule `Sync` types.
+/// Can be useful to correctly schedule `Sync` types.  
+/// To only order systems, `WorkloadBuilder::before` and `WorkloadBuilder::after` are simpler.
 /// ### Example:
 /// ```
 /// use shipyard::{system, FakeBorrow, View, World};
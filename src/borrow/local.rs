@@ -0,0 +1,184 @@
+use crate::atomic_refcell::AtomicRefCell;
+#[cfg(feature = "std")]
+use crate::atomic_refcell::RefMut;
+#[cfg(feature = "std")]
+use crate::error;
+use alloc::boxed::Box;
+#[cfg(feature = "std")]
+use core::any::type_name;
+use core::any::{Any, TypeId};
+#[cfg(feature = "std")]
+use core::cell::Cell;
+#[cfg(feature = "std")]
+use core::ops::{Deref, DerefMut};
+use core::sync::atomic::AtomicU64;
+#[cfg(feature = "std")]
+use core::sync::atomic::Ordering;
+use hashbrown::HashMap;
+
+/// Creates the default value of a `Local`.
+pub(crate) type LocalDefault = fn() -> Box<dyn Any + Send + Sync>;
+
+/// State owned by a single system, one value per `Local` type it borrows and its change ticks.
+// without std there is no Local to read them
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+#[derive(Default)]
+pub(crate) struct Locals {
+    values: HashMap<TypeId, AtomicRefCell<Box<dyn Any + Send + Sync>>>,
+    // tick of the system's previous run
+    last_run: AtomicU64,
+    // tick of the run in progress
+    this_run: AtomicU64,
+}
+
+impl Locals {
+    pub(crate) fn new(defaults: &[(TypeId, LocalDefault)]) -> Self {
+        Locals {
+            values: defaults
+                .iter()
+                .map(|&(type_id, default)| {
+                    (
+                        type_id,
+                        AtomicRefCell::new(
+                            default(),
+                            #[cfg(feature = "std")]
+                            None,
+                            #[cfg(feature = "std")]
+                            true,
+                        ),
+                    )
+                })
+                .collect(),
+            last_run: AtomicU64::new(0),
+            this_run: AtomicU64::new(0),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    // locals of the system running on this thread
+    static CURRENT: Cell<*const Locals> = const { Cell::new(core::ptr::null()) };
+}
+
+/// Makes `locals` available to `Local` until the guard is dropped, even when unwinding.  
+/// `this_run` becomes the system's last run when the guard is dropped.
+#[cfg(feature = "std")]
+pub(crate) fn enter(locals: &Locals, this_run: u64) -> LocalsGuard<'_> {
+    locals.this_run.store(this_run, Ordering::Relaxed);
+
+    LocalsGuard {
+        locals,
+        previous: CURRENT.with(|current| current.replace(locals)),
+    }
+}
+
+#[cfg(feature = "std")]
+pub(crate) struct LocalsGuard<'a> {
+    locals: &'a Locals,
+    previous: *const Locals,
+}
+
+#[cfg(feature = "std")]
+impl Drop for LocalsGuard<'_> {
+    fn drop(&mut self) {
+        let this_run = self.locals.this_run.load(Ordering::Relaxed);
+        self.locals.last_run.store(this_run, Ordering::Relaxed);
+        CURRENT.with(|current| current.set(self.previous));
+    }
+}
+
+/// Returns the ticks of the previous and current run of the system running on this thread.
+#[cfg(feature = "std")]
+pub(crate) fn system_ticks() -> Option<(u64, u64)> {
+    CURRENT.with(|current| {
+        // SAFE the pointer is only set while the system runs
+        unsafe { current.get().as_ref() }.map(|locals| {
+            (
+                locals.last_run.load(Ordering::Relaxed),
+                locals.this_run.load(Ordering::Relaxed),
+            )
+        })
+    })
+}
+
+/// State owned by a system and kept between its runs.
+///
+/// It's initialized with `T::default()` when the workload is built.
+/// Each system has its own `Local<T>`, it doesn't conflict with any other borrow.
+/// Workloads including the same system with `WorkloadBuilder::with_workload` share its `Local<T>`.
+/// It can only be borrowed by a system running inside a workload.
+/// ### Example:
+/// ```
+/// use shipyard::{system, Local, UniqueViewMut, World};
+///
+/// fn count(mut runs: Local<u32>, mut last: UniqueViewMut<u32>) {
+///     *runs += 1;
+///     *last = *runs;
+/// }
+///
+/// let world = World::new();
+/// world.add_unique(0u32);
+///
+/// world.add_workload("Count").with_system(system!(count)).build();
+///
+/// world.run_default();
+/// world.run_default();
+///
+/// world.run(|last: UniqueViewMut<u32>| assert_eq!(*last, 2));
+/// ```
+#[cfg(feature = "std")]
+pub struct Local<'a, T> {
+    local: RefMut<'a, T>,
+}
+
+#[cfg(feature = "std")]
+impl<'a, T: 'static> Local<'a, T> {
+    pub(crate) fn try_borrow() -> Result<Self, error::GetStorage> {
+        let locals: Option<&'a Locals> = CURRENT.with(|current| {
+            // SAFE the pointer is only set while the system runs
+            // the scheduler stays borrowed during the whole run so the locals can't be dropped
+            // and a Local can't be returned outside of the system
+            unsafe { current.get().as_ref() }
+        });
+        let local = locals
+            .and_then(|locals| locals.values.get(&TypeId::of::<T>()))
+            .ok_or_else(|| error::GetStorage::MissingLocal(type_name::<T>()))?
+            .try_borrow_mut()
+            .map_err(|err| error::GetStorage::StorageBorrow((type_name::<T>(), err)))?;
+
+        Ok(Local {
+            // the value was created by T::default()
+            local: RefMut::map(local, |local| local.downcast_mut().unwrap()),
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> Deref for Local<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.local
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> DerefMut for Local<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.local
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> AsRef<T> for Local<'_, T> {
+    fn as_ref(&self) -> &T {
+        &self.local
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> AsMut<T> for Local<'_, T> {
+    fn as_mut(&mut self) -> &mut T {
+        &mut self.local
+    }
+}
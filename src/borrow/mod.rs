@@ -1,5 +1,6 @@
 mod all_storages;
 mod fake_borrow;
+mod local;
 #[cfg(feature = "non_send")]
 mod non_send;
 #[cfg(all(feature = "non_send", feature = "non_sync"))]
@@ -9,6 +10,11 @@ mod non_sync;
 
 pub use all_storages::AllStoragesBorrow;
 pub use fake_borrow::FakeBorrow;
+#[cfg(feature = "std")]
+pub use local::Local;
+#[cfg(feature = "std")]
+pub(crate) use local::{enter as enter_locals, system_ticks};
+pub(crate) use local::{LocalDefault, Locals};
 #[cfg(feature = "non_send")]
 pub use non_send::NonSend;
 #[cfg(all(feature = "non_send", feature = "non_sync"))]
@@ -18,16 +24,22 @@ pub use non_sync::NonSync;
 
 use crate::atomic_refcell::AtomicRefCell;
 use crate::error;
-use crate::storage::{AllStorages, Entities};
+#[cfg(feature = "std")]
+use crate::event::{EventCursor, EventReader};
+use crate::event::{EventWriter, Events};
+use crate::storage::{AllStorages, CommandQueue, Commands, Entities};
 #[cfg(feature = "parallel")]
 use crate::view::ThreadPoolView;
 use crate::view::{
     AllStoragesViewMut, EntitiesView, EntitiesViewMut, UniqueView, UniqueViewMut, View, ViewMut,
 };
+#[cfg(feature = "std")]
+use alloc::boxed::Box;
 use alloc::vec::Vec;
-use core::any::TypeId;
+use core::any::{type_name, TypeId};
 use core::convert::TryInto;
 
+/// How a system borrows a storage.
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Mutation {
     Shared,
@@ -42,9 +54,11 @@ pub trait Borrow<'a> {
     where
         Self: Sized;
 
-    fn borrow_infos(infos: &mut Vec<(TypeId, Mutation)>);
+    fn borrow_infos(infos: &mut Vec<(TypeId, &'static str, Mutation)>);
 
     fn is_send_sync() -> bool;
+
+    fn local_defaults(_defaults: &mut Vec<(TypeId, LocalDefault)>) {}
 }
 
 impl<'a> Borrow<'a> for () {
@@ -58,7 +72,7 @@ impl<'a> Borrow<'a> for () {
         Ok(())
     }
 
-    fn borrow_infos(_: &mut Vec<(TypeId, Mutation)>) {}
+    fn borrow_infos(_: &mut Vec<(TypeId, &'static str, Mutation)>) {}
 
     fn is_send_sync() -> bool {
         true
@@ -73,8 +87,12 @@ impl<'a> Borrow<'a> for AllStoragesViewMut<'a> {
         all_storages.try_into()
     }
 
-    fn borrow_infos(infos: &mut Vec<(TypeId, Mutation)>) {
-        infos.push((TypeId::of::<AllStorages>(), Mutation::Unique));
+    fn borrow_infos(infos: &mut Vec<(TypeId, &'static str, Mutation)>) {
+        infos.push((
+            TypeId::of::<AllStorages>(),
+            type_name::<AllStorages>(),
+            Mutation::Unique,
+        ));
     }
 
     fn is_send_sync() -> bool {
@@ -93,8 +111,12 @@ impl<'a> Borrow<'a> for EntitiesView<'a> {
             .try_into()
     }
 
-    fn borrow_infos(infos: &mut Vec<(TypeId, Mutation)>) {
-        infos.push((TypeId::of::<Entities>(), Mutation::Shared));
+    fn borrow_infos(infos: &mut Vec<(TypeId, &'static str, Mutation)>) {
+        infos.push((
+            TypeId::of::<Entities>(),
+            type_name::<Entities>(),
+            Mutation::Shared,
+        ));
     }
 
     fn is_send_sync() -> bool {
@@ -113,8 +135,37 @@ impl<'a> Borrow<'a> for EntitiesViewMut<'a> {
             .try_into()
     }
 
-    fn borrow_infos(infos: &mut Vec<(TypeId, Mutation)>) {
-        infos.push((TypeId::of::<Entities>(), Mutation::Unique));
+    fn borrow_infos(infos: &mut Vec<(TypeId, &'static str, Mutation)>) {
+        infos.push((
+            TypeId::of::<Entities>(),
+            type_name::<Entities>(),
+            Mutation::Unique,
+        ));
+    }
+
+    fn is_send_sync() -> bool {
+        true
+    }
+}
+
+impl<'a> Borrow<'a> for Commands<'a> {
+    fn try_borrow(
+        all_storages: &'a AtomicRefCell<AllStorages>,
+        #[cfg(feature = "parallel")] _: &'a rayon::ThreadPool,
+    ) -> Result<Self, error::GetStorage> {
+        all_storages
+            .try_borrow()
+            .map_err(error::GetStorage::AllStoragesBorrow)?
+            .try_into()
+    }
+
+    fn borrow_infos(infos: &mut Vec<(TypeId, &'static str, Mutation)>) {
+        // the queue can be shared, it only has to be ordered with AllStorages borrows
+        infos.push((
+            TypeId::of::<CommandQueue>(),
+            type_name::<CommandQueue>(),
+            Mutation::Shared,
+        ));
     }
 
     fn is_send_sync() -> bool {
@@ -131,7 +182,7 @@ impl<'a> Borrow<'a> for ThreadPoolView<'a> {
         Ok(ThreadPoolView(thread_pool))
     }
 
-    fn borrow_infos(_: &mut Vec<(TypeId, Mutation)>) {}
+    fn borrow_infos(_: &mut Vec<(TypeId, &'static str, Mutation)>) {}
 
     fn is_send_sync() -> bool {
         true
@@ -149,8 +200,8 @@ impl<'a, T: 'static + Send + Sync> Borrow<'a> for View<'a, T> {
             .try_into()
     }
 
-    fn borrow_infos(infos: &mut Vec<(TypeId, Mutation)>) {
-        infos.push((TypeId::of::<T>(), Mutation::Shared));
+    fn borrow_infos(infos: &mut Vec<(TypeId, &'static str, Mutation)>) {
+        infos.push((TypeId::of::<T>(), type_name::<T>(), Mutation::Shared));
     }
 
     fn is_send_sync() -> bool {
@@ -169,8 +220,8 @@ impl<'a, T: 'static + Send + Sync> Borrow<'a> for ViewMut<'a, T> {
             .try_into()
     }
 
-    fn borrow_infos(infos: &mut Vec<(TypeId, Mutation)>) {
-        infos.push((TypeId::of::<T>(), Mutation::Unique));
+    fn borrow_infos(infos: &mut Vec<(TypeId, &'static str, Mutation)>) {
+        infos.push((TypeId::of::<T>(), type_name::<T>(), Mutation::Unique));
     }
 
     fn is_send_sync() -> bool {
@@ -189,7 +240,7 @@ impl<'a, T: 'static + Send + Sync> Borrow<'a> for UniqueView<'a, T> {
             .try_into()
     }
 
-    fn borrow_infos(infos: &mut Vec<(TypeId, Mutation)>) {
+    fn borrow_infos(infos: &mut Vec<(TypeId, &'static str, Mutation)>) {
         <View<'a, T> as Borrow>::borrow_infos(infos)
     }
 
@@ -209,7 +260,7 @@ impl<'a, T: 'static + Send + Sync> Borrow<'a> for UniqueViewMut<'a, T> {
             .try_into()
     }
 
-    fn borrow_infos(infos: &mut Vec<(TypeId, Mutation)>) {
+    fn borrow_infos(infos: &mut Vec<(TypeId, &'static str, Mutation)>) {
         <ViewMut<'a, T> as Borrow>::borrow_infos(infos)
     }
 
@@ -232,8 +283,8 @@ impl<'a, T: 'static + Sync> Borrow<'a> for NonSend<View<'a, T>> {
         .map(NonSend)
     }
 
-    fn borrow_infos(infos: &mut Vec<(TypeId, Mutation)>) {
-        infos.push((TypeId::of::<T>(), Mutation::Unique));
+    fn borrow_infos(infos: &mut Vec<(TypeId, &'static str, Mutation)>) {
+        infos.push((TypeId::of::<T>(), type_name::<T>(), Mutation::Unique));
     }
 
     fn is_send_sync() -> bool {
@@ -255,8 +306,8 @@ impl<'a, T: 'static + Sync> Borrow<'a> for NonSend<ViewMut<'a, T>> {
         .map(NonSend)
     }
 
-    fn borrow_infos(infos: &mut Vec<(TypeId, Mutation)>) {
-        infos.push((TypeId::of::<T>(), Mutation::Unique));
+    fn borrow_infos(infos: &mut Vec<(TypeId, &'static str, Mutation)>) {
+        infos.push((TypeId::of::<T>(), type_name::<T>(), Mutation::Unique));
     }
 
     fn is_send_sync() -> bool {
@@ -277,7 +328,7 @@ impl<'a, T: 'static + Sync> Borrow<'a> for NonSend<UniqueView<'a, T>> {
             .map(NonSend)
     }
 
-    fn borrow_infos(infos: &mut Vec<(TypeId, Mutation)>) {
+    fn borrow_infos(infos: &mut Vec<(TypeId, &'static str, Mutation)>) {
         <NonSend<View<'a, T>> as Borrow>::borrow_infos(infos)
     }
 
@@ -299,7 +350,7 @@ impl<'a, T: 'static + Sync> Borrow<'a> for NonSend<UniqueViewMut<'a, T>> {
             .map(NonSend)
     }
 
-    fn borrow_infos(infos: &mut Vec<(TypeId, Mutation)>) {
+    fn borrow_infos(infos: &mut Vec<(TypeId, &'static str, Mutation)>) {
         <NonSend<ViewMut<'a, T>> as Borrow>::borrow_infos(infos)
     }
 
@@ -322,8 +373,8 @@ impl<'a, T: 'static + Send> Borrow<'a> for NonSync<View<'a, T>> {
         .map(NonSync)
     }
 
-    fn borrow_infos(infos: &mut Vec<(TypeId, Mutation)>) {
-        infos.push((TypeId::of::<T>(), Mutation::Unique));
+    fn borrow_infos(infos: &mut Vec<(TypeId, &'static str, Mutation)>) {
+        infos.push((TypeId::of::<T>(), type_name::<T>(), Mutation::Unique));
     }
 
     fn is_send_sync() -> bool {
@@ -345,8 +396,8 @@ impl<'a, T: 'static + Send> Borrow<'a> for NonSync<ViewMut<'a, T>> {
         .map(NonSync)
     }
 
-    fn borrow_infos(infos: &mut Vec<(TypeId, Mutation)>) {
-        infos.push((TypeId::of::<T>(), Mutation::Unique));
+    fn borrow_infos(infos: &mut Vec<(TypeId, &'static str, Mutation)>) {
+        infos.push((TypeId::of::<T>(), type_name::<T>(), Mutation::Unique));
     }
 
     fn is_send_sync() -> bool {
@@ -367,7 +418,7 @@ impl<'a, T: 'static + Send> Borrow<'a> for NonSync<UniqueView<'a, T>> {
             .map(NonSync)
     }
 
-    fn borrow_infos(infos: &mut Vec<(TypeId, Mutation)>) {
+    fn borrow_infos(infos: &mut Vec<(TypeId, &'static str, Mutation)>) {
         <NonSync<View<'a, T>> as Borrow>::borrow_infos(infos)
     }
 
@@ -389,7 +440,7 @@ impl<'a, T: 'static + Send> Borrow<'a> for NonSync<UniqueViewMut<'a, T>> {
             .map(NonSync)
     }
 
-    fn borrow_infos(infos: &mut Vec<(TypeId, Mutation)>) {
+    fn borrow_infos(infos: &mut Vec<(TypeId, &'static str, Mutation)>) {
         <NonSync<ViewMut<'a, T>> as Borrow>::borrow_infos(infos)
     }
 
@@ -412,8 +463,8 @@ impl<'a, T: 'static> Borrow<'a> for NonSendSync<View<'a, T>> {
         .map(NonSendSync)
     }
 
-    fn borrow_infos(infos: &mut Vec<(TypeId, Mutation)>) {
-        infos.push((TypeId::of::<T>(), Mutation::Unique));
+    fn borrow_infos(infos: &mut Vec<(TypeId, &'static str, Mutation)>) {
+        infos.push((TypeId::of::<T>(), type_name::<T>(), Mutation::Unique));
     }
 
     fn is_send_sync() -> bool {
@@ -435,8 +486,8 @@ impl<'a, T: 'static> Borrow<'a> for NonSendSync<ViewMut<'a, T>> {
         .map(NonSendSync)
     }
 
-    fn borrow_infos(infos: &mut Vec<(TypeId, Mutation)>) {
-        infos.push((TypeId::of::<T>(), Mutation::Unique));
+    fn borrow_infos(infos: &mut Vec<(TypeId, &'static str, Mutation)>) {
+        infos.push((TypeId::of::<T>(), type_name::<T>(), Mutation::Unique));
     }
 
     fn is_send_sync() -> bool {
@@ -456,7 +507,7 @@ impl<'a, T: 'static> Borrow<'a> for NonSendSync<UniqueView<'a, T>> {
             .try_into()
             .map(NonSendSync)
     }
-    fn borrow_infos(infos: &mut Vec<(TypeId, Mutation)>) {
+    fn borrow_infos(infos: &mut Vec<(TypeId, &'static str, Mutation)>) {
         <NonSendSync<View<'a, T>> as Borrow>::borrow_infos(infos)
     }
 
@@ -478,7 +529,7 @@ impl<'a, T: 'static> Borrow<'a> for NonSendSync<UniqueViewMut<'a, T>> {
             .map(NonSendSync)
     }
 
-    fn borrow_infos(infos: &mut Vec<(TypeId, Mutation)>) {
+    fn borrow_infos(infos: &mut Vec<(TypeId, &'static str, Mutation)>) {
         <NonSendSync<ViewMut<'a, T>> as Borrow>::borrow_infos(infos)
     }
 
@@ -487,6 +538,55 @@ impl<'a, T: 'static> Borrow<'a> for NonSendSync<UniqueViewMut<'a, T>> {
     }
 }
 
+impl<'a, T: 'static + Send + Sync> Borrow<'a> for EventWriter<'a, T> {
+    fn try_borrow(
+        all_storages: &'a AtomicRefCell<AllStorages>,
+        #[cfg(feature = "parallel")] thread_pool: &'a rayon::ThreadPool,
+    ) -> Result<Self, error::GetStorage> {
+        <UniqueViewMut<'a, Events<T>> as Borrow>::try_borrow(
+            all_storages,
+            #[cfg(feature = "parallel")]
+            thread_pool,
+        )
+        .map(EventWriter::new)
+    }
+
+    fn borrow_infos(infos: &mut Vec<(TypeId, &'static str, Mutation)>) {
+        <UniqueViewMut<'a, Events<T>> as Borrow>::borrow_infos(infos)
+    }
+
+    fn is_send_sync() -> bool {
+        true
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, T: 'static + Send + Sync> Borrow<'a> for EventReader<'a, T> {
+    fn try_borrow(
+        all_storages: &'a AtomicRefCell<AllStorages>,
+        #[cfg(feature = "parallel")] thread_pool: &'a rayon::ThreadPool,
+    ) -> Result<Self, error::GetStorage> {
+        #[cfg(feature = "parallel")]
+        let events = <UniqueView<'a, Events<T>> as Borrow>::try_borrow(all_storages, thread_pool)?;
+        #[cfg(not(feature = "parallel"))]
+        let events = <UniqueView<'a, Events<T>> as Borrow>::try_borrow(all_storages)?;
+
+        Ok(EventReader::new(events, Local::try_borrow()?))
+    }
+
+    fn borrow_infos(infos: &mut Vec<(TypeId, &'static str, Mutation)>) {
+        <UniqueView<'a, Events<T>> as Borrow>::borrow_infos(infos)
+    }
+
+    fn is_send_sync() -> bool {
+        true
+    }
+
+    fn local_defaults(defaults: &mut Vec<(TypeId, LocalDefault)>) {
+        <Local<'a, EventCursor<T>> as Borrow>::local_defaults(defaults)
+    }
+}
+
 impl<'a, T: 'static> Borrow<'a> for FakeBorrow<T> {
     fn try_borrow(
         _: &'a AtomicRefCell<AllStorages>,
@@ -495,8 +595,8 @@ impl<'a, T: 'static> Borrow<'a> for FakeBorrow<T> {
         Ok(FakeBorrow::new())
     }
 
-    fn borrow_infos(infos: &mut Vec<(TypeId, Mutation)>) {
-        infos.push((TypeId::of::<T>(), Mutation::Unique))
+    fn borrow_infos(infos: &mut Vec<(TypeId, &'static str, Mutation)>) {
+        infos.push((TypeId::of::<T>(), type_name::<T>(), Mutation::Unique))
     }
 
     fn is_send_sync() -> bool {
@@ -504,6 +604,26 @@ impl<'a, T: 'static> Borrow<'a> for FakeBorrow<T> {
     }
 }
 
+#[cfg(feature = "std")]
+impl<'a, T: 'static + Default + Send + Sync> Borrow<'a> for Local<'a, T> {
+    fn try_borrow(
+        _: &'a AtomicRefCell<AllStorages>,
+        #[cfg(feature = "parallel")] _: &'a rayon::ThreadPool,
+    ) -> Result<Self, error::GetStorage> {
+        Local::try_borrow()
+    }
+
+    fn borrow_infos(_: &mut Vec<(TypeId, &'static str, Mutation)>) {}
+
+    fn is_send_sync() -> bool {
+        true
+    }
+
+    fn local_defaults(defaults: &mut Vec<(TypeId, LocalDefault)>) {
+        defaults.push((TypeId::of::<T>(), || Box::new(T::default())));
+    }
+}
+
 macro_rules! impl_borrow {
     ($(($type: ident, $index: tt))+) => {
         impl<'a, $($type: Borrow<'a>),+> Borrow<'a> for ($($type,)+) {
@@ -525,7 +645,7 @@ macro_rules! impl_borrow {
                 }
             }
 
-            fn borrow_infos(infos: &mut Vec<(TypeId, Mutation)>) {
+            fn borrow_infos(infos: &mut Vec<(TypeId, &'static str, Mutation)>) {
                 $(
                     $type::borrow_infos(infos);
                 )+
@@ -534,6 +654,12 @@ macro_rules! impl_borrow {
             fn is_send_sync() -> bool {
                 $($type::is_send_sync())&&+
             }
+
+            fn local_defaults(defaults: &mut Vec<(TypeId, LocalDefault)>) {
+                $(
+                    $type::local_defaults(defaults);
+                )+
+            }
         }
     }
 }
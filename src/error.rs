@@ -1,5 +1,7 @@
 use crate::EntityId;
 use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
 use core::fmt::{Debug, Display, Formatter};
 #[cfg(feature = "std")]
 use std::error::Error;
@@ -56,6 +58,8 @@ pub enum GetStorage {
     NonUnique((&'static str, Borrow)),
     MissingUnique(&'static str),
     Entities(Borrow),
+    MissingLocal(&'static str),
+    MismatchedStorage(&'static str),
 }
 
 #[cfg(feature = "std")]
@@ -97,6 +101,8 @@ impl Debug for GetStorage {
                 },
                 _ => unreachable!(),
             },
+            Self::MissingLocal(name) => fmt.write_fmt(format_args!("Local<{}> can only be borrowed by a system running inside a workload.", name)),
+            Self::MismatchedStorage(name) => fmt.write_fmt(format_args!("{}'s storage doesn't have the expected kind in the checkpoint or delta.\nIt was probably made by a World where {} is registered differently.", name, name)),
         }
     }
 }
@@ -269,6 +275,60 @@ impl Display for AddWorkload {
     }
 }
 
+/// Error returned by `WorkloadBuilder::try_build`.  
+/// The ordering constraints between systems can't form a cycle, the systems part of it are listed.
+#[derive(Clone, PartialEq, Eq)]
+pub enum Build {
+    Cycle(Vec<&'static str>),
+}
+
+#[cfg(feature = "std")]
+impl Error for Build {}
+
+impl Debug for Build {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            Self::Cycle(names) => {
+                fmt.write_str("Systems can't be ordered, these systems are part of a cycle:")?;
+                for name in names {
+                    fmt.write_fmt(format_args!("\n- {}", name))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Display for Build {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        Debug::fmt(self, fmt)
+    }
+}
+
+/// Error returned by `WorkloadBuilder::try_with_workload`.  
+/// Only workloads already built can be included.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum WithWorkload {
+    MissingWorkload,
+}
+
+#[cfg(feature = "std")]
+impl Error for WithWorkload {}
+
+impl Debug for WithWorkload {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            Self::MissingWorkload => fmt.write_str("No workload with this name exists."),
+        }
+    }
+}
+
+impl Display for WithWorkload {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        Debug::fmt(self, fmt)
+    }
+}
+
 /// Trying to set the default workload to a non existant one will result in this error.
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum SetDefaultWorkload {
@@ -296,12 +356,94 @@ impl Display for SetDefaultWorkload {
     }
 }
 
+/// Error returned by `World::try_remove_workload`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum RemoveWorkload {
+    Borrow,
+    MissingWorkload,
+}
+
+#[cfg(feature = "std")]
+impl Error for RemoveWorkload {}
+
+impl Debug for RemoveWorkload {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            Self::Borrow => {
+                fmt.write_str("Cannot mutably borrow scheduler while it's already borrowed.")
+            }
+            Self::MissingWorkload => fmt.write_str("No workload with this name exists."),
+        }
+    }
+}
+
+impl Display for RemoveWorkload {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        Debug::fmt(self, fmt)
+    }
+}
+
+/// Error returned by `World::try_enable_system` and `World::try_disable_system`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum EnableSystem {
+    Borrow,
+    MissingSystem,
+}
+
+#[cfg(feature = "std")]
+impl Error for EnableSystem {}
+
+impl Debug for EnableSystem {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            Self::Borrow => {
+                fmt.write_str("Cannot mutably borrow scheduler while it's already borrowed.")
+            }
+            Self::MissingSystem => fmt.write_str("No system with this name exists."),
+        }
+    }
+}
+
+impl Display for EnableSystem {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        Debug::fmt(self, fmt)
+    }
+}
+
+/// Error returned by `World::try_workload_info` and `World::try_workloads_info`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum WorkloadInfo {
+    Borrow,
+    MissingWorkload,
+}
+
+#[cfg(feature = "std")]
+impl Error for WorkloadInfo {}
+
+impl Debug for WorkloadInfo {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            Self::Borrow => fmt.write_str("Cannot borrow scheduler while it's already borrowed."),
+            Self::MissingWorkload => fmt.write_str("No workload with this name exists."),
+        }
+    }
+}
+
+impl Display for WorkloadInfo {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        Debug::fmt(self, fmt)
+    }
+}
+
 /// Error related to `run_default` and `run_workload`.  
 /// The error can be a storage error, problem with the scheduler's borrowing, a non existant workload or a custom error.
 pub enum RunWorkload {
     Scheduler,
     Run((&'static str, Run)),
     MissingWorkload,
+    /// All errors of a workload that doesn't stop at the first one, in the order they happened.
+    Errors(Vec<(&'static str, Run)>),
+    Storage(GetStorage),
 }
 
 impl RunWorkload {
@@ -334,6 +476,14 @@ impl Debug for RunWorkload {
             Self::Run((system_name, run)) => {
                 fmt.write_fmt(format_args!("System {} failed: {:?}", system_name, run))
             }
+            Self::Storage(get_storage) => Debug::fmt(get_storage, fmt),
+            Self::Errors(errors) => {
+                fmt.write_fmt(format_args!("{} systems failed:", errors.len()))?;
+                for (system_name, run) in errors {
+                    fmt.write_fmt(format_args!("\n- System {} failed: {:?}", system_name, run))?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -352,6 +502,10 @@ pub enum Run {
     Custom(Box<dyn Error + Send>),
     #[cfg(not(feature = "std"))]
     Custom(Box<dyn core::any::Any + Send>),
+    /// The system panicked, the payload is the value the panic was called with.  
+    /// Only returned when the `World` catches panics.
+    #[cfg(feature = "std")]
+    Panic((&'static str, Box<dyn core::any::Any + Send>)),
 }
 
 impl From<GetStorage> for Run {
@@ -379,6 +533,16 @@ impl Debug for Run {
         match self {
             Self::GetStorage(get_storage) => Debug::fmt(&get_storage, fmt),
             Self::Custom(_) => fmt.write_fmt(format_args!("run failed with a custom error.")),
+            #[cfg(feature = "std")]
+            Self::Panic((system_name, payload)) => {
+                if let Some(message) = payload.downcast_ref::<&str>() {
+                    fmt.write_fmt(format_args!("{} panicked: {}", system_name, message))
+                } else if let Some(message) = payload.downcast_ref::<String>() {
+                    fmt.write_fmt(format_args!("{} panicked: {}", system_name, message))
+                } else {
+                    fmt.write_fmt(format_args!("{} panicked.", system_name))
+                }
+            }
         }
     }
 }
@@ -423,7 +587,7 @@ impl Error for NotUpdatePack {}
 
 impl Debug for NotUpdatePack {
     fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
-        fmt.write_str("The storage isn't update packed. Use `view.update_pack()` to pack it.")
+        fmt.write_str("The storage isn't update packed. Use `view.update_pack()` to pack it. Removed and deleted components can also be tracked with `view.track_removal()`.")
     }
 }
 
@@ -433,6 +597,27 @@ impl Display for NotUpdatePack {
     }
 }
 
+/// Error when trying to use change tick related function on a storage not tracking changes.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct NotTrackingChanges;
+
+#[cfg(feature = "std")]
+impl Error for NotTrackingChanges {}
+
+impl Debug for NotTrackingChanges {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        fmt.write_str(
+            "The storage doesn't track changes. Use `view.track_changes()` to track them.",
+        )
+    }
+}
+
+impl Display for NotTrackingChanges {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        Debug::fmt(self, fmt)
+    }
+}
+
 /// Error when trying to access the *inserted* section of an update packed storage but the storage isn't update packed or the section isn't present in the window.
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Inserted {
@@ -630,3 +815,142 @@ impl Display for UniqueRemove {
         Debug::fmt(self, fmt)
     }
 }
+
+/// Error returned when cloning an entity.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum CloneEntity {
+    EntityIsNotAlive,
+}
+
+#[cfg(feature = "std")]
+impl Error for CloneEntity {}
+
+impl Debug for CloneEntity {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            Self::EntityIsNotAlive => fmt.write_str("Entity has to be alive to be cloned."),
+        }
+    }
+}
+
+impl Display for CloneEntity {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        Debug::fmt(self, fmt)
+    }
+}
+
+/// Error returned when merging a `World` into another.  
+/// Unique storages present in both `World`s are only an error with `UniqueConflict::Error`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Merge {
+    AllStoragesBorrow(Borrow),
+    UniqueConflict(&'static str),
+    Storage(GetStorage),
+}
+
+#[cfg(feature = "std")]
+impl Error for Merge {}
+
+impl Debug for Merge {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            Self::AllStoragesBorrow(borrow) => match borrow {
+                Borrow::Unique => fmt.write_str("Cannot mutably borrow AllStorages while it's already borrowed (AllStorages is borrowed to access any storage)."),
+                Borrow::Shared => {
+                    fmt.write_str("Cannot immutably borrow AllStorages while it's already mutably borrowed.")
+                },
+                _ => unreachable!(),
+            },
+            Self::UniqueConflict(name) => fmt.write_fmt(format_args!(
+                "Both Worlds have a unique storage of type {}.",
+                name
+            )),
+            Self::Storage(get_storage) => Debug::fmt(get_storage, fmt),
+        }
+    }
+}
+
+impl Display for Merge {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        Debug::fmt(self, fmt)
+    }
+}
+
+impl From<GetStorage> for Merge {
+    fn from(get_storage: GetStorage) -> Self {
+        Merge::Storage(get_storage)
+    }
+}
+
+/// Error returned when spawning a prefab.  
+/// The `Prefabs` unique storage has to be present and contain a prefab with the requested name.
+#[derive(Clone, PartialEq, Eq)]
+pub enum SpawnPrefab {
+    MissingPrefab(String),
+    Storage(GetStorage),
+}
+
+#[cfg(feature = "std")]
+impl Error for SpawnPrefab {}
+
+impl Debug for SpawnPrefab {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            Self::MissingPrefab(name) => {
+                fmt.write_fmt(format_args!("No prefab is registered as \"{}\".", name))
+            }
+            Self::Storage(get_storage) => Debug::fmt(get_storage, fmt),
+        }
+    }
+}
+
+impl Display for SpawnPrefab {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        Debug::fmt(self, fmt)
+    }
+}
+
+impl From<GetStorage> for SpawnPrefab {
+    fn from(get_storage: GetStorage) -> Self {
+        SpawnPrefab::Storage(get_storage)
+    }
+}
+
+/// Error returned when registering a type for serialization, snapshots, checkpoints or deltas.  
+/// Keys and types can only be registered once per registry.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Register {
+    AllStoragesBorrow(Borrow),
+    KeyAlreadyUsed(&'static str),
+    TypeAlreadyRegistered(&'static str),
+}
+
+#[cfg(feature = "std")]
+impl Error for Register {}
+
+impl Debug for Register {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            Self::AllStoragesBorrow(borrow) => match borrow {
+                Borrow::Unique => fmt.write_str("Cannot mutably borrow AllStorages while it's already borrowed (AllStorages is borrowed to access any storage)."),
+                Borrow::Shared => {
+                    fmt.write_str("Cannot immutably borrow AllStorages while it's already mutably borrowed.")
+                },
+                _ => unreachable!(),
+            },
+            Self::KeyAlreadyUsed(key) => fmt.write_fmt(format_args!(
+                "The key \"{}\" is already used by another storage.",
+                key
+            )),
+            Self::TypeAlreadyRegistered(name) => {
+                fmt.write_fmt(format_args!("{} is already registered.", name))
+            }
+        }
+    }
+}
+
+impl Display for Register {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        Debug::fmt(self, fmt)
+    }
+}
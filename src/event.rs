@@ -0,0 +1,184 @@
+#[cfg(feature = "std")]
+use crate::borrow::Local;
+use crate::error;
+use crate::storage::AllStorages;
+#[cfg(feature = "std")]
+use crate::view::UniqueView;
+use crate::view::UniqueViewMut;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+/// Unique storage of `T` events, added with `World::add_event`.
+///
+/// Events are double buffered: they're kept during the workload run they're sent in and the next one, then dropped.
+/// Systems send events with `EventWriter` and read them with `EventReader`.
+/// ### Example:
+/// ```
+/// use shipyard::{system, EventReader, EventWriter, UniqueViewMut, World};
+///
+/// struct Damage(u32);
+///
+/// fn attack(mut damages: EventWriter<Damage>) {
+///     damages.send(Damage(5));
+/// }
+///
+/// fn health(mut damages: EventReader<Damage>, mut health: UniqueViewMut<u32>) {
+///     for damage in damages.iter() {
+///         *health -= damage.0;
+///     }
+/// }
+///
+/// let world = World::new();
+/// world.add_unique(100u32);
+/// world.add_event::<Damage>();
+///
+/// world
+///     .add_workload("Fight")
+///     .with_system(system!(attack))
+///     .with_system(system!(health))
+///     .build();
+///
+/// world.run_default();
+/// world.run_default();
+///
+/// world.run(|health: UniqueViewMut<u32>| assert_eq!(*health, 90));
+/// ```
+pub struct Events<T> {
+    // events sent during the previous run
+    previous: Vec<T>,
+    // events sent during the current run
+    current: Vec<T>,
+    // id of the first event in previous
+    start: usize,
+}
+
+impl<T> Default for Events<T> {
+    fn default() -> Self {
+        Events::new()
+    }
+}
+
+impl<T> Events<T> {
+    /// Creates an empty `Events`.
+    pub fn new() -> Self {
+        Events {
+            previous: Vec::new(),
+            current: Vec::new(),
+            start: 0,
+        }
+    }
+    /// Adds `event` to the current buffer.
+    pub fn send(&mut self, event: T) {
+        self.current.push(event);
+    }
+    /// Drops the events of the previous buffer and swaps both buffers.
+    /// The scheduler calls it after each workload run, calling it by hand is only needed to use `Events` outside of workloads.
+    pub fn update(&mut self) {
+        self.start += self.previous.len();
+        self.previous.clear();
+        core::mem::swap(&mut self.previous, &mut self.current);
+    }
+    /// Returns all events still stored, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.previous.iter().chain(self.current.iter())
+    }
+    /// Returns the number of events still stored.
+    pub fn len(&self) -> usize {
+        self.previous.len() + self.current.len()
+    }
+    /// Returns `true` if no event is stored.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Drops all events.
+    /// Readers won't see them even if they didn't read them yet.
+    pub fn clear(&mut self) {
+        self.start += self.len();
+        self.previous.clear();
+        self.current.clear();
+    }
+    /// Id the next event will get.
+    fn end(&self) -> usize {
+        self.start + self.len()
+    }
+}
+
+/// Position of a reader in an `Events` storage.
+///
+/// `EventReader` keeps one for each system, it can be used directly to read events outside of workloads.
+pub struct EventCursor<T> {
+    next: usize,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for EventCursor<T> {
+    fn default() -> Self {
+        EventCursor {
+            next: 0,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> EventCursor<T> {
+    /// Returns the events sent since the last call, oldest first.
+    /// Events dropped before being read are skipped.
+    pub fn read<'e>(&mut self, events: &'e Events<T>) -> impl Iterator<Item = &'e T> {
+        let skip = self.next.saturating_sub(events.start);
+        self.next = events.end();
+        events.iter().skip(skip)
+    }
+}
+
+/// Sends `T` events, borrows `Events<T>` exclusively.
+pub struct EventWriter<'a, T> {
+    events: UniqueViewMut<'a, Events<T>>,
+}
+
+impl<'a, T> EventWriter<'a, T> {
+    pub(crate) fn new(events: UniqueViewMut<'a, Events<T>>) -> Self {
+        EventWriter { events }
+    }
+    /// Sends `event`.
+    pub fn send(&mut self, event: T) {
+        self.events.send(event);
+    }
+}
+
+impl<T> Extend<T> for EventWriter<'_, T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, events: I) {
+        self.events.current.extend(events);
+    }
+}
+
+/// Reads `T` events, borrows `Events<T>` immutably.
+///
+/// Each system has its own cursor, all readers see all events.
+/// It can only be borrowed by a system running inside a workload.
+#[cfg(feature = "std")]
+pub struct EventReader<'a, T> {
+    events: UniqueView<'a, Events<T>>,
+    cursor: Local<'a, EventCursor<T>>,
+}
+
+#[cfg(feature = "std")]
+impl<'a, T> EventReader<'a, T> {
+    pub(crate) fn new(
+        events: UniqueView<'a, Events<T>>,
+        cursor: Local<'a, EventCursor<T>>,
+    ) -> Self {
+        EventReader { events, cursor }
+    }
+    /// Returns the events sent since this system last read them, oldest first.
+    pub fn iter(&mut self) -> impl Iterator<Item = &T> {
+        self.cursor.read(&self.events)
+    }
+}
+
+/// Updates the `T` events, registered by `World::add_event`.
+pub(crate) fn update_events<T: 'static + Send + Sync>(
+    all_storages: &AllStorages,
+) -> Result<(), error::GetStorage> {
+    all_storages.unique_mut::<Events<T>>()?.update();
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use super::{CurrentId, IntoIterator, Shiperator};
+use crate::sparse_set::Changes;
+use crate::EntityId;
+
+/// Iterator over the components of `Changes`.
+pub struct ChangesIter<'w, T> {
+    changes: Changes<'w, T>,
+    current: usize,
+    end: usize,
+    current_id: EntityId,
+}
+
+impl<'w, T> ChangesIter<'w, T> {
+    pub(crate) fn new(changes: Changes<'w, T>) -> Self {
+        ChangesIter {
+            current: 0,
+            end: changes.window().len(),
+            current_id: EntityId::dead(),
+            changes,
+        }
+    }
+}
+
+impl<T> Clone for ChangesIter<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ChangesIter<'_, T> {}
+
+impl<'w, T> Shiperator for ChangesIter<'w, T> {
+    type Item = &'w T;
+
+    fn first_pass(&mut self) -> Option<Self::Item> {
+        let window = self.changes.window();
+
+        while self.current < self.end {
+            let current = self.current;
+            self.current += 1;
+
+            let entity = window.try_id_at(current)?;
+            if self.changes.is_changed(entity) {
+                self.current_id = entity;
+                // SAFE we checked for OOB and the lifetime is ok
+                return Some(unsafe { window.get_at_unbounded(current) });
+            }
+        }
+
+        None
+    }
+    fn post_process(&mut self) {}
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.end - self.current))
+    }
+}
+
+impl<T> CurrentId for ChangesIter<'_, T> {
+    type Id = EntityId;
+
+    unsafe fn current_id(&self) -> Self::Id {
+        self.current_id
+    }
+}
+
+impl<'w, T> core::iter::IntoIterator for ChangesIter<'w, T> {
+    type IntoIter = IntoIterator<Self>;
+    type Item = <Self as Shiperator>::Item;
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIterator(self)
+    }
+}
@@ -1,3 +1,4 @@
+mod changes;
 mod iter;
 mod loose;
 mod non_packed;
@@ -9,6 +10,7 @@ use super::into_abstract::IntoAbstract;
 use super::{CurrentId, DoubleEndedShiperator, ExactSizeShiperator, IntoIterator, Shiperator};
 
 pub use crate::storage::EntitiesIter;
+pub use changes::*;
 pub use iter::*;
 pub use loose::*;
 pub use non_packed::*;
@@ -122,7 +122,7 @@
 //!
 //! - **panic** *(default)* adds panicking functions
 //! - **parallel** *(default)* &mdash; adds parallel iterators and dispatch
-//! - **serde** &mdash; adds (de)serialization support with [serde](https://github.com/serde-rs/serde)
+//! - **serde** &mdash; adds (de)serialization support with [serde](https://github.com/serde-rs/serde), including whole `World`s
 //! - **non_send** &mdash; add methods and types required to work with `!Send` components
 //! - **non_sync** &mdash; add methods and types required to work with `!Sync` components
 //! - **std** *(default)* &mdash; let shipyard use the standard library
@@ -148,6 +148,7 @@ mod borrow;
 mod delete;
 /// Contains all error types.
 pub mod error;
+mod event;
 mod get;
 mod iter;
 mod not;
@@ -170,8 +171,15 @@ pub use crate::borrow::NonSendSync;
 pub use crate::borrow::NonSync;
 #[doc(hidden)]
 pub use crate::borrow::{AllStoragesBorrow, Borrow};
-pub use borrow::FakeBorrow;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use borrow::Local;
+pub use borrow::{FakeBorrow, Mutation};
 pub use delete::Delete;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use event::EventReader;
+pub use event::{EventCursor, EventWriter, Events};
 pub use get::Get;
 pub use iter::{
     iterators, CurrentId, Enumerate, ExactSizeShiperator, Filter, IntoIter, IntoIterIds, Map,
@@ -181,10 +189,13 @@ pub use not::Not;
 pub use pack::{LoosePack, TightPack};
 pub use remove::Remove;
 pub use sparse_set::{
-    sort, sort::IntoSortable, AddComponentUnchecked, Contains, OldComponent, SparseSet, Window,
-    WindowMut,
+    sort, sort::IntoSortable, AddComponentUnchecked, Changes, ComponentDelta, Contains,
+    OldComponent, SparseSet, Window, WindowMut,
+};
+pub use storage::{
+    AllStorages, Checkpoint, CommandComponents, Commands, DeleteAny, Delta, Entities, EntityId,
+    EntityMap, MapEntities, Prefab, PrefabComponents, Prefabs, Snapshot, UniqueConflict,
 };
-pub use storage::{AllStorages, DeleteAny, Entities, EntityId};
 #[doc(hidden)]
 pub use system::{AllSystem, Nothing, System};
 #[cfg(feature = "parallel")]
@@ -192,4 +203,9 @@ pub use view::ThreadPoolView;
 pub use view::{
     AllStoragesViewMut, EntitiesView, EntitiesViewMut, UniqueView, UniqueViewMut, View, ViewMut,
 };
-pub use world::{WorkloadBuilder, World};
+pub use world::{
+    Conflict, ErrorPolicy, FixedTime, SystemInfo, WorkloadBuilder, WorkloadInfo, World,
+};
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use world::{Profiler, SystemTiming, TimingProfiler};
@@ -139,7 +139,7 @@ macro_rules! impl_remove {
                 }
 
                 Ok(($(
-                    self.$index.actual_remove(entity),
+                    self.$index.notified_remove(entity),
                 )+))
             }
             #[cfg(feature = "panic")]
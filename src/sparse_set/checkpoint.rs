@@ -0,0 +1,62 @@
+use super::{Pack, RemovalTracking, SparseIndex, SparseSet, UpdatePack, BUCKET_SIZE};
+use crate::storage::EntityId;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+/// Copy of a `SparseSet`'s content and pack state.
+pub(crate) struct SparseSetCheckpoint<T> {
+    pub(super) sparse: Vec<Option<Box<[SparseIndex; BUCKET_SIZE]>>>,
+    pub(super) dense: Vec<EntityId>,
+    pub(super) data: Vec<T>,
+    shared: usize,
+    pack_len: usize,
+    update: Option<UpdatePack<T>>,
+    removal: Option<RemovalTracking<T>>,
+}
+
+impl<T: Clone> SparseSet<T> {
+    /// Clones the content and pack state of the sparse set.
+    pub(crate) fn checkpoint(&self) -> SparseSetCheckpoint<T> {
+        let (pack_len, update) = match &self.pack_info.pack {
+            Pack::Tight(pack) => (pack.len, None),
+            Pack::Loose(pack) => (pack.len, None),
+            Pack::Update(pack) => (0, Some(clone_update_pack(pack))),
+            Pack::NoPack => (0, None),
+        };
+
+        SparseSetCheckpoint {
+            sparse: self.sparse.clone(),
+            dense: self.dense.clone(),
+            data: self.data.clone(),
+            shared: self.shared,
+            pack_len,
+            update,
+            removal: self.pack_info.removal.clone(),
+        }
+    }
+    /// Replaces the sparse set's content with the one in `checkpoint`.
+    /// The pack is kept, only its state is restored.
+    pub(crate) fn restore_checkpoint(&mut self, checkpoint: &SparseSetCheckpoint<T>) {
+        self.sparse.clone_from(&checkpoint.sparse);
+        self.dense.clone_from(&checkpoint.dense);
+        self.data.clone_from(&checkpoint.data);
+        self.shared = checkpoint.shared;
+
+        self.restore_pack_state(
+            checkpoint.pack_len,
+            checkpoint.update.as_ref().map(clone_update_pack),
+        );
+        if self.pack_info.removal.is_some() {
+            self.pack_info.removal = Some(checkpoint.removal.clone().unwrap_or_default());
+        }
+    }
+}
+
+fn clone_update_pack<T: Clone>(pack: &UpdatePack<T>) -> UpdatePack<T> {
+    UpdatePack {
+        inserted: pack.inserted,
+        modified: pack.modified,
+        removed: pack.removed.clone(),
+        deleted: pack.deleted.clone(),
+    }
+}
@@ -0,0 +1,120 @@
+use super::{OldComponent, Pack, SparseIndex, SparseSet, SparseSetCheckpoint, BUCKET_SIZE};
+use crate::storage::EntityId;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::any::TypeId;
+
+/// Components added, changed and removed from a storage since a `Checkpoint`.
+///
+/// Shared components are not tracked.
+pub struct ComponentDelta<T> {
+    added: Vec<(EntityId, T)>,
+    changed: Vec<(EntityId, T)>,
+    removed: Vec<EntityId>,
+}
+
+impl<T> ComponentDelta<T> {
+    /// Returns the components added to an entity, with their current value.
+    pub fn added(&self) -> &[(EntityId, T)] {
+        &self.added
+    }
+    /// Returns the components whose value changed, with their current value.
+    pub fn changed(&self) -> &[(EntityId, T)] {
+        &self.changed
+    }
+    /// Returns the entities that lost their component.
+    pub fn removed(&self) -> &[EntityId] {
+        &self.removed
+    }
+    /// Returns `true` if nothing was added, changed or removed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl<T: Clone + PartialEq> SparseSet<T> {
+    /// Compares the sparse set with its state in `previous`.
+    /// A missing `previous` is considered empty.
+    pub(crate) fn delta(&self, previous: Option<&SparseSetCheckpoint<T>>) -> ComponentDelta<T> {
+        let mut delta = ComponentDelta {
+            added: Vec::new(),
+            changed: Vec::new(),
+            removed: Vec::new(),
+        };
+
+        for (&entity, component) in self.dense.iter().zip(&self.data) {
+            let old_component = previous.and_then(|previous| {
+                owned_index(&previous.sparse, &previous.dense, entity)
+                    .map(|index| &previous.data[index])
+            });
+
+            match old_component {
+                Some(old_component) if old_component != component => {
+                    delta.changed.push((entity, component.clone()))
+                }
+                Some(_) => {}
+                None => delta.added.push((entity, component.clone())),
+            }
+        }
+
+        if let Some(previous) = previous {
+            delta.removed.extend(
+                previous
+                    .dense
+                    .iter()
+                    .copied()
+                    .filter(|&entity| owned_index(&self.sparse, &self.dense, entity).is_none()),
+            );
+        }
+
+        delta
+    }
+    /// Removes, adds and updates components to match `delta`.
+    /// Returns the entities that lost their component, they have to be unpacked from the observer storages.
+    pub(crate) fn apply_delta(&mut self, delta: &ComponentDelta<T>) -> Vec<EntityId> {
+        let mut removed = Vec::new();
+        for &entity in &delta.removed {
+            if let Some(OldComponent::Owned(_)) = self.actual_remove(entity) {
+                if let Some(removed) = self.pack_info.removed_mut() {
+                    removed.push(entity);
+                }
+
+                removed.push(entity);
+            }
+        }
+
+        for (entity, component) in delta.added.iter().chain(&delta.changed) {
+            self.insert(component.clone(), *entity);
+        }
+
+        removed
+    }
+    /// Returns the storages an entity has to be unpacked from when it loses its component.
+    pub(crate) fn unpack_types(&self) -> Vec<TypeId> {
+        let mut types = self.pack_info.observer_types.clone();
+        match &self.pack_info.pack {
+            Pack::Tight(pack) => types.extend_from_slice(&pack.types),
+            Pack::Loose(pack) => types.extend_from_slice(&pack.tight_types),
+            Pack::Update(_) | Pack::NoPack => {}
+        }
+
+        types
+    }
+}
+
+/// Returns the index of `entity`'s component if it owns one.
+pub(super) fn owned_index(
+    sparse: &[Option<Box<[SparseIndex; BUCKET_SIZE]>>],
+    dense: &[EntityId],
+    entity: EntityId,
+) -> Option<usize> {
+    let bucket = sparse.get(entity.bucket())?.as_ref()?;
+    // SAFE bucket_index is always in bound and both variants are plain old data
+    let index = unsafe { bucket.get_unchecked(entity.bucket_index()).owned };
+
+    if dense.get(index) == Some(&entity) {
+        Some(index)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,60 @@
+use super::SparseSet;
+use crate::storage::EntityId;
+use alloc::boxed::Box;
+
+type Hook<T> = Box<dyn Fn(EntityId, &T) + Send + Sync>;
+
+/// Callbacks called synchronously when a component is added or leaves its storage.
+pub(crate) struct Hooks<T> {
+    pub(crate) on_insert: Option<Hook<T>>,
+    pub(crate) on_remove: Option<Hook<T>>,
+    pub(crate) on_delete: Option<Hook<T>>,
+}
+
+impl<T> Default for Hooks<T> {
+    fn default() -> Self {
+        Hooks {
+            on_insert: None,
+            on_remove: None,
+            on_delete: None,
+        }
+    }
+}
+
+impl<T> SparseSet<T> {
+    /// Sets the callback called after a component is added to this storage, replacing the previous one.
+    /// It's also called when a component is replaced, with the new component.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{EntitiesViewMut, EntityId, ViewMut, World};
+    ///
+    /// let world = World::new();
+    ///
+    /// world.run(|mut u32s: ViewMut<u32>| {
+    ///     u32s.on_insert(|entity: EntityId, value: &u32| println!("{:?}: {}", entity, value));
+    /// });
+    ///
+    /// world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>| {
+    ///     entities.add_entity(&mut u32s, 0);
+    /// });
+    /// ```
+    pub fn on_insert<F: Fn(EntityId, &T) + Send + Sync + 'static>(&mut self, hook: F) {
+        self.hooks.on_insert = Some(Box::new(hook));
+    }
+    /// Sets the callback called when a component is removed from this storage, replacing the previous one.
+    /// It's called before the component is returned to the caller or moved to another `World`.
+    pub fn on_remove<F: Fn(EntityId, &T) + Send + Sync + 'static>(&mut self, hook: F) {
+        self.hooks.on_remove = Some(Box::new(hook));
+    }
+    /// Sets the callback called before a component is deleted from this storage, replacing the previous one.
+    /// It's called by `delete`, `clear` and when the entity is deleted or stripped.  
+    /// Hooks are not called when a `World` is restored from a checkpoint, a snapshot or serialized data.
+    pub fn on_delete<F: Fn(EntityId, &T) + Send + Sync + 'static>(&mut self, hook: F) {
+        self.hooks.on_delete = Some(Box::new(hook));
+    }
+    /// Removes all callbacks of this storage.
+    pub fn clear_hooks(&mut self) {
+        self.hooks = Hooks::default();
+    }
+}
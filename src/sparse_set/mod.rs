@@ -1,20 +1,34 @@
 mod add_component;
+mod checkpoint;
 mod contains;
+mod delta;
+mod hooks;
 mod pack_info;
+#[cfg(feature = "serde")]
+mod serde;
+mod snapshot;
 pub mod sort;
+mod ticks;
 mod view_add_entity;
 mod windows;
 
 pub use add_component::AddComponentUnchecked;
 pub use contains::Contains;
-pub use windows::{Window, WindowMut, WindowSort1};
+pub use delta::ComponentDelta;
+pub use windows::{Changes, Window, WindowMut, WindowSort1};
 
-pub(crate) use pack_info::{LoosePack, Pack, PackInfo, TightPack, UpdatePack};
+#[cfg(feature = "serde")]
+pub(crate) use self::serde::{LoadedSparseSet, LoadedSparseSetSeed, SparseSetParts};
+pub(crate) use checkpoint::SparseSetCheckpoint;
+use hooks::Hooks;
+pub(crate) use pack_info::{LoosePack, Pack, PackInfo, RemovalTracking, TightPack, UpdatePack};
+pub(crate) use ticks::{Change, ChangeTicks, ComponentTicks};
 pub(crate) use view_add_entity::ViewAddEntity;
 pub(crate) use windows::RawWindowMut;
 
+use crate::atomic_refcell::AtomicRefCell;
 use crate::error;
-use crate::storage::EntityId;
+use crate::storage::{EntityId, Storage};
 use crate::unknown_storage::UnknownStorage;
 use alloc::boxed::Box;
 use alloc::vec::Vec;
@@ -47,6 +61,9 @@ pub struct SparseSet<T> {
     pub(crate) data: Vec<T>,
     pub(crate) pack_info: PackInfo<T>,
     shared: usize,
+    hooks: Hooks<T>,
+    // tick of the current borrow, kept even when not tracking changes for track_changes
+    change_tick: u64,
 }
 
 impl<T> SparseSet<T> {
@@ -57,6 +74,8 @@ impl<T> SparseSet<T> {
             data: Vec::new(),
             pack_info: Default::default(),
             shared: 0,
+            hooks: Default::default(),
+            change_tick: 0,
         }
     }
     pub(crate) fn window(&self) -> Window<'_, T> {
@@ -68,6 +87,20 @@ impl<T> SparseSet<T> {
     pub(crate) fn raw_window_mut(&mut self) -> RawWindowMut<'_, T> {
         self.window_mut().into_raw()
     }
+    /// Adds the storages observing this one to `storage_to_unpack`, keeping it sorted.
+    fn storage_to_unpack(&self, storage_to_unpack: &mut Vec<TypeId>) {
+        storage_to_unpack.reserve(self.pack_info.observer_types.len());
+
+        let mut i = 0;
+        for observer in self.pack_info.observer_types.iter().copied() {
+            while i < storage_to_unpack.len() && observer < storage_to_unpack[i] {
+                i += 1;
+            }
+            if storage_to_unpack.is_empty() || observer != storage_to_unpack[i] {
+                storage_to_unpack.insert(i, observer);
+            }
+        }
+    }
     pub(crate) fn allocate_at(&mut self, entity: EntityId) {
         if entity.bucket() >= self.sparse.len() {
             self.sparse.resize(entity.bucket() + 1, None);
@@ -211,6 +244,19 @@ impl<T> SparseSet<T> {
             }
         }
 
+        if let Some(ticks) = &mut self.pack_info.ticks {
+            match old_component {
+                // replacing a component modifies it, like in update packs
+                Some(OldComponent::Owned(_)) => ticks.modify(entity),
+                Some(OldComponent::Shared) | None => ticks.insert(entity),
+            }
+        }
+
+        if let Some(hook) = &self.hooks.on_insert {
+            // the component was just inserted
+            hook(entity, self.get(entity).unwrap());
+        }
+
         old_component
     }
     /// Removes `entity`'s component from this storage.
@@ -222,20 +268,7 @@ impl<T> SparseSet<T> {
             match self.pack_info.pack {
                 Pack::Tight(_) => Err(error::Remove::MissingPackStorage(type_name::<T>())),
                 Pack::Loose(_) => Err(error::Remove::MissingPackStorage(type_name::<T>())),
-                Pack::Update(_) => {
-                    let component = self.actual_remove(entity);
-
-                    if let Some(OldComponent::Owned(_)) = &component {
-                        if let Pack::Update(update) = &mut self.pack_info.pack {
-                            update.removed.push(entity);
-                        } else {
-                            unreachable!()
-                        }
-                    }
-
-                    Ok(component)
-                }
-                Pack::NoPack => Ok(self.actual_remove(entity)),
+                Pack::Update(_) | Pack::NoPack => Ok(self.notified_remove(entity)),
             }
         } else {
             Err(error::Remove::MissingPackStorage(type_name::<T>()))
@@ -251,6 +284,21 @@ impl<T> SparseSet<T> {
     {
         self.try_remove(entity).unwrap()
     }
+    /// Removes `entity`'s component, records it if the storage tracks removal and calls the `on_remove` hook.
+    pub(crate) fn notified_remove(&mut self, entity: EntityId) -> Option<OldComponent<T>> {
+        let component = self.actual_remove(entity);
+
+        if let Some(OldComponent::Owned(component)) = &component {
+            if let Some(removed) = self.pack_info.removed_mut() {
+                removed.push(entity);
+            }
+            if let Some(hook) = &self.hooks.on_remove {
+                hook(entity, component);
+            }
+        }
+
+        component
+    }
     pub(crate) fn actual_remove(&mut self, entity: EntityId) -> Option<OldComponent<T>> {
         unsafe {
             match self.sparse_index(entity) {
@@ -421,8 +469,12 @@ impl<T> SparseSet<T> {
     }
     pub(crate) fn actual_delete(&mut self, entity: EntityId) {
         if let Some(OldComponent::Owned(component)) = self.actual_remove(entity) {
-            if let Pack::Update(pack) = &mut self.pack_info.pack {
-                pack.deleted.push((entity, component));
+            if let Some(hook) = &self.hooks.on_delete {
+                hook(entity, &component);
+            }
+
+            if let Some(deleted) = self.pack_info.deleted_mut() {
+                deleted.push((entity, component));
             }
         }
     }
@@ -505,6 +557,10 @@ impl<T> SparseSet<T> {
                             }
                         }
 
+                        if let Some(ticks) = &mut self.pack_info.ticks {
+                            ticks.modify(entity);
+                        }
+
                         Some(self.data.get_unchecked_mut(index))
                     } else {
                         None
@@ -621,52 +677,50 @@ impl<T> SparseSet<T> {
     pub fn inserted_or_modified_mut(&mut self) -> WindowMut<'_, T> {
         self.try_inserted_or_modified_mut().unwrap()
     }
-    /// Returns the *deleted* components of an update packed storage.
+    /// Returns the *deleted* components of a storage tracking removal.
     pub fn try_deleted(&self) -> Result<&[(EntityId, T)], error::NotUpdatePack> {
-        if let Pack::Update(pack) = &self.pack_info.pack {
-            Ok(&pack.deleted)
-        } else {
-            Err(error::NotUpdatePack)
-        }
+        self.pack_info
+            .deleted()
+            .map(|deleted| &**deleted)
+            .ok_or(error::NotUpdatePack)
     }
-    /// Returns the *deleted* components of an update packed storage.  
+    /// Returns the *deleted* components of a storage tracking removal.  
     /// Unwraps errors.
     #[cfg(feature = "panic")]
     #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
     pub fn deleted(&self) -> &[(EntityId, T)] {
         self.try_deleted().unwrap()
     }
-    /// Returns the ids of *removed* components of an update packed storage.
+    /// Returns the ids of *removed* components of a storage tracking removal.
     pub fn try_removed(&self) -> Result<&[EntityId], error::NotUpdatePack> {
-        if let Pack::Update(pack) = &self.pack_info.pack {
-            Ok(&pack.removed)
-        } else {
-            Err(error::NotUpdatePack)
-        }
+        self.pack_info
+            .removed()
+            .map(|removed| &**removed)
+            .ok_or(error::NotUpdatePack)
     }
-    /// Returns the ids of *removed* components of an update packed storage.
+    /// Returns the ids of *removed* components of a storage tracking removal.
     /// Unwraps errors.
     #[cfg(feature = "panic")]
     #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
     pub fn removed(&self) -> &[EntityId] {
         self.try_removed().unwrap()
     }
-    /// Takes ownership of the *deleted* components of an update packed storage.
+    /// Takes ownership of the *deleted* components of a storage tracking removal.
     pub fn try_take_deleted(&mut self) -> Result<Vec<(EntityId, T)>, error::NotUpdatePack> {
         self.window_mut().try_take_deleted()
     }
-    /// Takes ownership of the *deleted* components of an update packed storage.  
+    /// Takes ownership of the *deleted* components of a storage tracking removal.  
     /// Unwraps errors.
     #[cfg(feature = "panic")]
     #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
     pub fn take_deleted(&mut self) -> Vec<(EntityId, T)> {
         self.try_take_deleted().unwrap()
     }
-    /// Takes ownership of the ids of *removed* components of an update packed storage.
+    /// Takes ownership of the ids of *removed* components of a storage tracking removal.
     pub fn try_take_removed(&mut self) -> Result<Vec<EntityId>, error::NotUpdatePack> {
         self.window_mut().try_take_removed()
     }
-    /// Takes ownership of the ids of *removed* components of an update packed storage.  
+    /// Takes ownership of the ids of *removed* components of a storage tracking removal.  
     /// Unwraps errors.
     #[cfg(feature = "panic")]
     #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
@@ -706,6 +760,22 @@ impl<T> SparseSet<T> {
     pub fn clear_inserted_and_modified(&mut self) {
         self.try_clear_inserted_and_modified().unwrap()
     }
+    /// Brings back the pack's state after the whole content was replaced.
+    /// `pack_len` is only used by tight and loose packs.
+    /// Without a saved update pack, all components are considered inserted.
+    pub(crate) fn restore_pack_state(&mut self, pack_len: usize, update: Option<UpdatePack<T>>) {
+        let len = self.len();
+        match (&mut self.pack_info.pack, update) {
+            (Pack::Tight(pack), _) => pack.len = pack_len,
+            (Pack::Loose(pack), _) => pack.len = pack_len,
+            (Pack::Update(pack), Some(update)) => *pack = update,
+            (Pack::Update(pack), None) => {
+                pack.inserted = len;
+                pack.modified = 0;
+            }
+            (Pack::NoPack, _) => {}
+        }
+    }
     //          ▼ old end of pack
     //              ▼ new end of pack
     // [_ _ _ _ | _ | _ _ _ _ _]
@@ -774,6 +844,81 @@ impl<T> SparseSet<T> {
     pub(crate) fn clone_indices(&self) -> Vec<EntityId> {
         self.dense.clone()
     }
+    /// Makes this storage track *removed* and *deleted* components, whatever its pack.  
+    /// Update packed storages always track them.
+    pub fn track_removal(&mut self) {
+        if self.pack_info.removed().is_none() {
+            self.pack_info.removal = Some(RemovalTracking::default());
+        }
+    }
+    /// Returns `true` if this storage tracks *removed* and *deleted* components.
+    pub fn is_tracking_removal(&self) -> bool {
+        self.pack_info.removed().is_some()
+    }
+    /// Makes this storage keep the tick at which each component was inserted and last modified.  
+    /// Components already present are considered unchanged since tick 0.
+    ///
+    /// `inserted`, `modified` and `inserted_or_modified` on a view then return the components changed since the system borrowing it last ran,
+    /// several systems can react to the same changes without clearing anything.  
+    /// Ticks can also be compared to any tick with `inserted_since`, `modified_since` and `inserted_or_modified_since`.
+    pub fn track_changes(&mut self) {
+        if self.pack_info.ticks.is_none() {
+            self.pack_info.ticks = Some(ChangeTicks::new(&self.dense, self.change_tick));
+        }
+    }
+    /// Returns `true` if this storage keeps change ticks.
+    pub fn is_tracking_changes(&self) -> bool {
+        self.pack_info.ticks.is_some()
+    }
+    /// Sets the tick stamped on the components inserted or modified until the next borrow.
+    pub(crate) fn set_change_tick(&mut self, tick: u64) {
+        self.change_tick = tick;
+        if let Some(ticks) = &mut self.pack_info.ticks {
+            ticks.current = tick;
+        }
+    }
+    /// Returns the components inserted after `tick`.
+    pub fn try_inserted_since(
+        &self,
+        tick: u64,
+    ) -> Result<impl Iterator<Item = (EntityId, &T)>, error::NotTrackingChanges> {
+        self.window().try_inserted_since(tick)
+    }
+    /// Returns the components inserted after `tick`.  
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn inserted_since(&self, tick: u64) -> impl Iterator<Item = (EntityId, &T)> {
+        self.try_inserted_since(tick).unwrap()
+    }
+    /// Returns the components modified after `tick`, except the ones inserted after it.
+    pub fn try_modified_since(
+        &self,
+        tick: u64,
+    ) -> Result<impl Iterator<Item = (EntityId, &T)>, error::NotTrackingChanges> {
+        self.window().try_modified_since(tick)
+    }
+    /// Returns the components modified after `tick`, except the ones inserted after it.  
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn modified_since(&self, tick: u64) -> impl Iterator<Item = (EntityId, &T)> {
+        self.try_modified_since(tick).unwrap()
+    }
+    /// Returns the components inserted or modified after `tick`.
+    pub fn try_inserted_or_modified_since(
+        &self,
+        tick: u64,
+    ) -> Result<impl Iterator<Item = (EntityId, &T)>, error::NotTrackingChanges> {
+        self.window().try_inserted_or_modified_since(tick)
+    }
+    /// Returns the components inserted or modified after `tick`.  
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn inserted_or_modified_since(&self, tick: u64) -> impl Iterator<Item = (EntityId, &T)> {
+        self.try_inserted_or_modified_since(tick).unwrap()
+    }
     /// Update packs this storage making it track *inserted*, *modified* and *deleted* components.
     pub fn try_update_pack(&mut self) -> Result<(), error::Pack>
     where
@@ -781,11 +926,13 @@ impl<T> SparseSet<T> {
     {
         match self.pack_info.pack {
             Pack::NoPack => {
+                // the update pack takes over removal tracking
+                let removal = self.pack_info.removal.take().unwrap_or_default();
                 self.pack_info.pack = Pack::Update(UpdatePack {
                     inserted: self.len(),
                     modified: 0,
-                    removed: Vec::new(),
-                    deleted: Vec::new(),
+                    removed: removal.removed,
+                    deleted: removal.deleted,
                 });
                 Ok(())
             }
@@ -811,20 +958,45 @@ impl<T> SparseSet<T> {
     }
     /// Deletes all components in this storage.
     pub fn clear(&mut self) {
+        if let Some(hook) = &self.hooks.on_delete {
+            for (&id, component) in self.dense.iter().zip(&self.data) {
+                hook(id, component);
+            }
+        }
+        self.delete_all();
+    }
+    /// Deletes all components in this storage without calling the `on_delete` hook.
+    fn delete_all(&mut self) {
         for id in &self.dense {
             self.sparse[id.bucket()].as_mut().unwrap()[id.bucket_index()].owned = core::usize::MAX;
         }
         match &mut self.pack_info.pack {
             Pack::Tight(tight) => tight.len = 0,
             Pack::Loose(loose) => loose.len = 0,
-            Pack::Update(update) => update
-                .deleted
-                .extend(self.dense.drain(..).zip(self.data.drain(..))),
-            Pack::NoPack => {}
+            Pack::Update(_) | Pack::NoPack => {}
+        }
+        if let Some(deleted) = self.pack_info.deleted_mut() {
+            deleted.extend(self.dense.drain(..).zip(self.data.drain(..)));
         }
         self.dense.clear();
         self.data.clear();
     }
+    /// Drops all components in this storage without calling the `on_delete` hook nor recording them as deleted.  
+    /// Used before replacing the whole content, removal tracking and update pack are emptied as well.
+    pub(crate) fn actual_clear(&mut self) {
+        self.delete_all();
+
+        if let Pack::Update(update) = &mut self.pack_info.pack {
+            update.inserted = 0;
+            update.modified = 0;
+            update.removed.clear();
+            update.deleted.clear();
+        }
+        if let Some(removal) = &mut self.pack_info.removal {
+            removal.removed.clear();
+            removal.deleted.clear();
+        }
+    }
     /// Returns the `EntityId` at a given `index`.
     pub fn try_id_at(&self, index: usize) -> Option<EntityId> {
         self.dense.get(index).copied()
@@ -1118,25 +1290,74 @@ impl<T> core::ops::IndexMut<EntityId> for SparseSet<T> {
 impl<T: 'static> UnknownStorage for SparseSet<T> {
     fn delete(&mut self, entity: EntityId, storage_to_unpack: &mut Vec<TypeId>) {
         self.actual_delete(entity);
-
-        storage_to_unpack.reserve(self.pack_info.observer_types.len());
-
-        let mut i = 0;
-        for observer in self.pack_info.observer_types.iter().copied() {
-            while i < storage_to_unpack.len() && observer < storage_to_unpack[i] {
-                i += 1;
-            }
-            if storage_to_unpack.is_empty() || observer != storage_to_unpack[i] {
-                storage_to_unpack.insert(i, observer);
-            }
-        }
+        self.storage_to_unpack(storage_to_unpack);
+    }
+    fn remove(&mut self, entity: EntityId, storage_to_unpack: &mut Vec<TypeId>) {
+        self.notified_remove(entity);
+        self.storage_to_unpack(storage_to_unpack);
     }
     fn clear(&mut self) {
         <Self>::clear(self)
     }
+    fn actual_clear(&mut self) {
+        <Self>::actual_clear(self)
+    }
     fn unpack(&mut self, entity: EntityId) {
         Self::unpack(self, entity);
     }
+    fn contains(&self, entity: EntityId) -> bool {
+        delta::owned_index(&self.sparse, &self.dense, entity).is_some()
+    }
+    fn move_component(
+        &mut self,
+        entity: EntityId,
+        other: &mut dyn UnknownStorage,
+        target: EntityId,
+        storage_to_unpack: &mut Vec<TypeId>,
+    ) -> bool {
+        if let Some(other) = other.any_mut().downcast_mut::<Self>() {
+            if let Some(OldComponent::Owned(component)) = self.notified_remove(entity) {
+                other.insert(component, target);
+            }
+
+            self.storage_to_unpack(storage_to_unpack);
+
+            true
+        } else {
+            false
+        }
+    }
+    fn new_empty(&self, like: &AtomicRefCell<dyn UnknownStorage>) -> Option<Storage> {
+        Some(Storage(Box::new(like.new_like(SparseSet::<T>::new()))))
+    }
+    fn pack(&mut self, entity: EntityId, components: &[TypeId]) {
+        let packable = match &self.pack_info.pack {
+            Pack::Tight(pack) => pack.is_packable(components).is_ok(),
+            Pack::Loose(pack) => pack.is_packable(components).is_ok(),
+            Pack::Update(_) | Pack::NoPack => false,
+        };
+
+        if packable {
+            Self::pack(self, entity);
+        }
+    }
+    fn reset_pack(&mut self) -> Vec<EntityId> {
+        match &mut self.pack_info.pack {
+            Pack::Tight(pack) => pack.len = 0,
+            Pack::Loose(pack) => pack.len = 0,
+            Pack::Update(_) | Pack::NoPack => return Vec::new(),
+        }
+
+        self.dense.clone()
+    }
+    fn reset_ticks(&mut self, tick: u64) {
+        if let Some(ticks) = &mut self.pack_info.ticks {
+            ticks.reset(&self.dense, tick);
+        }
+    }
+    fn type_name(&self) -> &'static str {
+        type_name::<T>()
+    }
     fn any(&self) -> &dyn Any {
         self
     }
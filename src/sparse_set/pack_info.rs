@@ -1,3 +1,4 @@
+use super::ChangeTicks;
 use crate::storage::EntityId;
 use alloc::sync::Arc;
 use alloc::vec::Vec;
@@ -23,6 +24,9 @@ impl<T> Pack<T> {
 pub struct PackInfo<T> {
     pub(crate) pack: Pack<T>,
     pub(crate) observer_types: Vec<TypeId>,
+    // update packs track removal on their own
+    pub(crate) removal: Option<RemovalTracking<T>>,
+    pub(crate) ticks: Option<ChangeTicks>,
 }
 
 impl<T> Default for PackInfo<T> {
@@ -30,11 +34,45 @@ impl<T> Default for PackInfo<T> {
         PackInfo {
             pack: Pack::NoPack,
             observer_types: Vec::new(),
+            removal: None,
+            ticks: None,
         }
     }
 }
 
 impl<T> PackInfo<T> {
+    /// Returns the ids of *removed* components if the storage tracks removal.
+    pub(crate) fn removed(&self) -> Option<&Vec<EntityId>> {
+        match (&self.pack, &self.removal) {
+            (Pack::Update(pack), _) => Some(&pack.removed),
+            (_, Some(removal)) => Some(&removal.removed),
+            _ => None,
+        }
+    }
+    /// Returns the ids of *removed* components mutably if the storage tracks removal.
+    pub(crate) fn removed_mut(&mut self) -> Option<&mut Vec<EntityId>> {
+        match (&mut self.pack, &mut self.removal) {
+            (Pack::Update(pack), _) => Some(&mut pack.removed),
+            (_, Some(removal)) => Some(&mut removal.removed),
+            _ => None,
+        }
+    }
+    /// Returns the *deleted* components if the storage tracks removal.
+    pub(crate) fn deleted(&self) -> Option<&Vec<(EntityId, T)>> {
+        match (&self.pack, &self.removal) {
+            (Pack::Update(pack), _) => Some(&pack.deleted),
+            (_, Some(removal)) => Some(&removal.deleted),
+            _ => None,
+        }
+    }
+    /// Returns the *deleted* components mutably if the storage tracks removal.
+    pub(crate) fn deleted_mut(&mut self) -> Option<&mut Vec<(EntityId, T)>> {
+        match (&mut self.pack, &mut self.removal) {
+            (Pack::Update(pack), _) => Some(&mut pack.deleted),
+            (_, Some(removal)) => Some(&mut removal.deleted),
+            _ => None,
+        }
+    }
     /// Returns `true` if enough storages were passed in
     pub(crate) fn has_all_storages(&self, components: &[TypeId], additionals: &[TypeId]) -> bool {
         match &self.pack {
@@ -602,3 +640,19 @@ pub(crate) struct UpdatePack<T> {
     pub(crate) removed: Vec<EntityId>,
     pub(crate) deleted: Vec<(EntityId, T)>,
 }
+
+/// *Removed* and *deleted* components of a storage tracking removal without being update packed.
+#[derive(Clone)]
+pub(crate) struct RemovalTracking<T> {
+    pub(crate) removed: Vec<EntityId>,
+    pub(crate) deleted: Vec<(EntityId, T)>,
+}
+
+impl<T> Default for RemovalTracking<T> {
+    fn default() -> Self {
+        RemovalTracking {
+            removed: Vec::new(),
+            deleted: Vec::new(),
+        }
+    }
+}
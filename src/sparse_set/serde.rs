@@ -0,0 +1,601 @@
+use super::delta::owned_index;
+use super::{Pack, PackInfo, SparseIndex, SparseSet, UpdatePack, BUCKET_SIZE};
+use crate::storage::{EntityId, EntityMap};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt::Formatter;
+use core::marker::PhantomData;
+use serde::de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+const FIELDS: &[&str] = &["components", "shared", "pack_len", "update"];
+const UPDATE_FIELDS: &[&str] = &["inserted", "modified", "removed", "deleted"];
+
+/// Borrowed parts of a storage, enough to serialize it.
+pub(crate) struct SparseSetParts<'a, T> {
+    pub(crate) sparse: &'a [Option<Box<[SparseIndex; BUCKET_SIZE]>>],
+    pub(crate) dense: &'a [EntityId],
+    pub(crate) data: &'a [T],
+    pub(crate) pack_info: &'a PackInfo<T>,
+    pub(crate) shared: usize,
+}
+
+impl<'a, T> SparseSetParts<'a, T> {
+    /// Iterates over `(index, owner)` pairs, index being the one of the entity observing `owner`'s component.
+    fn shared_components(&self) -> impl Iterator<Item = (u64, EntityId)> + 'a {
+        let dense = self.dense;
+        let sparse = if self.shared == 0 {
+            &[][..]
+        } else {
+            self.sparse
+        };
+
+        sparse
+            .iter()
+            .enumerate()
+            .filter_map(|(bucket, indices)| Some((bucket, indices.as_ref()?)))
+            .flat_map(move |(bucket, indices)| {
+                indices.iter().enumerate().filter_map(move |(i, index)| {
+                    let entity_index = bucket * BUCKET_SIZE + i;
+                    // SAFE an owned index is either usize::MAX or points back to entity_index in dense
+                    unsafe {
+                        match *index {
+                            SparseIndex { owned }
+                                if owned == usize::MAX
+                                    || dense.get(owned).map(|id| id.uindex())
+                                        == Some(entity_index) =>
+                            {
+                                None
+                            }
+                            SparseIndex { shared } => Some((entity_index as u64, shared)),
+                        }
+                    }
+                })
+            })
+    }
+}
+
+impl<T> SparseSet<T> {
+    pub(crate) fn parts(&self) -> SparseSetParts<'_, T> {
+        SparseSetParts {
+            sparse: &self.sparse,
+            dense: &self.dense,
+            data: &self.data,
+            pack_info: &self.pack_info,
+            shared: self.shared,
+        }
+    }
+}
+
+/// Serializes the components as `(EntityId, T)` pairs in the storage's order.
+///
+/// Shared components are serialized as `(index, EntityId)` pairs, the index being the one of the entity observing.
+/// Tight and loose packs only save their length, update packs save their *inserted*, *modified*, *removed* and *deleted* components.
+impl<T: Serialize> Serialize for SparseSet<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.parts().serialize(serializer)
+    }
+}
+
+impl<T: Serialize> Serialize for SparseSetParts<'_, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        struct Components<'a, T>(&'a SparseSetParts<'a, T>);
+
+        impl<T: Serialize> Serialize for Components<'_, T> {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                serializer.collect_seq(self.0.dense.iter().zip(self.0.data))
+            }
+        }
+
+        struct Shared<'a, T>(&'a SparseSetParts<'a, T>);
+
+        impl<T> Serialize for Shared<'_, T> {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                serializer.collect_seq(self.0.shared_components())
+            }
+        }
+
+        struct Update<'a, T>(&'a UpdatePack<T>);
+
+        impl<T: Serialize> Serialize for Update<'_, T> {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                let mut state = serializer.serialize_struct("UpdatePack", 4)?;
+                state.serialize_field("inserted", &self.0.inserted)?;
+                state.serialize_field("modified", &self.0.modified)?;
+                state.serialize_field("removed", &self.0.removed)?;
+                state.serialize_field("deleted", &self.0.deleted)?;
+                state.end()
+            }
+        }
+
+        let (pack_len, update) = match &self.pack_info.pack {
+            Pack::Tight(pack) => (pack.len, None),
+            Pack::Loose(pack) => (pack.len, None),
+            Pack::Update(pack) => (0, Some(Update(pack))),
+            Pack::NoPack => (0, None),
+        };
+
+        let mut state = serializer.serialize_struct("SparseSet", 4)?;
+        state.serialize_field("components", &Components(self))?;
+        state.serialize_field("shared", &Shared(self))?;
+        state.serialize_field("pack_len", &pack_len)?;
+        state.serialize_field("update", &update)?;
+        state.end()
+    }
+}
+
+/// Deserializes a storage on its own.
+///
+/// Tight and loose packs can't exist outside a `World`, their length is ignored.  
+/// Without the `World`'s entities, entities observing a shared component have to be in the range of the entities owning one.
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for SparseSet<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        LoadedSparseSet::deserialize(deserializer).map(|loaded| loaded.sparse_set)
+    }
+}
+
+/// Replaces all components in the storage with the deserialized ones.
+///
+/// The storage stays in the same pack. When it's tight or loose packed, all storages in the pack should be loaded from the same save.
+impl<'de, T: Deserialize<'de>> DeserializeSeed<'de> for &mut SparseSet<T> {
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        LoadedSparseSet::deserialize(deserializer)?.load_into(self);
+        Ok(())
+    }
+}
+
+/// Storage deserialized on its own, ready to be loaded in a `SparseSet`.
+pub(crate) struct LoadedSparseSet<T> {
+    sparse_set: SparseSet<T>,
+    pack_len: usize,
+}
+
+impl<T> LoadedSparseSet<T> {
+    /// Replaces `sparse_set`'s components with the loaded ones.
+    /// The pack `sparse_set` is part of is kept.
+    pub(crate) fn load_into(self, sparse_set: &mut SparseSet<T>) {
+        let LoadedSparseSet {
+            sparse_set: loaded,
+            pack_len,
+        } = self;
+
+        sparse_set.sparse = loaded.sparse;
+        sparse_set.dense = loaded.dense;
+        sparse_set.data = loaded.data;
+        sparse_set.shared = loaded.shared;
+
+        let update = match loaded.pack_info.pack {
+            Pack::Update(update) => Some(update),
+            _ => None,
+        };
+        sparse_set.restore_pack_state(pack_len, update);
+    }
+    /// Adds the loaded components to `sparse_set`, each one to the entity its original one is mapped to.  
+    /// Components of entities missing from `entity_map` are dropped.
+    /// Returns the entities that received a component.
+    pub(crate) fn add_into(
+        self,
+        sparse_set: &mut SparseSet<T>,
+        entity_map: &EntityMap,
+        mut map_component: impl FnMut(&mut T),
+    ) -> Vec<EntityId> {
+        let shared: Vec<_> = self
+            .sparse_set
+            .parts()
+            .shared_components()
+            .filter_map(|(index, owner)| Some((entity_map.get_index(index)?, owner)))
+            .collect();
+
+        let mut entities = Vec::with_capacity(self.sparse_set.len());
+        sparse_set.reserve(self.sparse_set.len());
+
+        for (entity, mut component) in self.sparse_set.dense.into_iter().zip(self.sparse_set.data) {
+            if let Some(entity) = entity_map.get(entity) {
+                map_component(&mut component);
+                sparse_set.insert(component, entity);
+                entities.push(entity);
+            }
+        }
+
+        for (observer, owner) in shared {
+            sparse_set.share(entity_map.map(owner), observer);
+        }
+
+        entities
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for LoadedSparseSet<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            "SparseSet",
+            FIELDS,
+            SparseSetVisitor {
+                index_count: None,
+                phantom: PhantomData,
+            },
+        )
+    }
+}
+
+/// Deserializes a storage of a `World` whose entities are already loaded.
+pub(crate) struct LoadedSparseSetSeed<T> {
+    // number of indices given to the World's entities
+    index_count: Option<usize>,
+    phantom: PhantomData<T>,
+}
+
+impl<T> LoadedSparseSetSeed<T> {
+    /// Shared components will only be accepted for entities with an index lower than `index_count`.
+    pub(crate) fn new(index_count: Option<usize>) -> Self {
+        LoadedSparseSetSeed {
+            index_count,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> DeserializeSeed<'de> for LoadedSparseSetSeed<T> {
+    type Value = LoadedSparseSet<T>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            "SparseSet",
+            FIELDS,
+            SparseSetVisitor {
+                index_count: self.index_count,
+                phantom: PhantomData,
+            },
+        )
+    }
+}
+
+enum Field {
+    Components,
+    Shared,
+    PackLen,
+    Update,
+}
+
+impl<'de> Deserialize<'de> for Field {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct FieldVisitor;
+
+        impl<'de> Visitor<'de> for FieldVisitor {
+            type Value = Field;
+
+            fn expecting(&self, formatter: &mut Formatter<'_>) -> core::fmt::Result {
+                formatter.write_str("`components`, `shared`, `pack_len` or `update`")
+            }
+            fn visit_str<E>(self, value: &str) -> Result<Field, E>
+            where
+                E: de::Error,
+            {
+                match value {
+                    "components" => Ok(Field::Components),
+                    "shared" => Ok(Field::Shared),
+                    "pack_len" => Ok(Field::PackLen),
+                    "update" => Ok(Field::Update),
+                    _ => Err(de::Error::unknown_field(value, FIELDS)),
+                }
+            }
+        }
+
+        deserializer.deserialize_identifier(FieldVisitor)
+    }
+}
+
+struct SparseSetVisitor<T> {
+    index_count: Option<usize>,
+    phantom: PhantomData<T>,
+}
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for SparseSetVisitor<T> {
+    type Value = LoadedSparseSet<T>;
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> core::fmt::Result {
+        formatter.write_str("struct SparseSet")
+    }
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let components = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let shared = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        let pack_len = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+        let update = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(3, &self))?;
+
+        load(components, shared, pack_len, update, self.index_count)
+    }
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut components = None;
+        let mut shared = None;
+        let mut pack_len = None;
+        let mut update = None;
+
+        while let Some(key) = map.next_key()? {
+            match key {
+                Field::Components => {
+                    if components.is_some() {
+                        return Err(de::Error::duplicate_field("components"));
+                    }
+                    components = Some(map.next_value()?);
+                }
+                Field::Shared => {
+                    if shared.is_some() {
+                        return Err(de::Error::duplicate_field("shared"));
+                    }
+                    shared = Some(map.next_value()?);
+                }
+                Field::PackLen => {
+                    if pack_len.is_some() {
+                        return Err(de::Error::duplicate_field("pack_len"));
+                    }
+                    pack_len = Some(map.next_value()?);
+                }
+                Field::Update => {
+                    if update.is_some() {
+                        return Err(de::Error::duplicate_field("update"));
+                    }
+                    update = Some(map.next_value()?);
+                }
+            }
+        }
+
+        let components = components.ok_or_else(|| de::Error::missing_field("components"))?;
+
+        load(
+            components,
+            shared.unwrap_or_default(),
+            pack_len.unwrap_or(0),
+            update.unwrap_or_default(),
+            self.index_count,
+        )
+    }
+}
+
+fn load<T, E: de::Error>(
+    components: Vec<(EntityId, T)>,
+    shared: Vec<(u64, EntityId)>,
+    pack_len: usize,
+    update: Option<UpdatePack<T>>,
+    index_count: Option<usize>,
+) -> Result<LoadedSparseSet<T>, E> {
+    let mut sparse_set = SparseSet::new();
+    sparse_set.reserve(components.len());
+
+    for (entity, component) in components {
+        if sparse_set.insert(component, entity).is_some() {
+            return Err(de::Error::custom(format_args!(
+                "{:?} has more than one component",
+                entity
+            )));
+        }
+    }
+
+    // without the World's entities, observers have to be in the range of the entities owning a component
+    let index_count = index_count.unwrap_or(sparse_set.sparse.len() * BUCKET_SIZE) as u64;
+    for (index, owner) in shared {
+        if index >= index_count {
+            return Err(de::Error::custom(format_args!(
+                "the entity at index {} observes a component but is out of the loaded entities' range",
+                index
+            )));
+        }
+
+        let observer = EntityId::new(index);
+        // SAFE both variants are plain old data
+        let observer_owns = sparse_set
+            .sparse_index(observer)
+            .map(|sparse_index| unsafe { sparse_index.owned })
+            .and_then(|owned| sparse_set.dense.get(owned))
+            .map(|entity| entity.index())
+            == Some(index);
+        if observer_owns {
+            return Err(de::Error::custom(format_args!(
+                "the entity at index {} both owns and observes a component",
+                index
+            )));
+        }
+
+        if owned_index(&sparse_set.sparse, &sparse_set.dense, owner).is_none() {
+            return Err(de::Error::custom(format_args!(
+                "{:?} is observed but doesn't have a component",
+                owner
+            )));
+        }
+
+        sparse_set.share(owner, observer);
+    }
+
+    if pack_len > sparse_set.len() {
+        return Err(de::Error::custom(
+            "the pack is longer than the number of components",
+        ));
+    }
+
+    if let Some(update) = update {
+        if update.inserted + update.modified > sparse_set.len() {
+            return Err(de::Error::custom(
+                "there are more inserted and modified components than components",
+            ));
+        }
+
+        sparse_set.pack_info.pack = Pack::Update(update);
+    }
+
+    Ok(LoadedSparseSet {
+        sparse_set,
+        pack_len,
+    })
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for UpdatePack<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct("UpdatePack", UPDATE_FIELDS, UpdateVisitor(PhantomData))
+    }
+}
+
+enum UpdateField {
+    Inserted,
+    Modified,
+    Removed,
+    Deleted,
+}
+
+impl<'de> Deserialize<'de> for UpdateField {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct FieldVisitor;
+
+        impl<'de> Visitor<'de> for FieldVisitor {
+            type Value = UpdateField;
+
+            fn expecting(&self, formatter: &mut Formatter<'_>) -> core::fmt::Result {
+                formatter.write_str("`inserted`, `modified`, `removed` or `deleted`")
+            }
+            fn visit_str<E>(self, value: &str) -> Result<UpdateField, E>
+            where
+                E: de::Error,
+            {
+                match value {
+                    "inserted" => Ok(UpdateField::Inserted),
+                    "modified" => Ok(UpdateField::Modified),
+                    "removed" => Ok(UpdateField::Removed),
+                    "deleted" => Ok(UpdateField::Deleted),
+                    _ => Err(de::Error::unknown_field(value, UPDATE_FIELDS)),
+                }
+            }
+        }
+
+        deserializer.deserialize_identifier(FieldVisitor)
+    }
+}
+
+struct UpdateVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for UpdateVisitor<T> {
+    type Value = UpdatePack<T>;
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> core::fmt::Result {
+        formatter.write_str("struct UpdatePack")
+    }
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let inserted = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let modified = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        let removed = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+        let deleted = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(3, &self))?;
+
+        Ok(UpdatePack {
+            inserted,
+            modified,
+            removed,
+            deleted,
+        })
+    }
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut inserted = None;
+        let mut modified = None;
+        let mut removed = None;
+        let mut deleted = None;
+
+        while let Some(key) = map.next_key()? {
+            match key {
+                UpdateField::Inserted => {
+                    if inserted.is_some() {
+                        return Err(de::Error::duplicate_field("inserted"));
+                    }
+                    inserted = Some(map.next_value()?);
+                }
+                UpdateField::Modified => {
+                    if modified.is_some() {
+                        return Err(de::Error::duplicate_field("modified"));
+                    }
+                    modified = Some(map.next_value()?);
+                }
+                UpdateField::Removed => {
+                    if removed.is_some() {
+                        return Err(de::Error::duplicate_field("removed"));
+                    }
+                    removed = Some(map.next_value()?);
+                }
+                UpdateField::Deleted => {
+                    if deleted.is_some() {
+                        return Err(de::Error::duplicate_field("deleted"));
+                    }
+                    deleted = Some(map.next_value()?);
+                }
+            }
+        }
+
+        Ok(UpdatePack {
+            inserted: inserted.ok_or_else(|| de::Error::missing_field("inserted"))?,
+            modified: modified.ok_or_else(|| de::Error::missing_field("modified"))?,
+            removed: removed.unwrap_or_default(),
+            deleted: deleted.unwrap_or_default(),
+        })
+    }
+}
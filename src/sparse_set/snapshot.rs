@@ -0,0 +1,86 @@
+use super::{Pack, SparseIndex, SparseSet, UpdatePack, BUCKET_SIZE};
+use crate::storage::{write_raw, EntityId, RawReader};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::mem::MaybeUninit;
+
+impl<T: Copy> SparseSet<T> {
+    /// Copies the sparse set's memory at the end of `buffer`.
+    pub(crate) fn write_snapshot(&self, buffer: &mut Vec<MaybeUninit<u8>>) {
+        let pack_len = match &self.pack_info.pack {
+            Pack::Tight(pack) => pack.len,
+            Pack::Loose(pack) => pack.len,
+            Pack::Update(_) | Pack::NoPack => 0,
+        };
+
+        write_raw(
+            buffer,
+            &[self.dense.len(), self.sparse.len(), self.shared, pack_len],
+        );
+        write_raw(buffer, &self.dense);
+        write_raw(buffer, &self.data);
+
+        for bucket in &self.sparse {
+            match bucket {
+                Some(bucket) => {
+                    write_raw(buffer, &[true]);
+                    write_raw(buffer, &**bucket);
+                }
+                None => write_raw(buffer, &[false]),
+            }
+        }
+
+        match &self.pack_info.pack {
+            Pack::Update(pack) => {
+                write_raw(buffer, &[true]);
+                write_raw(
+                    buffer,
+                    &[
+                        pack.inserted,
+                        pack.modified,
+                        pack.removed.len(),
+                        pack.deleted.len(),
+                    ],
+                );
+                write_raw(buffer, &pack.removed);
+                write_raw(buffer, &pack.deleted);
+            }
+            _ => write_raw(buffer, &[false]),
+        }
+    }
+    /// Replaces the sparse set's content with the one written by `write_snapshot`.
+    /// The pack is kept, only its state is restored.
+    pub(crate) fn restore_snapshot(&mut self, reader: &mut RawReader<'_>) {
+        let [len, bucket_count, shared, pack_len] = reader.read::<[usize; 4]>();
+
+        self.dense = reader.read_vec(len);
+        self.data = reader.read_vec(len);
+        self.shared = shared;
+
+        self.sparse.clear();
+        self.sparse.reserve(bucket_count);
+        for _ in 0..bucket_count {
+            if reader.read::<bool>() {
+                self.sparse
+                    .push(Some(Box::new(reader.read::<[SparseIndex; BUCKET_SIZE]>())));
+            } else {
+                self.sparse.push(None);
+            }
+        }
+
+        let update = if reader.read::<bool>() {
+            let [inserted, modified, removed_len, deleted_len] = reader.read::<[usize; 4]>();
+
+            Some(UpdatePack {
+                inserted,
+                modified,
+                removed: reader.read_vec::<EntityId>(removed_len),
+                deleted: reader.read_vec::<(EntityId, T)>(deleted_len),
+            })
+        } else {
+            None
+        };
+
+        self.restore_pack_state(pack_len, update);
+    }
+}
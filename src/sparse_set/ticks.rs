@@ -0,0 +1,95 @@
+use super::BUCKET_SIZE;
+use crate::storage::EntityId;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+/// Ticks at which a component was inserted and last modified.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct ComponentTicks {
+    pub(crate) inserted: u64,
+    pub(crate) modified: u64,
+}
+
+/// Kind of change a component went through.
+#[derive(Clone, Copy)]
+pub(crate) enum Change {
+    Inserted,
+    // excludes the components inserted after the tick
+    Modified,
+    InsertedOrModified,
+}
+
+impl ComponentTicks {
+    /// Returns `true` if the component went through `change` after `tick`.
+    pub(crate) fn changed(self, change: Change, tick: u64) -> bool {
+        match change {
+            Change::Inserted => self.inserted > tick,
+            Change::Modified => self.modified > tick && self.inserted <= tick,
+            // inserting a component also sets its modification tick
+            Change::InsertedOrModified => self.modified > tick,
+        }
+    }
+}
+
+/// Change ticks of a storage tracking changes.
+// indexed like the sparse array so components can move in dense without moving their ticks
+pub(crate) struct ChangeTicks {
+    // tick stamped on the components inserted or modified through the current borrow
+    pub(crate) current: u64,
+    pub(crate) buckets: Vec<Option<Box<[ComponentTicks; BUCKET_SIZE]>>>,
+}
+
+impl ChangeTicks {
+    /// Creates the ticks of a storage containing `dense`, its components are unchanged since tick 0.  
+    /// `current` is the tick of the borrow creating them.
+    pub(crate) fn new(dense: &[EntityId], current: u64) -> Self {
+        let mut ticks = ChangeTicks {
+            current,
+            buckets: Vec::new(),
+        };
+
+        for &entity in dense {
+            ticks.ticks_mut(entity);
+        }
+
+        ticks
+    }
+    fn ticks_mut(&mut self, entity: EntityId) -> &mut ComponentTicks {
+        if entity.bucket() >= self.buckets.len() {
+            self.buckets.resize_with(entity.bucket() + 1, || None);
+        }
+
+        &mut self.buckets[entity.bucket()]
+            .get_or_insert_with(|| Box::new([ComponentTicks::default(); BUCKET_SIZE]))
+            [entity.bucket_index()]
+    }
+    /// Forgets all ticks, components in `dense` are considered inserted at `tick`.
+    pub(crate) fn reset(&mut self, dense: &[EntityId], tick: u64) {
+        self.buckets.clear();
+
+        for &entity in dense {
+            *self.ticks_mut(entity) = ComponentTicks {
+                inserted: tick,
+                modified: tick,
+            };
+        }
+    }
+    pub(crate) fn insert(&mut self, entity: EntityId) {
+        let current = self.current;
+        *self.ticks_mut(entity) = ComponentTicks {
+            inserted: current,
+            modified: current,
+        };
+    }
+    pub(crate) fn modify(&mut self, entity: EntityId) {
+        let current = self.current;
+        self.ticks_mut(entity).modified = current;
+    }
+    pub(crate) fn get(&self, entity: EntityId) -> ComponentTicks {
+        self.buckets
+            .get(entity.bucket())
+            .and_then(Option::as_ref)
+            .map(|bucket| bucket[entity.bucket_index()])
+            .unwrap_or_default()
+    }
+}
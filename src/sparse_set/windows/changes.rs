@@ -0,0 +1,152 @@
+use super::Window;
+use crate::error;
+use crate::iter::ChangesIter;
+use crate::sparse_set::Change;
+use crate::EntityId;
+use core::ops::{Deref, Index};
+
+/// Components of a view inserted and/or modified since the system borrowing it last ran.
+///
+/// When the storage tracks changes, components are filtered with their change ticks and each system sees the changes made since its previous run.
+/// Otherwise it's the matching section of the update pack, shared by all systems until it's cleared.
+///
+/// Derefs to the window the changes are taken from.
+pub struct Changes<'w, T> {
+    window: Window<'w, T>,
+    // `None` when `window` is a section of the update pack
+    since: Option<(Change, u64)>,
+}
+
+impl<T> Clone for Changes<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Changes<'_, T> {}
+
+impl<'w, T> Changes<'w, T> {
+    /// Returns the components of `window` inserted after `last_run` or the *inserted* section of the update pack.
+    pub(crate) fn try_inserted(
+        window: Window<'w, T>,
+        last_run: u64,
+    ) -> Result<Self, error::Inserted> {
+        if window.pack_info.ticks.is_some() {
+            Ok(Changes::since(window, Change::Inserted, last_run))
+        } else {
+            window.try_inserted().map(Changes::section)
+        }
+    }
+    /// Returns the components of `window` modified after `last_run` or the *modified* section of the update pack.
+    pub(crate) fn try_modified(
+        window: Window<'w, T>,
+        last_run: u64,
+    ) -> Result<Self, error::Modified> {
+        if window.pack_info.ticks.is_some() {
+            Ok(Changes::since(window, Change::Modified, last_run))
+        } else {
+            window.try_modified().map(Changes::section)
+        }
+    }
+    /// Returns the components of `window` inserted or modified after `last_run` or the *inserted* and *modified* sections of the update pack.
+    pub(crate) fn try_inserted_or_modified(
+        window: Window<'w, T>,
+        last_run: u64,
+    ) -> Result<Self, error::InsertedOrModified> {
+        if window.pack_info.ticks.is_some() {
+            Ok(Changes::since(window, Change::InsertedOrModified, last_run))
+        } else {
+            window.try_inserted_or_modified().map(Changes::section)
+        }
+    }
+    fn since(window: Window<'w, T>, change: Change, tick: u64) -> Self {
+        Changes {
+            window,
+            since: Some((change, tick)),
+        }
+    }
+    fn section(window: Window<'w, T>) -> Self {
+        Changes {
+            window,
+            since: None,
+        }
+    }
+    pub(crate) fn window(&self) -> Window<'w, T> {
+        self.window
+    }
+    /// Returns `true` if `entity`'s component, present in the window, is part of the changes.
+    pub(crate) fn is_changed(&self, entity: EntityId) -> bool {
+        match (self.since, &self.window.pack_info.ticks) {
+            (Some((change, tick)), Some(ticks)) => ticks.get(entity).changed(change, tick),
+            _ => true,
+        }
+    }
+    /// Returns `true` if `entity`'s component is part of the changes.
+    pub fn contains(&self, entity: EntityId) -> bool {
+        self.window.contains(entity) && self.is_changed(entity)
+    }
+    /// Returns the number of components changed.
+    pub fn len(&self) -> usize {
+        match self.since {
+            Some(_) => self
+                .window
+                .dense
+                .iter()
+                .filter(|&&entity| self.is_changed(entity))
+                .count(),
+            None => self.window.len(),
+        }
+    }
+    /// Returns `true` if no component changed.
+    pub fn is_empty(&self) -> bool {
+        !self
+            .window
+            .dense
+            .iter()
+            .any(|&entity| self.is_changed(entity))
+    }
+    pub(crate) fn get(&self, entity: EntityId) -> Option<&'w T> {
+        let index = self.window.index_of(entity)?;
+
+        if self.is_changed(entity) {
+            // SAFE index_of returns an index in bound
+            Some(unsafe { self.window.get_at_unbounded(index) })
+        } else {
+            None
+        }
+    }
+    /// Returns an iterator over the changed components.
+    pub fn iter(&self) -> ChangesIter<'w, T> {
+        ChangesIter::new(*self)
+    }
+    /// Returns a parallel iterator over the changed components.
+    #[cfg(feature = "parallel")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "parallel")))]
+    pub fn par_iter(&self) -> impl rayon::iter::ParallelIterator<Item = &'w T>
+    where
+        T: 'static + Sync,
+    {
+        use crate::iter::{IntoIter, Shiperator};
+        use rayon::iter::{Either, ParallelBridge};
+
+        match self.since {
+            // components changed since a tick aren't contiguous
+            Some(_) => Either::Left(self.iter().into_iterator().par_bridge()),
+            None => Either::Right(self.window.par_iter()),
+        }
+    }
+}
+
+impl<'w, T> Deref for Changes<'w, T> {
+    type Target = Window<'w, T>;
+    fn deref(&self) -> &Self::Target {
+        &self.window
+    }
+}
+
+impl<T> Index<EntityId> for Changes<'_, T> {
+    type Output = T;
+    fn index(&self, entity: EntityId) -> &Self::Output {
+        self.get(entity).unwrap()
+    }
+}
@@ -1,8 +1,12 @@
+mod changes;
 mod sort;
 
+pub use changes::Changes;
 pub use sort::WindowSort1;
 
-use super::{Pack, PackInfo};
+#[cfg(feature = "serde")]
+use super::SparseSetParts;
+use super::{Change, ComponentTicks, Pack, PackInfo};
 use super::{SparseIndex, SparseSet};
 use crate::error;
 use crate::EntityId;
@@ -88,14 +92,14 @@ impl<'w, T> Window<'w, T> {
         self.dense.as_ptr()
     }
     /// Returns the *inserted* section of an update packed window.
-    pub fn try_inserted(&self) -> Result<Window<'_, T>, error::Inserted> {
+    pub fn try_inserted(&self) -> Result<Window<'w, T>, error::Inserted> {
         if let Pack::Update(pack) = &self.pack_info.pack {
             if self.offset == 0 && self.len() >= pack.inserted {
                 Ok(Window {
-                    sparse: &self.sparse,
+                    sparse: self.sparse,
                     dense: &self.dense[0..pack.inserted],
                     data: &self.data[0..pack.inserted],
-                    pack_info: &self.pack_info,
+                    pack_info: self.pack_info,
                     offset: 0,
                     shared: self.shared,
                 })
@@ -110,20 +114,20 @@ impl<'w, T> Window<'w, T> {
     /// Unwraps errors.
     #[cfg(feature = "panic")]
     #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
-    pub fn inserted(&self) -> Window<'_, T> {
+    pub fn inserted(&self) -> Window<'w, T> {
         self.try_inserted().unwrap()
     }
     /// Returns the *modified* section of an update packed window.
-    pub fn try_modified(&self) -> Result<Window<'_, T>, error::Modified> {
+    pub fn try_modified(&self) -> Result<Window<'w, T>, error::Modified> {
         if let Pack::Update(pack) = &self.pack_info.pack {
             if self.offset <= pack.inserted && self.len() >= pack.modified {
                 Ok(Window {
-                    sparse: &self.sparse,
+                    sparse: self.sparse,
                     dense: &self.dense[(pack.inserted - self.offset)
                         ..(pack.inserted + pack.modified - self.offset)],
                     data: &self.data[(pack.inserted - self.offset)
                         ..(pack.inserted + pack.modified - self.offset)],
-                    pack_info: &self.pack_info,
+                    pack_info: self.pack_info,
                     offset: (pack.inserted - self.offset),
                     shared: self.shared,
                 })
@@ -138,18 +142,18 @@ impl<'w, T> Window<'w, T> {
     /// Unwraps errors.
     #[cfg(feature = "panic")]
     #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
-    pub fn modified(&self) -> Window<'_, T> {
+    pub fn modified(&self) -> Window<'w, T> {
         self.try_modified().unwrap()
     }
     /// Returns the *inserted* and *modified* section of an update packed window.
-    pub fn try_inserted_or_modified(&self) -> Result<Window<'_, T>, error::InsertedOrModified> {
+    pub fn try_inserted_or_modified(&self) -> Result<Window<'w, T>, error::InsertedOrModified> {
         if let Pack::Update(pack) = &self.pack_info.pack {
             if self.offset == 0 && self.len() >= pack.inserted + pack.modified {
                 Ok(Window {
-                    sparse: &self.sparse,
+                    sparse: self.sparse,
                     dense: &self.dense[0..pack.inserted + pack.modified],
                     data: &self.data[0..pack.inserted + pack.modified],
-                    pack_info: &self.pack_info,
+                    pack_info: self.pack_info,
                     offset: 0,
                     shared: self.shared,
                 })
@@ -164,39 +168,97 @@ impl<'w, T> Window<'w, T> {
     /// Unwraps errors.
     #[cfg(feature = "panic")]
     #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
-    pub fn inserted_or_modified(&self) -> Window<'_, T> {
+    pub fn inserted_or_modified(&self) -> Window<'w, T> {
         self.try_inserted_or_modified().unwrap()
     }
-    /// Returns the *deleted* components of an update packed window.
+    /// Returns the *deleted* components of a window of a storage tracking removal.
     pub fn try_deleted(&self) -> Result<&[(EntityId, T)], error::NotUpdatePack> {
-        if let Pack::Update(pack) = &self.pack_info.pack {
-            Ok(&pack.deleted)
-        } else {
-            Err(error::NotUpdatePack)
-        }
+        self.pack_info
+            .deleted()
+            .map(|deleted| &**deleted)
+            .ok_or(error::NotUpdatePack)
     }
-    /// Returns the *deleted* components of an update packed window.  
+    /// Returns the *deleted* components of a window of a storage tracking removal.  
     /// Unwraps errors.
     #[cfg(feature = "panic")]
     #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
     pub fn deleted(&self) -> &[(EntityId, T)] {
         self.try_deleted().unwrap()
     }
-    /// Returns the ids of *removed* components of an update packed window.
+    /// Returns the ids of *removed* components of a window of a storage tracking removal.
     pub fn try_removed(&self) -> Result<&[EntityId], error::NotUpdatePack> {
-        if let Pack::Update(pack) = &self.pack_info.pack {
-            Ok(&pack.removed)
-        } else {
-            Err(error::NotUpdatePack)
-        }
+        self.pack_info
+            .removed()
+            .map(|removed| &**removed)
+            .ok_or(error::NotUpdatePack)
     }
-    /// Returns the ids of *removed* components of an update packed window.  
+    /// Returns the ids of *removed* components of a window of a storage tracking removal.  
     /// Unwraps errors.
     #[cfg(feature = "panic")]
     #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
     pub fn removed(&self) -> &[EntityId] {
         self.try_removed().unwrap()
     }
+    /// Returns the components of the window inserted after `tick`.
+    pub fn try_inserted_since(
+        &self,
+        tick: u64,
+    ) -> Result<impl Iterator<Item = (EntityId, &'w T)> + 'w, error::NotTrackingChanges> {
+        self.changed_since(Change::Inserted, tick)
+    }
+    /// Returns the components of the window inserted after `tick`.  
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn inserted_since(&self, tick: u64) -> impl Iterator<Item = (EntityId, &'w T)> + 'w {
+        self.try_inserted_since(tick).unwrap()
+    }
+    /// Returns the components of the window modified after `tick`, except the ones inserted after it.
+    pub fn try_modified_since(
+        &self,
+        tick: u64,
+    ) -> Result<impl Iterator<Item = (EntityId, &'w T)> + 'w, error::NotTrackingChanges> {
+        self.changed_since(Change::Modified, tick)
+    }
+    /// Returns the components of the window modified after `tick`, except the ones inserted after it.  
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn modified_since(&self, tick: u64) -> impl Iterator<Item = (EntityId, &'w T)> + 'w {
+        self.try_modified_since(tick).unwrap()
+    }
+    /// Returns the components of the window inserted or modified after `tick`.
+    pub fn try_inserted_or_modified_since(
+        &self,
+        tick: u64,
+    ) -> Result<impl Iterator<Item = (EntityId, &'w T)> + 'w, error::NotTrackingChanges> {
+        self.changed_since(Change::InsertedOrModified, tick)
+    }
+    /// Returns the components of the window inserted or modified after `tick`.  
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn inserted_or_modified_since(
+        &self,
+        tick: u64,
+    ) -> impl Iterator<Item = (EntityId, &'w T)> + 'w {
+        self.try_inserted_or_modified_since(tick).unwrap()
+    }
+    fn changed_since(
+        &self,
+        change: Change,
+        tick: u64,
+    ) -> Result<impl Iterator<Item = (EntityId, &'w T)> + 'w, error::NotTrackingChanges> {
+        let pack_info: &'w PackInfo<T> = self.pack_info;
+        let ticks = pack_info.ticks.as_ref().ok_or(error::NotTrackingChanges)?;
+
+        Ok(self
+            .dense
+            .iter()
+            .copied()
+            .zip(self.data)
+            .filter(move |&(entity, _)| ticks.get(entity).changed(change, tick)))
+    }
     /// Returns the `EntityId` at a given `index`.
     pub fn try_id_at(&self, index: usize) -> Option<EntityId> {
         self.dense.get(index).copied()
@@ -307,6 +369,18 @@ impl<'w, T> Window<'w, T> {
     pub(crate) fn offset(&self) -> usize {
         self.offset
     }
+    /// Returns the parts needed to serialize the storage.
+    /// The window has to cover the whole storage.
+    #[cfg(feature = "serde")]
+    pub(crate) fn parts(&self) -> SparseSetParts<'w, T> {
+        SparseSetParts {
+            sparse: self.sparse,
+            dense: self.dense,
+            data: self.data,
+            pack_info: self.pack_info,
+            shared: self.shared,
+        }
+    }
 }
 
 impl<T> Index<EntityId> for Window<'_, T> {
@@ -351,6 +425,7 @@ impl<'w, T> WindowMut<'w, T> {
         let sparse_len = self.sparse.len();
         let sparse: *mut Option<Box<[SparseIndex; super::BUCKET_SIZE]>> = self.sparse.as_mut_ptr();
         let sparse = sparse as *mut *mut SparseIndex;
+        let (ticks, ticks_len, tick) = raw_ticks(self.pack_info);
 
         RawWindowMut {
             sparse,
@@ -359,6 +434,9 @@ impl<'w, T> WindowMut<'w, T> {
             dense_len: self.dense.len(),
             data: self.data.as_mut_ptr(),
             pack_info: self.pack_info,
+            ticks,
+            ticks_len,
+            tick,
             offset: self.offset,
             shared: self.shared,
             _phantom: PhantomData,
@@ -368,6 +446,7 @@ impl<'w, T> WindowMut<'w, T> {
         let sparse_len = self.sparse.len();
         let sparse: *mut Option<Box<[SparseIndex; super::BUCKET_SIZE]>> = self.sparse.as_mut_ptr();
         let sparse = sparse as *mut *mut SparseIndex;
+        let (ticks, ticks_len, tick) = raw_ticks(self.pack_info);
 
         RawWindowMut {
             sparse,
@@ -376,6 +455,9 @@ impl<'w, T> WindowMut<'w, T> {
             dense_len: self.dense.len(),
             data: self.data.as_mut_ptr(),
             pack_info: self.pack_info,
+            ticks,
+            ticks_len,
+            tick,
             offset: self.offset,
             shared: self.shared,
             _phantom: PhantomData,
@@ -435,6 +517,10 @@ impl<'w, T> WindowMut<'w, T> {
                             }
                         }
 
+                        if let Some(ticks) = &mut self.pack_info.ticks {
+                            ticks.modify(entity);
+                        }
+
                         Some(self.data.get_unchecked_mut(index))
                     } else {
                         None
@@ -615,64 +701,104 @@ impl<'w, T> WindowMut<'w, T> {
     pub fn inserted_or_modified_mut(&mut self) -> WindowMut<'_, T> {
         self.try_inserted_or_modified_mut().unwrap()
     }
-    /// Returns the *deleted* components of an update packed window.
+    /// Returns the *deleted* components of a window of a storage tracking removal.
     pub fn try_deleted(&self) -> Result<&[(EntityId, T)], error::NotUpdatePack> {
-        if let Pack::Update(pack) = &self.pack_info.pack {
-            Ok(&pack.deleted)
-        } else {
-            Err(error::NotUpdatePack)
-        }
+        self.pack_info
+            .deleted()
+            .map(|deleted| &**deleted)
+            .ok_or(error::NotUpdatePack)
     }
-    /// Returns the *deleted* components of an update packed window.  
+    /// Returns the *deleted* components of a window of a storage tracking removal.  
     /// Unwraps errors.
     #[cfg(feature = "panic")]
     #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
     pub fn deleted(&self) -> &[(EntityId, T)] {
         self.try_deleted().unwrap()
     }
-    /// Returns the ids of *removed* components of an update packed window.
+    /// Returns the ids of *removed* components of a window of a storage tracking removal.
     pub fn try_removed(&self) -> Result<&[EntityId], error::NotUpdatePack> {
-        if let Pack::Update(pack) = &self.pack_info.pack {
-            Ok(&pack.removed)
-        } else {
-            Err(error::NotUpdatePack)
-        }
+        self.pack_info
+            .removed()
+            .map(|removed| &**removed)
+            .ok_or(error::NotUpdatePack)
     }
-    /// Returns the ids of *removed* components of an update packed window.  
+    /// Returns the ids of *removed* components of a window of a storage tracking removal.  
     /// Unwraps errors.
     #[cfg(feature = "panic")]
     #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
     pub fn removed(&self) -> &[EntityId] {
         self.try_removed().unwrap()
     }
-    /// Takes ownership of the *deleted* components of an update packed window.
+    /// Returns the components of the window inserted after `tick`.
+    pub fn try_inserted_since(
+        &self,
+        tick: u64,
+    ) -> Result<impl Iterator<Item = (EntityId, &T)>, error::NotTrackingChanges> {
+        self.as_non_mut().try_inserted_since(tick)
+    }
+    /// Returns the components of the window inserted after `tick`.  
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn inserted_since(&self, tick: u64) -> impl Iterator<Item = (EntityId, &T)> {
+        self.try_inserted_since(tick).unwrap()
+    }
+    /// Returns the components of the window modified after `tick`, except the ones inserted after it.
+    pub fn try_modified_since(
+        &self,
+        tick: u64,
+    ) -> Result<impl Iterator<Item = (EntityId, &T)>, error::NotTrackingChanges> {
+        self.as_non_mut().try_modified_since(tick)
+    }
+    /// Returns the components of the window modified after `tick`, except the ones inserted after it.  
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn modified_since(&self, tick: u64) -> impl Iterator<Item = (EntityId, &T)> {
+        self.try_modified_since(tick).unwrap()
+    }
+    /// Returns the components of the window inserted or modified after `tick`.
+    pub fn try_inserted_or_modified_since(
+        &self,
+        tick: u64,
+    ) -> Result<impl Iterator<Item = (EntityId, &T)>, error::NotTrackingChanges> {
+        self.as_non_mut().try_inserted_or_modified_since(tick)
+    }
+    /// Returns the components of the window inserted or modified after `tick`.  
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn inserted_or_modified_since(&self, tick: u64) -> impl Iterator<Item = (EntityId, &T)> {
+        self.try_inserted_or_modified_since(tick).unwrap()
+    }
+    /// Takes ownership of the *deleted* components of a window of a storage tracking removal.
     pub fn try_take_deleted(&mut self) -> Result<Vec<(EntityId, T)>, error::NotUpdatePack> {
-        if let Pack::Update(pack) = &mut self.pack_info.pack {
-            let mut vec = Vec::with_capacity(pack.deleted.capacity());
-            core::mem::swap(&mut vec, &mut pack.deleted);
+        if let Some(deleted) = self.pack_info.deleted_mut() {
+            let mut vec = Vec::with_capacity(deleted.capacity());
+            core::mem::swap(&mut vec, deleted);
             Ok(vec)
         } else {
             Err(error::NotUpdatePack)
         }
     }
-    /// Takes ownership of the *deleted* components of an update packed window.  
+    /// Takes ownership of the *deleted* components of a window of a storage tracking removal.  
     /// Unwraps errors.
     #[cfg(feature = "panic")]
     #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
     pub fn take_deleted(&mut self) -> Vec<(EntityId, T)> {
         self.try_take_deleted().unwrap()
     }
-    /// Takes ownership of the ids of *removed* components of an update packed window.
+    /// Takes ownership of the ids of *removed* components of a window of a storage tracking removal.
     pub fn try_take_removed(&mut self) -> Result<Vec<EntityId>, error::NotUpdatePack> {
-        if let Pack::Update(pack) = &mut self.pack_info.pack {
-            let mut vec = Vec::with_capacity(pack.removed.capacity());
-            core::mem::swap(&mut vec, &mut pack.removed);
+        if let Some(removed) = self.pack_info.removed_mut() {
+            let mut vec = Vec::with_capacity(removed.capacity());
+            core::mem::swap(&mut vec, removed);
             Ok(vec)
         } else {
             Err(error::NotUpdatePack)
         }
     }
-    /// Takes ownership of the ids of *removed* components of an update packed window.  
+    /// Takes ownership of the ids of *removed* components of a window of a storage tracking removal.  
     /// Unwraps errors.
     #[cfg(feature = "panic")]
     #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
@@ -987,6 +1113,10 @@ pub struct RawWindowMut<'a, T> {
     dense_len: usize,
     data: *mut T,
     pack_info: *mut PackInfo<T>,
+    // null if the storage doesn't track changes
+    ticks: *mut *mut ComponentTicks,
+    ticks_len: usize,
+    tick: u64,
     offset: usize,
     shared: usize,
     _phantom: PhantomData<&'a mut T>,
@@ -1054,6 +1184,7 @@ impl<'w, T> RawWindowMut<'w, T> {
     ///
     /// `index` has to be in the interval `[0, self.dense_len)`.
     pub(crate) unsafe fn get_at_unbounded(&self, index: usize) -> &'w mut T {
+        self.modify(index);
         &mut *self.data.add(index)
     }
     /// # Safety
@@ -1063,8 +1194,27 @@ impl<'w, T> RawWindowMut<'w, T> {
         &self,
         range: core::ops::Range<usize>,
     ) -> &'w mut [T] {
+        for index in range.clone() {
+            self.modify(index);
+        }
         core::slice::from_raw_parts_mut(self.data.add(range.start), range.len())
     }
+    /// Stamps the component at `index` with the current tick if the storage tracks changes.
+    /// # Safety
+    ///
+    /// `index` has to be in the interval `[0, self.dense_len)`.
+    unsafe fn modify(&self, index: usize) {
+        if !self.ticks.is_null() {
+            let entity = ptr::read(self.dense.add(index));
+            if entity.bucket() < self.ticks_len {
+                let bucket = ptr::read(self.ticks.add(entity.bucket()));
+                // each entity has its own ticks, parallel iterators never write to the same ones
+                if !bucket.is_null() {
+                    (*bucket.add(entity.bucket_index())).modified = self.tick;
+                }
+            }
+        }
+    }
     pub(crate) fn dense(&self) -> *const EntityId {
         self.dense
     }
@@ -1134,9 +1284,28 @@ impl<T> Clone for RawWindowMut<'_, T> {
             dense_len: self.dense_len,
             data: self.data,
             pack_info: self.pack_info,
+            ticks: self.ticks,
+            ticks_len: self.ticks_len,
+            tick: self.tick,
             offset: self.offset,
             shared: self.shared,
             _phantom: PhantomData,
         }
     }
 }
+
+/// Returns a pointer to the ticks buckets, their count and the current tick.
+fn raw_ticks<T>(pack_info: &mut PackInfo<T>) -> (*mut *mut ComponentTicks, usize, u64) {
+    match &mut pack_info.ticks {
+        Some(ticks) => {
+            let buckets: *mut Option<Box<[ComponentTicks; super::BUCKET_SIZE]>> =
+                ticks.buckets.as_mut_ptr();
+            (
+                buckets as *mut *mut ComponentTicks,
+                ticks.buckets.len(),
+                ticks.current,
+            )
+        }
+        None => (ptr::null_mut(), 0, 0),
+    }
+}
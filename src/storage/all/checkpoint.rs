@@ -0,0 +1,211 @@
+use super::clone_entity::{clone_component, CloneComponentFn};
+use super::delta::{apply_delta_sparse_set, delta_sparse_set, ApplyDeltaFn, DeltaFn};
+use super::AllStorages;
+use crate::error;
+use crate::sparse_set::SparseSetCheckpoint;
+use crate::storage::{Entities, Storage};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::any::{type_name, Any, TypeId};
+use hashbrown::hash_map::Entry;
+use parking_lot::lock_api::RawRwLock as _;
+
+type CheckpointFn = fn(&Storage) -> Result<Box<dyn Any + Send + Sync>, error::GetStorage>;
+pub(super) type RestoreFn = fn(&mut AllStorages, &dyn Any) -> Result<(), error::GetStorage>;
+
+/// Type erased functions to clone a storage registered with `register_clone`, `register_unique_clone` or `register_delta`.
+pub(crate) struct CloneEntry {
+    checkpoint: CheckpointFn,
+    restore: RestoreFn,
+    // unique storages can't be cloned into an entity
+    pub(super) clone_component: Option<CloneComponentFn>,
+    // only present for storages registered with `register_delta`
+    pub(super) delta: Option<(DeltaFn, ApplyDeltaFn)>,
+}
+
+impl CloneEntry {
+    fn new<T: 'static + Send + Sync + Clone>() -> Self {
+        CloneEntry {
+            checkpoint: checkpoint_sparse_set::<T>,
+            restore: restore_sparse_set::<T>,
+            clone_component: Some(clone_component::<T>),
+            delta: None,
+        }
+    }
+    pub(super) fn new_delta<T: 'static + Send + Sync + Clone + PartialEq>() -> Self {
+        CloneEntry {
+            checkpoint: checkpoint_sparse_set::<T>,
+            restore: restore_sparse_set::<T>,
+            clone_component: Some(clone_component::<T>),
+            delta: Some((delta_sparse_set::<T>, apply_delta_sparse_set::<T>)),
+        }
+    }
+    fn new_unique<T: 'static + Send + Sync + Clone>() -> Self {
+        CloneEntry {
+            checkpoint: checkpoint_unique::<T>,
+            restore: restore_unique::<T>,
+            clone_component: None,
+            delta: None,
+        }
+    }
+}
+
+/// Copy of `Entities` and all cloneable storages, made by `AllStorages::checkpoint`.
+pub struct Checkpoint {
+    pub(super) entities: Entities,
+    pub(super) storages: Vec<(TypeId, RestoreFn, Box<dyn Any + Send + Sync>)>,
+}
+
+impl AllStorages {
+    /// Registers `T`'s storage as cloneable, it'll be part of checkpoints.
+    pub fn try_register_clone<T: 'static + Send + Sync + Clone>(
+        &mut self,
+    ) -> Result<(), error::Register> {
+        self.insert_clone_entry::<T>(CloneEntry::new::<T>())
+    }
+    /// Registers `T`'s storage as cloneable, it'll be part of checkpoints.
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn register_clone<T: 'static + Send + Sync + Clone>(&mut self) {
+        self.try_register_clone::<T>().unwrap()
+    }
+    /// Registers `T`'s unique storage as cloneable, it'll be part of checkpoints.
+    pub fn try_register_unique_clone<T: 'static + Send + Sync + Clone>(
+        &mut self,
+    ) -> Result<(), error::Register> {
+        self.insert_clone_entry::<T>(CloneEntry::new_unique::<T>())
+    }
+    /// Registers `T`'s unique storage as cloneable, it'll be part of checkpoints.
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn register_unique_clone<T: 'static + Send + Sync + Clone>(&mut self) {
+        self.try_register_unique_clone::<T>().unwrap()
+    }
+    pub(super) fn insert_clone_entry<T: 'static>(
+        &mut self,
+        entry: CloneEntry,
+    ) -> Result<(), error::Register> {
+        match self.clone_registry.entry(TypeId::of::<T>()) {
+            Entry::Occupied(_) => Err(error::Register::TypeAlreadyRegistered(type_name::<T>())),
+            Entry::Vacant(vacant) => {
+                vacant.insert(entry);
+                Ok(())
+            }
+        }
+    }
+    /// Copies `Entities` and all storages registered as cloneable.
+    /// Registered storages that don't exist yet are skipped.
+    pub fn try_checkpoint(&self) -> Result<Checkpoint, error::GetStorage> {
+        self.lock.lock_shared();
+        // SAFE we locked
+        let storages = unsafe { &*self.storages.get() };
+
+        let result = (|| {
+            // AllStorages is always created with Entities so there's no way to not find it
+            let entities = storages[&TypeId::of::<Entities>()]
+                .entities()
+                .map_err(error::GetStorage::Entities)?
+                .checkpoint();
+
+            let mut saved = Vec::with_capacity(self.clone_registry.len());
+            for (type_id, entry) in &self.clone_registry {
+                if let Some(storage) = storages.get(type_id) {
+                    saved.push((*type_id, entry.restore, (entry.checkpoint)(storage)?));
+                }
+            }
+
+            Ok(Checkpoint {
+                entities,
+                storages: saved,
+            })
+        })();
+
+        self.lock.unlock_shared();
+        result
+    }
+    /// Copies `Entities` and all storages registered as cloneable.
+    /// Registered storages that don't exist yet are skipped.
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn checkpoint(&self) -> Checkpoint {
+        self.try_checkpoint().unwrap()
+    }
+    /// Brings back `Entities` and all storages to the state saved in `checkpoint`.
+    /// Storages that aren't part of the checkpoint are emptied, unique storages are left as is.
+    /// Packs aren't part of the checkpoint, they have to be set up before restoring.
+    /// Tight and loose packs are rebuilt from the restored components.
+    /// Restored components are considered inserted at the tick of the restore.
+    pub fn try_restore(&mut self, checkpoint: &Checkpoint) -> Result<(), error::GetStorage> {
+        self.restore_with(
+            |entities| entities.restore_checkpoint(&checkpoint.entities),
+            |all_storages| {
+                for (_, restore, storage) in &checkpoint.storages {
+                    restore(all_storages, &**storage)?;
+                }
+
+                Ok(())
+            },
+        )
+    }
+    /// Brings back `Entities` and all storages to the state saved in `checkpoint`.
+    /// Storages that aren't part of the checkpoint are emptied, unique storages are left as is.
+    /// Packs aren't part of the checkpoint, they have to be set up before restoring.
+    /// Tight and loose packs are rebuilt from the restored components.
+    /// Restored components are considered inserted at the tick of the restore.
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn restore(&mut self, checkpoint: &Checkpoint) {
+        self.try_restore(checkpoint).unwrap()
+    }
+}
+
+fn checkpoint_sparse_set<T: 'static + Send + Sync + Clone>(
+    storage: &Storage,
+) -> Result<Box<dyn Any + Send + Sync>, error::GetStorage> {
+    Ok(Box::new(storage.sparse_set::<T>()?.checkpoint()))
+}
+
+fn restore_sparse_set<T: 'static + Send + Sync + Clone>(
+    all_storages: &mut AllStorages,
+    checkpoint: &dyn Any,
+) -> Result<(), error::GetStorage> {
+    // the checkpoint always pairs the checkpoint and restore functions of the same type
+    let checkpoint = checkpoint.downcast_ref::<SparseSetCheckpoint<T>>().unwrap();
+
+    all_storages
+        .storages
+        .get_mut()
+        .entry(TypeId::of::<T>())
+        .or_insert_with(Storage::new::<T>)
+        .sparse_set_mut::<T>()?
+        .restore_checkpoint(checkpoint);
+
+    Ok(())
+}
+
+fn checkpoint_unique<T: 'static + Send + Sync + Clone>(
+    storage: &Storage,
+) -> Result<Box<dyn Any + Send + Sync>, error::GetStorage> {
+    Ok(Box::new(storage.unique::<T>()?.clone()))
+}
+
+fn restore_unique<T: 'static + Send + Sync + Clone>(
+    all_storages: &mut AllStorages,
+    checkpoint: &dyn Any,
+) -> Result<(), error::GetStorage> {
+    // the checkpoint always pairs the checkpoint and restore functions of the same type
+    let unique = checkpoint.downcast_ref::<T>().unwrap().clone();
+
+    match all_storages.storages.get_mut().entry(TypeId::of::<T>()) {
+        Entry::Occupied(entry) => *entry.get().unique_mut::<T>()? = unique,
+        Entry::Vacant(entry) => {
+            entry.insert(Storage::new_unique::<T>(unique));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use super::AllStorages;
+use crate::error;
+use crate::storage::{Entities, EntityId, Storage};
+use alloc::vec::Vec;
+use core::any::TypeId;
+
+pub(super) type CloneComponentFn = fn(&mut Storage, EntityId, EntityId);
+
+impl AllStorages {
+    /// Creates a new entity with a clone of all `entity`'s cloneable components.
+    /// Returns the new entity and the names of the components that were skipped because they aren't registered with `register_clone`.  
+    /// Shared components are not cloned.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{AllStoragesViewMut, EntitiesViewMut, View, ViewMut, World};
+    ///
+    /// let world = World::new();
+    /// world.register_clone::<String>();
+    ///
+    /// let entity = world.run(|mut entities: EntitiesViewMut, mut strings: ViewMut<String>| {
+    ///     entities.add_entity(&mut strings, String::from("goblin"))
+    /// });
+    ///
+    /// let (clone, skipped) = world.run(|mut all_storages: AllStoragesViewMut| {
+    ///     all_storages.clone_entity(entity)
+    /// });
+    /// assert!(skipped.is_empty());
+    ///
+    /// world.run(|strings: View<String>| {
+    ///     assert_eq!(strings[entity], "goblin");
+    ///     assert_eq!(strings[clone], "goblin");
+    /// });
+    /// ```
+    pub fn try_clone_entity(
+        &mut self,
+        entity: EntityId,
+    ) -> Result<(EntityId, Vec<&'static str>), error::CloneEntity> {
+        let (types, skipped) = self.cloneable_components(entity)?;
+
+        // we have unique access to all storages so we can unwrap
+        let clone = self.storages.get_mut()[&TypeId::of::<Entities>()]
+            .entities_mut()
+            .unwrap()
+            .generate();
+
+        self.clone_components(entity, clone, &types);
+
+        Ok((clone, skipped))
+    }
+    /// Creates a new entity with a clone of all `entity`'s cloneable components.
+    /// Returns the new entity and the names of the components that were skipped because they aren't registered with `register_clone`.  
+    /// Shared components are not cloned.  
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn clone_entity(&mut self, entity: EntityId) -> (EntityId, Vec<&'static str>) {
+        self.try_clone_entity(entity).unwrap()
+    }
+    /// Adds a clone of all `entity`'s cloneable components to `target`.
+    /// `target`'s components of the same types are replaced, the others are kept.  
+    /// Returns the names of the components that were skipped because they aren't registered with `register_clone`.  
+    /// Shared components are not cloned.
+    pub fn try_clone_entity_into(
+        &mut self,
+        entity: EntityId,
+        target: EntityId,
+    ) -> Result<Vec<&'static str>, error::CloneEntity> {
+        // we have unique access to all storages so we can unwrap
+        if !self.entities().unwrap().is_alive(target) {
+            return Err(error::CloneEntity::EntityIsNotAlive);
+        }
+
+        let (types, skipped) = self.cloneable_components(entity)?;
+
+        if entity != target {
+            self.clone_components(entity, target, &types);
+        }
+
+        Ok(skipped)
+    }
+    /// Adds a clone of all `entity`'s cloneable components to `target`.
+    /// `target`'s components of the same types are replaced, the others are kept.  
+    /// Returns the names of the components that were skipped because they aren't registered with `register_clone`.  
+    /// Shared components are not cloned.  
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn clone_entity_into(&mut self, entity: EntityId, target: EntityId) -> Vec<&'static str> {
+        self.try_clone_entity_into(entity, target).unwrap()
+    }
+    /// Returns the types of `entity`'s cloneable components and the names of the others.
+    fn cloneable_components(
+        &mut self,
+        entity: EntityId,
+    ) -> Result<(Vec<TypeId>, Vec<&'static str>), error::CloneEntity> {
+        // we have unique access to all storages so we can unwrap
+        if !self.entities().unwrap().is_alive(entity) {
+            return Err(error::CloneEntity::EntityIsNotAlive);
+        }
+
+        let mut types = Vec::new();
+        let mut not_cloneable = Vec::new();
+
+        for (type_id, storage) in self.storages.get_mut().iter() {
+            if storage.contains(entity).unwrap() {
+                match self.clone_registry.get(type_id) {
+                    Some(entry) if entry.clone_component.is_some() => types.push(*type_id),
+                    _ => not_cloneable.push(storage.type_name().unwrap()),
+                }
+            }
+        }
+
+        not_cloneable.sort_unstable();
+
+        Ok((types, not_cloneable))
+    }
+    fn clone_components(&mut self, entity: EntityId, target: EntityId, types: &[TypeId]) {
+        let storages = self.storages.get_mut();
+
+        for type_id in types {
+            // types were checked by cloneable_components
+            let clone_component = self.clone_registry[type_id].clone_component.unwrap();
+            clone_component(storages.get_mut(type_id).unwrap(), entity, target);
+        }
+
+        self.pack_entity(target);
+    }
+}
+
+pub(super) fn clone_component<T: 'static + Clone>(
+    storage: &mut Storage,
+    entity: EntityId,
+    target: EntityId,
+) {
+    // we have unique access to the storage and the registry pairs the storage and function of the same type
+    let mut sparse_set = storage.sparse_set_mut::<T>().unwrap();
+
+    let component = sparse_set[entity].clone();
+    sparse_set.insert(component, target);
+}
@@ -0,0 +1,213 @@
+use super::AllStorages;
+use crate::atomic_refcell::{Borrow, Ref};
+use crate::error;
+use crate::storage::EntityId;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::any::TypeId;
+use core::convert::TryFrom;
+use parking_lot::Mutex;
+
+type Command = Box<dyn FnOnce(&mut AllStorages) -> Result<(), error::GetStorage> + Send>;
+
+/// Commands recorded but not applied yet.
+#[derive(Default)]
+pub(crate) struct CommandQueue(Mutex<Vec<Command>>);
+
+/// Records structural changes to apply them later, in the order they were recorded.
+///
+/// `Commands` doesn't conflict with any borrow except `AllStorages`, systems of the same batch can all use it.
+/// The commands are applied by `AllStorages::flush_commands` or by a `WorkloadBuilder::with_flush` system.
+/// ### Example:
+/// ```
+/// use shipyard::{system, Commands, EntitiesViewMut, IntoIter, Shiperator, View, ViewMut, World};
+///
+/// fn spawn(commands: Commands, u32s: View<u32>) {
+///     for &value in u32s.iter() {
+///         commands.spawn((value as usize,));
+///     }
+/// }
+///
+/// let world = World::new();
+/// world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>| {
+///     entities.add_entity(&mut u32s, 1);
+/// });
+///
+/// world
+///     .add_workload("Spawn")
+///     .with_system(system!(spawn))
+///     .with_flush()
+///     .build();
+///
+/// world.run_default();
+///
+/// world.run(|usizes: View<usize>| assert_eq!(usizes.len(), 1));
+/// ```
+pub struct Commands<'a> {
+    queue: &'a CommandQueue,
+    _all_borrow: Borrow<'a>,
+}
+
+impl<'a> TryFrom<Ref<'a, AllStorages>> for Commands<'a> {
+    type Error = error::GetStorage;
+    fn try_from(all_storages: Ref<'a, AllStorages>) -> Result<Self, Self::Error> {
+        // SAFE all_storages is dropped before all_borrow
+        let (all_storages, all_borrow) = unsafe { Ref::destructure(all_storages) };
+        Ok(Commands {
+            queue: &all_storages.commands,
+            _all_borrow: all_borrow,
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a AllStorages> for Commands<'a> {
+    type Error = error::GetStorage;
+    fn try_from(all_storages: &'a AllStorages) -> Result<Self, Self::Error> {
+        Ok(Commands {
+            queue: &all_storages.commands,
+            _all_borrow: Borrow::None,
+        })
+    }
+}
+
+impl Commands<'_> {
+    /// Records the creation of an entity with `components`.
+    pub fn spawn<C: CommandComponents>(&self, components: C) {
+        self.push(move |all_storages| {
+            // we have unique access to all storages so we can unwrap
+            let entity = all_storages.entities_mut().unwrap().generate();
+
+            match components.insert(all_storages, entity) {
+                Ok(()) => {
+                    all_storages.pack_entity(entity);
+                    Ok(())
+                }
+                Err(err) => {
+                    all_storages.delete(entity);
+                    Err(err)
+                }
+            }
+        });
+    }
+    /// Records the addition of `components` to `entity`, replacing the components of the same type.
+    /// Does nothing if `entity` is deleted before the commands are applied.
+    pub fn insert<C: CommandComponents>(&self, entity: EntityId, components: C) {
+        self.push(move |all_storages| {
+            // we have unique access to all storages so we can unwrap
+            if !all_storages.entities().unwrap().is_alive(entity) {
+                return Ok(());
+            }
+
+            components.insert(all_storages, entity)?;
+            all_storages.pack_entity(entity);
+            Ok(())
+        });
+    }
+    /// Records the removal of the `C` components from `entity`.
+    /// `C` has to be a tuple even for a single type.
+    pub fn remove<C: CommandComponents>(&self, entity: EntityId) {
+        self.push(move |all_storages| {
+            let mut type_ids = Vec::new();
+            C::type_ids(&mut type_ids);
+            all_storages.remove_components(entity, &type_ids);
+            Ok(())
+        });
+    }
+    /// Records the deletion of `entity` and all its components.
+    pub fn delete(&self, entity: EntityId) {
+        self.push(move |all_storages| {
+            all_storages.delete(entity);
+            Ok(())
+        });
+    }
+    fn push<F: FnOnce(&mut AllStorages) -> Result<(), error::GetStorage> + Send + 'static>(
+        &self,
+        command: F,
+    ) {
+        self.queue.0.lock().push(Box::new(command));
+    }
+}
+
+/// Trait used as a bound for `Commands::spawn`, `Commands::insert` and `Commands::remove`.
+pub trait CommandComponents: 'static + Send {
+    fn insert(self, all_storages: &AllStorages, entity: EntityId) -> Result<(), error::GetStorage>;
+    fn type_ids(type_ids: &mut Vec<TypeId>);
+}
+
+impl CommandComponents for () {
+    fn insert(self, _: &AllStorages, _: EntityId) -> Result<(), error::GetStorage> {
+        Ok(())
+    }
+    fn type_ids(_: &mut Vec<TypeId>) {}
+}
+
+macro_rules! impl_command_components {
+    ($(($type: ident, $index: tt))+) => {
+        impl<$($type: 'static + Send + Sync),+> CommandComponents for ($($type,)+) {
+            fn insert(self, all_storages: &AllStorages, entity: EntityId) -> Result<(), error::GetStorage> {
+                $(
+                    all_storages.sparse_set_mut::<$type>()?.insert(self.$index, entity);
+                )+
+
+                Ok(())
+            }
+            fn type_ids(type_ids: &mut Vec<TypeId>) {
+                $(
+                    type_ids.push(TypeId::of::<$type>());
+                )+
+            }
+        }
+    }
+}
+
+macro_rules! command_components {
+    ($(($type: ident, $index: tt))*;($type1: ident, $index1: tt) $(($queue_type: ident, $queue_index: tt))*) => {
+        impl_command_components![$(($type, $index))*];
+        command_components![$(($type, $index))* ($type1, $index1); $(($queue_type, $queue_index))*];
+    };
+    ($(($type: ident, $index: tt))*;) => {
+        impl_command_components![$(($type, $index))*];
+    }
+}
+
+command_components![(A, 0); (B, 1) (C, 2) (D, 3) (E, 4) (F, 5) (G, 6) (H, 7) (I, 8) (J, 9)];
+
+impl AllStorages {
+    /// Applies the commands recorded by `Commands`, in the order they were recorded.
+    /// Stops at the first error, the following commands are kept for the next flush.
+    pub fn try_flush_commands(&mut self) -> Result<(), error::GetStorage> {
+        let mut commands = core::mem::take(self.commands.0.get_mut()).into_iter();
+
+        while let Some(command) = commands.next() {
+            if let Err(err) = command(self) {
+                *self.commands.0.get_mut() = commands.collect();
+                return Err(err);
+            }
+        }
+
+        Ok(())
+    }
+    /// Applies the commands recorded by `Commands`, in the order they were recorded.
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn flush_commands(&mut self) {
+        self.try_flush_commands().unwrap()
+    }
+    /// Removes the components of `entity` stored in the `type_ids` storages.
+    fn remove_components(&mut self, entity: EntityId, type_ids: &[TypeId]) {
+        let mut storage_to_unpack = Vec::new();
+        let storages = self.storages.get_mut();
+
+        for type_id in type_ids {
+            if let Some(storage) = storages.get_mut(type_id) {
+                // we have unique access to all storages so we can unwrap
+                storage.remove(entity, &mut storage_to_unpack).unwrap();
+            }
+        }
+
+        for storage in storage_to_unpack {
+            storages.get_mut(&storage).unwrap().unpack(entity).unwrap();
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use super::{AllStorages, Checkpoint};
+use crate::error;
+use crate::sparse_set::{ComponentDelta, SparseSet, SparseSetCheckpoint};
+use crate::storage::{Entities, EntityId, Storage};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::any::{type_name, Any, TypeId};
+use parking_lot::lock_api::RawRwLock as _;
+
+pub(super) type DeltaFn = fn(
+    Option<&Storage>,
+    Option<&(dyn Any + Send + Sync)>,
+) -> Result<Option<Box<dyn Any + Send + Sync>>, error::GetStorage>;
+pub(super) type ApplyDeltaFn =
+    fn(&mut AllStorages, &dyn Any, &mut Vec<EntityId>) -> Result<(), error::GetStorage>;
+
+/// Differences between a `Checkpoint` and the state of a `World`, made by `AllStorages::delta`.
+///
+/// Only storages registered with `register_delta` are compared, unique storages are not part of a delta.
+pub struct Delta {
+    entities: Entities,
+    created: Vec<EntityId>,
+    destroyed: Vec<EntityId>,
+    storages: Vec<(TypeId, ApplyDeltaFn, Box<dyn Any + Send + Sync>)>,
+}
+
+impl Delta {
+    /// Returns the entities created since the checkpoint.
+    pub fn created(&self) -> &[EntityId] {
+        &self.created
+    }
+    /// Returns the entities deleted since the checkpoint.
+    pub fn destroyed(&self) -> &[EntityId] {
+        &self.destroyed
+    }
+    /// Returns the components of type `T` added, changed or removed since the checkpoint.
+    /// Returns `None` if none were or if `T` isn't registered with `register_delta`.
+    pub fn components<T: 'static>(&self) -> Option<&ComponentDelta<T>> {
+        self.storages
+            .iter()
+            .find(|(type_id, _, _)| *type_id == TypeId::of::<T>())
+            .and_then(|(_, _, delta)| delta.downcast_ref())
+    }
+    /// Returns `true` if no entity and no component changed since the checkpoint.
+    pub fn is_empty(&self) -> bool {
+        self.created.is_empty() && self.destroyed.is_empty() && self.storages.is_empty()
+    }
+}
+
+impl AllStorages {
+    /// Registers `T`'s storage as cloneable, it'll be part of checkpoints and deltas.
+    pub fn try_register_delta<T: 'static + Send + Sync + Clone + PartialEq>(
+        &mut self,
+    ) -> Result<(), error::Register> {
+        self.insert_clone_entry::<T>(super::CloneEntry::new_delta::<T>())
+    }
+    /// Registers `T`'s storage as cloneable, it'll be part of checkpoints and deltas.
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn register_delta<T: 'static + Send + Sync + Clone + PartialEq>(&mut self) {
+        self.try_register_delta::<T>().unwrap()
+    }
+    /// Lists the entities and components that changed since `previous` was made.
+    /// Components are compared with `PartialEq`.
+    pub fn try_delta(&self, previous: &Checkpoint) -> Result<Delta, error::GetStorage> {
+        self.lock.lock_shared();
+        // SAFE we locked
+        let storages = unsafe { &*self.storages.get() };
+
+        let result = (|| {
+            // AllStorages is always created with Entities so there's no way to not find it
+            let entities = storages[&TypeId::of::<Entities>()]
+                .entities()
+                .map_err(error::GetStorage::Entities)?;
+            let (created, destroyed) = entities.delta(&previous.entities);
+            let entities = entities.checkpoint();
+
+            let mut changed = Vec::new();
+            for (type_id, entry) in &self.clone_registry {
+                if let Some((delta, apply_delta)) = entry.delta {
+                    let old_storage = previous
+                        .storages
+                        .iter()
+                        .find(|(other, _, _)| other == type_id)
+                        .map(|(_, _, storage)| &**storage);
+
+                    if let Some(delta) = delta(storages.get(type_id), old_storage)? {
+                        changed.push((*type_id, apply_delta, delta));
+                    }
+                }
+            }
+
+            Ok(Delta {
+                entities,
+                created,
+                destroyed,
+                storages: changed,
+            })
+        })();
+
+        self.lock.unlock_shared();
+        result
+    }
+    /// Lists the entities and components that changed since `previous` was made.
+    /// Components are compared with `PartialEq`.
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn delta(&self, previous: &Checkpoint) -> Delta {
+        self.try_delta(previous).unwrap()
+    }
+    /// Applies the changes listed in `delta`.
+    /// `Entities` and the storages are expected to be in the state of the checkpoint `delta` was made from.
+    /// Added and changed components are considered inserted or modified at the tick `delta` is applied.
+    pub fn try_apply_delta(&mut self, delta: &Delta) -> Result<(), error::GetStorage> {
+        for &entity in &delta.destroyed {
+            self.strip(entity);
+        }
+
+        // we have unique access to all storages so we can unwrap
+        self.storages.get_mut()[&TypeId::of::<Entities>()]
+            .entities_mut()
+            .unwrap()
+            .restore_checkpoint(&delta.entities);
+
+        let mut to_pack = Vec::new();
+        for (_, apply_delta, storage) in &delta.storages {
+            apply_delta(self, &**storage, &mut to_pack)?;
+        }
+
+        to_pack.sort_unstable();
+        to_pack.dedup();
+
+        for entity in to_pack {
+            self.pack_entity(entity);
+        }
+
+        Ok(())
+    }
+    /// Applies the changes listed in `delta`.
+    /// `Entities` and the storages are expected to be in the state of the checkpoint `delta` was made from.
+    /// Added and changed components are considered inserted or modified at the tick `delta` is applied.
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn apply_delta(&mut self, delta: &Delta) {
+        self.try_apply_delta(delta).unwrap()
+    }
+}
+
+pub(super) fn delta_sparse_set<T: 'static + Send + Sync + Clone + PartialEq>(
+    storage: Option<&Storage>,
+    previous: Option<&(dyn Any + Send + Sync)>,
+) -> Result<Option<Box<dyn Any + Send + Sync>>, error::GetStorage> {
+    // the checkpoint can come from a World where T is registered differently
+    let previous = match previous {
+        Some(previous) => Some(
+            previous
+                .downcast_ref::<SparseSetCheckpoint<T>>()
+                .ok_or_else(|| error::GetStorage::MismatchedStorage(type_name::<T>()))?,
+        ),
+        None => None,
+    };
+
+    let delta = match storage {
+        Some(storage) => storage.sparse_set::<T>()?.delta(previous),
+        None => SparseSet::new().delta(previous),
+    };
+
+    if delta.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(Box::new(delta)))
+    }
+}
+
+pub(super) fn apply_delta_sparse_set<T: 'static + Send + Sync + Clone + PartialEq>(
+    all_storages: &mut AllStorages,
+    delta: &dyn Any,
+    to_pack: &mut Vec<EntityId>,
+) -> Result<(), error::GetStorage> {
+    let delta = delta
+        .downcast_ref::<ComponentDelta<T>>()
+        .ok_or_else(|| error::GetStorage::MismatchedStorage(type_name::<T>()))?;
+
+    let tick = all_storages.next_change_tick();
+    let storages = all_storages.storages.get_mut();
+    let mut sparse_set = storages
+        .entry(TypeId::of::<T>())
+        .or_insert_with(Storage::new::<T>)
+        .sparse_set_mut::<T>()?;
+
+    // added and changed components are stamped with the tick of the delta
+    sparse_set.set_change_tick(tick);
+    let removed = sparse_set.apply_delta(delta);
+    let unpack_types = sparse_set.unpack_types();
+    drop(sparse_set);
+
+    for unpack_type in unpack_types {
+        if unpack_type == TypeId::of::<T>() {
+            continue;
+        }
+
+        if let Some(storage) = storages.get_mut(&unpack_type) {
+            for &entity in &removed {
+                // we have unique access to all storages so we can unwrap
+                storage.unpack(entity).unwrap();
+            }
+        }
+    }
+
+    to_pack.extend(delta.added().iter().map(|(entity, _)| *entity));
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use super::AllStorages;
+use crate::error;
+use crate::storage::{Entities, EntityMap};
+use alloc::vec::Vec;
+use core::any::TypeId;
+
+/// What to do when both `World`s of a merge have a unique storage of the same type.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum UniqueConflict {
+    /// Keeps the unique storage of the `World` merged into, the other one is left as is.
+    Keep,
+    /// Replaces the unique storage of the `World` merged into.
+    Replace,
+    /// Returns an error before anything is merged.
+    Error,
+}
+
+impl AllStorages {
+    /// Moves all entities, components and unique storages of `other` to this `AllStorages`.
+    /// Entities are given new ids, the returned `EntityMap` links both.
+    /// `conflict` decides what happens to unique storages present in both.
+    ///
+    /// Shared components are not moved and `EntityId`s held by components are not modified,
+    /// `MapEntities` can be used with the returned `EntityMap` to update them.
+    pub fn try_merge(
+        &mut self,
+        other: &mut AllStorages,
+        conflict: UniqueConflict,
+    ) -> Result<EntityMap, error::Merge> {
+        let uniques = self.check_merge(other, conflict)?;
+
+        // we have unique access to all storages so we can unwrap
+        let entities: Vec<_> = other.entities().unwrap().iter().collect();
+        let entity_map = other.try_move_entities(self, &entities)?;
+
+        let storages = self.storages.get_mut();
+        let other_storages = other.storages.get_mut();
+        for type_id in uniques {
+            if conflict == UniqueConflict::Replace || !storages.contains_key(&type_id) {
+                // check_merge only returns types present in other
+                storages.insert(type_id, other_storages.remove(&type_id).unwrap());
+            }
+        }
+
+        Ok(entity_map)
+    }
+    /// Moves all entities, components and unique storages of `other` to this `AllStorages`.
+    /// Entities are given new ids, the returned `EntityMap` links both.
+    /// `conflict` decides what happens to unique storages present in both.
+    ///
+    /// Shared components are not moved and `EntityId`s held by components are not modified,
+    /// `MapEntities` can be used with the returned `EntityMap` to update them.
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn merge(&mut self, other: &mut AllStorages, conflict: UniqueConflict) -> EntityMap {
+        self.try_merge(other, conflict).unwrap()
+    }
+    /// Returns the types of `other`'s unique storages.
+    /// Returns an error if one of them can't be merged.
+    fn check_merge(
+        &mut self,
+        other: &mut AllStorages,
+        conflict: UniqueConflict,
+    ) -> Result<Vec<TypeId>, error::Merge> {
+        let storages = self.storages.get_mut();
+        let mut uniques = Vec::new();
+
+        // we have unique access to all storages so we can unwrap
+        for (type_id, other_storage) in other.storages.get_mut().iter() {
+            if *type_id == TypeId::of::<Entities>() || other_storage.new_empty().unwrap().is_some()
+            {
+                continue;
+            }
+
+            if let Some(storage) = storages.get(type_id) {
+                let name = other_storage.type_name().unwrap();
+
+                if storage.new_empty().unwrap().is_some() {
+                    return Err(error::GetStorage::NonUnique((name, error::Borrow::Unique)).into());
+                } else if conflict == UniqueConflict::Error {
+                    return Err(error::Merge::UniqueConflict(name));
+                }
+            }
+
+            uniques.push(*type_id);
+        }
+
+        Ok(uniques)
+    }
+}
@@ -1,9 +1,30 @@
+mod checkpoint;
+mod clone_entity;
+mod commands;
 mod delete_any;
+mod delta;
 mod hasher;
+mod merge;
+mod move_entities;
+mod prefab;
+#[cfg(feature = "serde")]
+mod serde;
+mod snapshot;
 
+pub use checkpoint::Checkpoint;
+pub use commands::{CommandComponents, Commands};
 pub use delete_any::DeleteAny;
+pub use delta::Delta;
+pub use merge::UniqueConflict;
+pub use prefab::{Prefab, PrefabComponents, Prefabs};
+pub use snapshot::Snapshot;
 
+pub(crate) use commands::CommandQueue;
 pub(crate) use hasher::TypeIdHasher;
+pub(crate) use snapshot::{write_raw, RawReader};
+
+#[cfg(feature = "serde")]
+use self::serde::SerdeRegistry;
 
 use super::{Entities, EntityId, Storage};
 use crate::atomic_refcell::{AtomicRefCell, Ref, RefMut};
@@ -12,11 +33,14 @@ use crate::error;
 use crate::sparse_set::SparseSet;
 use alloc::boxed::Box;
 use alloc::vec::Vec;
+use checkpoint::CloneEntry;
 use core::any::TypeId;
 use core::cell::UnsafeCell;
 use core::hash::BuildHasherDefault;
+use core::sync::atomic::{AtomicU64, Ordering};
 use hashbrown::{hash_map::Entry, HashMap};
 use parking_lot::{lock_api::RawRwLock as _, RawRwLock};
+use snapshot::SnapshotEntry;
 
 /// Contains all components present in the World.
 // The lock is held very briefly:
@@ -29,6 +53,15 @@ use parking_lot::{lock_api::RawRwLock as _, RawRwLock};
 pub struct AllStorages {
     lock: RawRwLock,
     storages: UnsafeCell<HashMap<TypeId, Storage, BuildHasherDefault<TypeIdHasher>>>,
+    // registries are only modified with an exclusive borrow
+    clone_registry: HashMap<TypeId, CloneEntry, BuildHasherDefault<TypeIdHasher>>,
+    snapshot_registry: Vec<SnapshotEntry>,
+    #[cfg(feature = "serde")]
+    serde_registry: SerdeRegistry,
+    // recorded by Commands, applied by flush_commands
+    commands: CommandQueue,
+    // last tick given to a system run or a borrow outside of workloads
+    change_tick: AtomicU64,
     #[cfg(feature = "non_send")]
     thread_id: std::thread::ThreadId,
 }
@@ -56,6 +89,12 @@ impl AllStorages {
 
         AllStorages {
             storages: UnsafeCell::new(storages),
+            clone_registry: HashMap::default(),
+            snapshot_registry: Vec::new(),
+            #[cfg(feature = "serde")]
+            serde_registry: SerdeRegistry::default(),
+            commands: CommandQueue::default(),
+            change_tick: AtomicU64::new(0),
             lock: RawRwLock::INIT,
             #[cfg(feature = "non_send")]
             thread_id: std::thread::current().id(),
@@ -134,7 +173,7 @@ impl AllStorages {
             if let Some(storage) = storages.get(&type_id) {
                 let sparse_set = storage.sparse_set_mut::<T>();
                 self.lock.unlock_shared();
-                return sparse_set;
+                return self.with_change_tick(sparse_set);
             }
         }
         self.lock.unlock_shared();
@@ -147,7 +186,37 @@ impl AllStorages {
             .or_insert_with(Storage::new::<T>)
             .sparse_set_mut::<T>();
         self.lock.unlock_exclusive();
-        sparse_set
+        self.with_change_tick(sparse_set)
+    }
+    /// Sets the tick `sparse_set` stamps on the components it inserts or modifies.
+    fn with_change_tick<'a, T>(
+        &self,
+        sparse_set: Result<RefMut<'a, SparseSet<T>>, error::GetStorage>,
+    ) -> Result<RefMut<'a, SparseSet<T>>, error::GetStorage> {
+        sparse_set.map(|mut sparse_set| {
+            sparse_set.set_change_tick(self.next_change_tick());
+            sparse_set
+        })
+    }
+    /// Returns the tick of the system running on this thread or a new tick outside of workloads.
+    pub(crate) fn next_change_tick(&self) -> u64 {
+        #[cfg(feature = "std")]
+        {
+            if let Some((_, this_run)) = crate::borrow::system_ticks() {
+                return this_run;
+            }
+        }
+
+        self.new_change_tick()
+    }
+    /// Returns a tick greater than all the ticks given so far.
+    pub(crate) fn new_change_tick(&self) -> u64 {
+        self.change_tick.fetch_add(1, Ordering::Relaxed) + 1
+    }
+    /// Returns the last tick given to a system run or a borrow outside of workloads.  
+    /// Components inserted or modified after this call will have a greater tick, it can be passed to `inserted_since` and `modified_since`.
+    pub fn change_tick(&self) -> u64 {
+        self.change_tick.load(Ordering::Relaxed)
     }
     #[cfg(feature = "non_send")]
     pub(crate) fn sparse_set_non_send<T: 'static + Sync>(
@@ -190,7 +259,7 @@ impl AllStorages {
             if let Some(storage) = storages.get(&type_id) {
                 let sparse_set = storage.sparse_set_mut::<T>();
                 self.lock.unlock_shared();
-                return sparse_set;
+                return self.with_change_tick(sparse_set);
             }
         }
         self.lock.unlock_shared();
@@ -203,7 +272,7 @@ impl AllStorages {
             .or_insert_with(|| Storage::new_non_send::<T>(self.thread_id))
             .sparse_set_mut::<T>();
         self.lock.unlock_exclusive();
-        sparse_set
+        self.with_change_tick(sparse_set)
     }
     #[cfg(feature = "non_sync")]
     pub(crate) fn sparse_set_non_sync<T: 'static + Send>(
@@ -246,7 +315,7 @@ impl AllStorages {
             if let Some(storage) = storages.get(&type_id) {
                 let sparse_set = storage.sparse_set_mut::<T>();
                 self.lock.unlock_shared();
-                return sparse_set;
+                return self.with_change_tick(sparse_set);
             }
         }
         self.lock.unlock_shared();
@@ -259,7 +328,7 @@ impl AllStorages {
             .or_insert_with(Storage::new_non_sync::<T>)
             .sparse_set_mut::<T>();
         self.lock.unlock_exclusive();
-        sparse_set
+        self.with_change_tick(sparse_set)
     }
     #[cfg(all(feature = "non_send", feature = "non_sync"))]
     pub(crate) fn sparse_set_non_send_sync<T: 'static>(
@@ -302,7 +371,7 @@ impl AllStorages {
             if let Some(storage) = storages.get(&type_id) {
                 let sparse_set = storage.sparse_set_mut::<T>();
                 self.lock.unlock_shared();
-                return sparse_set;
+                return self.with_change_tick(sparse_set);
             }
         }
         self.lock.unlock_shared();
@@ -315,7 +384,7 @@ impl AllStorages {
             .or_insert_with(|| Storage::new_non_send_sync::<T>(self.thread_id))
             .sparse_set_mut::<T>();
         self.lock.unlock_exclusive();
-        sparse_set
+        self.with_change_tick(sparse_set)
     }
     pub(crate) fn unique<T: 'static>(&self) -> Result<Ref<'_, T>, error::GetStorage> {
         let type_id = TypeId::of::<T>();
@@ -485,6 +554,71 @@ impl AllStorages {
             storages.get_mut(&storage).unwrap().unpack(entity).unwrap();
         }
     }
+    /// Packs `entity` in all storages where it has all the components required by the pack.
+    pub(crate) fn pack_entity(&mut self, entity: EntityId) {
+        let storages = self.storages.get_mut();
+
+        // we have unique access to all storages so we can unwrap
+        let mut types: Vec<_> = storages
+            .iter()
+            .filter(|(_, storage)| storage.contains(entity).unwrap())
+            .map(|(type_id, _)| *type_id)
+            .collect();
+        types.sort_unstable();
+
+        for type_id in &types {
+            storages
+                .get_mut(type_id)
+                .unwrap()
+                .pack(entity, &types)
+                .unwrap();
+        }
+    }
+    /// Replaces `Entities` and all storages' content with a saved state.  
+    /// Storages are emptied first, without calling hooks nor tracking removal, then `restore_entities` and `restore_storages` bring back the saved state.  
+    /// Change ticks are reset, all restored components are considered inserted at the restore's tick.
+    /// Tight and loose packs are rebuilt from scratch afterwards since members of a pack might not agree on which entities are packed.
+    pub(crate) fn restore_with<E>(
+        &mut self,
+        restore_entities: impl FnOnce(&mut Entities),
+        restore_storages: impl FnOnce(&mut AllStorages) -> Result<(), E>,
+    ) -> Result<(), E> {
+        let storages = self.storages.get_mut();
+
+        for storage in storages.values_mut() {
+            // we have unique access to all storages so we can unwrap
+            storage.actual_clear().unwrap();
+        }
+
+        restore_entities(
+            &mut storages
+                .get_mut(&TypeId::of::<Entities>())
+                .unwrap()
+                .entities_mut()
+                .unwrap(),
+        );
+
+        restore_storages(self)?;
+
+        let tick = self.next_change_tick();
+
+        // a pack can be made of registered and unregistered storages
+        let mut entities = Vec::new();
+        for storage in self.storages.get_mut().values_mut() {
+            // we have unique access to all storages so we can unwrap
+            entities.extend(storage.reset_pack().unwrap());
+            storage.reset_ticks(tick).unwrap();
+        }
+
+        entities.sort_unstable();
+        entities.dedup();
+
+        for entity in entities {
+            self.pack_entity(entity);
+        }
+
+        Ok(())
+    }
     /// Deletes all entities and their components.
     pub fn clear(&mut self) {
         // SAFE we have unique access
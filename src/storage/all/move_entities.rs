@@ -0,0 +1,129 @@
+use super::AllStorages;
+use crate::error;
+use crate::storage::{Entities, EntityId, EntityMap};
+use alloc::vec::Vec;
+use core::any::TypeId;
+
+impl AllStorages {
+    /// Moves `entities` and all their components to `other`.
+    /// They're deleted from this `AllStorages` and given new ids in `other`, the returned `EntityMap` links both.
+    /// Dead entities are ignored and shared components are not moved.
+    ///
+    /// `EntityId`s held by components are not modified, `MapEntities` can be used with the returned `EntityMap` to update them.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{AllStoragesViewMut, EntitiesViewMut, View, ViewMut, World};
+    ///
+    /// let loading = World::new();
+    /// let world = World::new();
+    ///
+    /// let entity = loading.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>| {
+    ///     entities.add_entity(&mut u32s, 0)
+    /// });
+    ///
+    /// let entity_map = loading.run(|mut loading: AllStoragesViewMut| {
+    ///     world.run(|mut all_storages: AllStoragesViewMut| {
+    ///         loading.move_entities(&mut all_storages, &[entity])
+    ///     })
+    /// });
+    ///
+    /// world.run(|u32s: View<u32>| {
+    ///     assert_eq!(u32s[entity_map.get(entity).unwrap()], 0);
+    /// });
+    /// ```
+    pub fn try_move_entities(
+        &mut self,
+        other: &mut AllStorages,
+        entities: &[EntityId],
+    ) -> Result<EntityMap, error::GetStorage> {
+        // we have unique access to all storages so we can unwrap
+        let alive: Vec<_> = {
+            let all_entities = self.entities().unwrap();
+            entities
+                .iter()
+                .copied()
+                .filter(|&entity| all_entities.is_alive(entity))
+                .collect()
+        };
+
+        self.check_move(other, &alive)?;
+
+        let mut entity_map = EntityMap::new();
+        let mut storage_to_unpack = Vec::new();
+
+        for entity in alive {
+            let target = other.storages.get_mut()[&TypeId::of::<Entities>()]
+                .entities_mut()
+                .unwrap()
+                .generate();
+            entity_map.insert(entity, target);
+
+            let storages = self.storages.get_mut();
+            storages[&TypeId::of::<Entities>()]
+                .entities_mut()
+                .unwrap()
+                .delete(entity);
+
+            let other_storages = other.storages.get_mut();
+            for (type_id, storage) in storages.iter_mut() {
+                if storage.contains(entity).unwrap() {
+                    // only sparse sets can contain entities
+                    let other_storage = other_storages
+                        .entry(*type_id)
+                        .or_insert_with(|| storage.new_empty().unwrap().unwrap());
+
+                    // check_move made sure both storages store the same type
+                    storage
+                        .move_component(entity, other_storage, target, &mut storage_to_unpack)
+                        .unwrap();
+                }
+            }
+
+            for type_id in storage_to_unpack.drain(..) {
+                storages.get_mut(&type_id).unwrap().unpack(entity).unwrap();
+            }
+
+            other.pack_entity(target);
+        }
+
+        Ok(entity_map)
+    }
+    /// Moves `entities` and all their components to `other`.
+    /// They're deleted from this `AllStorages` and given new ids in `other`, the returned `EntityMap` links both.
+    /// Dead entities are ignored and shared components are not moved.
+    ///
+    /// `EntityId`s held by components are not modified, `MapEntities` can be used with the returned `EntityMap` to update them.
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn move_entities(&mut self, other: &mut AllStorages, entities: &[EntityId]) -> EntityMap {
+        self.try_move_entities(other, entities).unwrap()
+    }
+    /// Returns an error if a component of `entities` is stored in a unique storage in `other`.
+    fn check_move(
+        &mut self,
+        other: &mut AllStorages,
+        entities: &[EntityId],
+    ) -> Result<(), error::GetStorage> {
+        let other_storages = other.storages.get_mut();
+
+        // we have unique access to all storages so we can unwrap
+        for (type_id, storage) in self.storages.get_mut().iter() {
+            if let Some(other_storage) = other_storages.get(type_id) {
+                if other_storage.new_empty().unwrap().is_none()
+                    && entities
+                        .iter()
+                        .any(|&entity| storage.contains(entity).unwrap())
+                {
+                    return Err(error::GetStorage::Unique {
+                        name: storage.type_name().unwrap(),
+                        borrow: error::Borrow::Unique,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
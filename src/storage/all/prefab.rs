@@ -0,0 +1,250 @@
+use super::AllStorages;
+use crate::error;
+use crate::storage::EntityId;
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::any::{Any, TypeId};
+use hashbrown::HashMap;
+
+type SpawnFn =
+    fn(&AllStorages, &(dyn Any + Send + Sync), EntityId) -> Result<(), error::GetStorage>;
+
+/// Blueprint of an entity, made of cloneable components.
+#[derive(Default)]
+pub struct Prefab {
+    components: Vec<(TypeId, SpawnFn, Box<dyn Any + Send + Sync>)>,
+}
+
+impl Prefab {
+    /// Creates a prefab from a tuple of components.
+    pub fn new<C: PrefabComponents>(components: C) -> Self {
+        let mut prefab = Prefab::default();
+        components.add_to(&mut prefab);
+        prefab
+    }
+    /// Adds `component` to the prefab, replacing the component of the same type if present.
+    pub fn insert<T: 'static + Send + Sync + Clone>(&mut self, component: T) {
+        let type_id = TypeId::of::<T>();
+        let component: Box<dyn Any + Send + Sync> = Box::new(component);
+
+        if let Some((_, _, old)) = self
+            .components
+            .iter_mut()
+            .find(|(other, _, _)| *other == type_id)
+        {
+            *old = component;
+        } else {
+            self.components
+                .push((type_id, spawn_component::<T>, component));
+        }
+    }
+    /// Returns the number of components in the prefab.
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+    /// Returns `true` if the prefab doesn't have any component.
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+    /// Adds the prefab's components to `entity`, the ones of the same type as an override are skipped.
+    fn spawn(
+        &self,
+        all_storages: &AllStorages,
+        entity: EntityId,
+        overrides: Option<&Prefab>,
+    ) -> Result<(), error::GetStorage> {
+        for (type_id, spawn, component) in &self.components {
+            let overridden = overrides
+                .into_iter()
+                .flat_map(|overrides| &overrides.components)
+                .any(|(other, _, _)| other == type_id);
+
+            if !overridden {
+                spawn(all_storages, &**component, entity)?;
+            }
+        }
+
+        if let Some(overrides) = overrides {
+            for (_, spawn, component) in &overrides.components {
+                spawn(all_storages, &**component, entity)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Trait used as a bound for `Prefabs::register` and `AllStorages::spawn_prefab_with`.
+pub trait PrefabComponents {
+    fn add_to(self, prefab: &mut Prefab);
+}
+
+impl PrefabComponents for () {
+    fn add_to(self, _: &mut Prefab) {}
+}
+
+macro_rules! impl_prefab_components {
+    ($(($type: ident, $index: tt))+) => {
+        impl<$($type: 'static + Send + Sync + Clone),+> PrefabComponents for ($($type,)+) {
+            fn add_to(self, prefab: &mut Prefab) {
+                $(
+                    prefab.insert(self.$index);
+                )+
+            }
+        }
+    }
+}
+
+macro_rules! prefab_components {
+    ($(($type: ident, $index: tt))*;($type1: ident, $index1: tt) $(($queue_type: ident, $queue_index: tt))*) => {
+        impl_prefab_components![$(($type, $index))*];
+        prefab_components![$(($type, $index))* ($type1, $index1); $(($queue_type, $queue_index))*];
+    };
+    ($(($type: ident, $index: tt))*;) => {
+        impl_prefab_components![$(($type, $index))*];
+    }
+}
+
+prefab_components![(A, 0); (B, 1) (C, 2) (D, 3) (E, 4) (F, 5) (G, 6) (H, 7) (I, 8) (J, 9)];
+
+/// Named prefabs, add it to the `World` as a unique storage to spawn them with `AllStorages::spawn_prefab`.
+///
+/// Prefabs are spawned from `AllStorages` and not `Entities` since a prefab can hold components of any type,
+/// `Entities` is one of the storages borrowed while spawning.
+///
+/// ### Example
+/// ```
+/// use shipyard::{AllStoragesViewMut, Prefabs, View, World};
+///
+/// let mut prefabs = Prefabs::new();
+/// prefabs.register("goblin", (String::from("goblin"), 10u32));
+///
+/// let world = World::new();
+/// world.add_unique(prefabs);
+///
+/// let (goblin, boss) = world.run(|mut all_storages: AllStoragesViewMut| {
+///     (
+///         all_storages.spawn_prefab("goblin"),
+///         all_storages.spawn_prefab_with("goblin", (50u32,)),
+///     )
+/// });
+///
+/// world.run(|strings: View<String>, u32s: View<u32>| {
+///     assert_eq!(strings[goblin], "goblin");
+///     assert_eq!(u32s[goblin], 10);
+///     assert_eq!(strings[boss], "goblin");
+///     assert_eq!(u32s[boss], 50);
+/// });
+/// ```
+#[derive(Default)]
+pub struct Prefabs {
+    prefabs: HashMap<Cow<'static, str>, Prefab>,
+}
+
+impl Prefabs {
+    /// Creates an empty `Prefabs`.
+    pub fn new() -> Self {
+        Prefabs::default()
+    }
+    /// Registers a prefab made of `components` under `name`, replacing any prefab with the same name.
+    pub fn register<C: PrefabComponents>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        components: C,
+    ) {
+        self.insert(name, Prefab::new(components));
+    }
+    /// Adds `prefab` under `name`, replacing any prefab with the same name.
+    pub fn insert(&mut self, name: impl Into<Cow<'static, str>>, prefab: Prefab) {
+        self.prefabs.insert(name.into(), prefab);
+    }
+    /// Returns the prefab registered under `name`.
+    pub fn get(&self, name: &str) -> Option<&Prefab> {
+        self.prefabs.get(name)
+    }
+    /// Returns the prefab registered under `name` mutably.
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Prefab> {
+        self.prefabs.get_mut(name)
+    }
+    /// Removes the prefab registered under `name` and returns it.
+    pub fn remove(&mut self, name: &str) -> Option<Prefab> {
+        self.prefabs.remove(name)
+    }
+}
+
+impl AllStorages {
+    /// Creates a new entity with a clone of all components of the prefab registered under `name`.
+    /// The `Prefabs` unique storage has to be present.
+    pub fn try_spawn_prefab(&mut self, name: &str) -> Result<EntityId, error::SpawnPrefab> {
+        self.spawn(name, None)
+    }
+    /// Creates a new entity with a clone of all components of the prefab registered under `name`.
+    /// The `Prefabs` unique storage has to be present.
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn spawn_prefab(&mut self, name: &str) -> EntityId {
+        self.try_spawn_prefab(name).unwrap()
+    }
+    /// Creates a new entity with a clone of all components of the prefab registered under `name`.
+    /// `overrides` replace the prefab's components of the same type, each type is only inserted once.
+    /// The `Prefabs` unique storage has to be present.
+    pub fn try_spawn_prefab_with<C: PrefabComponents>(
+        &mut self,
+        name: &str,
+        overrides: C,
+    ) -> Result<EntityId, error::SpawnPrefab> {
+        self.spawn(name, Some(Prefab::new(overrides)))
+    }
+    /// Creates a new entity with a clone of all components of the prefab registered under `name`.
+    /// `overrides` replace the prefab's components of the same type, each type is only inserted once.
+    /// The `Prefabs` unique storage has to be present.
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn spawn_prefab_with<C: PrefabComponents>(&mut self, name: &str, overrides: C) -> EntityId {
+        self.try_spawn_prefab_with(name, overrides).unwrap()
+    }
+    fn spawn(
+        &mut self,
+        name: &str,
+        overrides: Option<Prefab>,
+    ) -> Result<EntityId, error::SpawnPrefab> {
+        let prefabs = self.unique::<Prefabs>()?;
+        let prefab = prefabs
+            .get(name)
+            .ok_or_else(|| error::SpawnPrefab::MissingPrefab(name.into()))?;
+
+        // we have unique access to all storages so we can unwrap
+        let entity = self.entities_mut().unwrap().generate();
+
+        let result = prefab.spawn(self, entity, overrides.as_ref());
+        drop(prefabs);
+
+        match result {
+            Ok(()) => {
+                self.pack_entity(entity);
+                Ok(entity)
+            }
+            Err(err) => {
+                self.delete(entity);
+                Err(err.into())
+            }
+        }
+    }
+}
+
+fn spawn_component<T: 'static + Send + Sync + Clone>(
+    all_storages: &AllStorages,
+    component: &(dyn Any + Send + Sync),
+    entity: EntityId,
+) -> Result<(), error::GetStorage> {
+    // the prefab always pairs the component and spawn function of the same type
+    let component = component.downcast_ref::<T>().unwrap();
+    all_storages
+        .sparse_set_mut::<T>()?
+        .insert(component.clone(), entity);
+
+    Ok(())
+}
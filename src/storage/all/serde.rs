@@ -0,0 +1,667 @@
+use super::AllStorages;
+use crate::atomic_refcell::Ref;
+use crate::error;
+use crate::sparse_set::{LoadedSparseSet, LoadedSparseSetSeed, SparseSet};
+use crate::storage::{Entities, EntityId, EntityMap, MapEntities, Storage};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::any::{type_name, Any, TypeId};
+use core::fmt::Formatter;
+use hashbrown::hash_map::Entry;
+use parking_lot::lock_api::RawRwLock as _;
+use serde::de::{self, DeserializeOwned, DeserializeSeed, MapAccess, SeqAccess, Visitor};
+use serde::ser::{self, SerializeMap, SerializeStruct};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+const FIELDS: &[&str] = &["entities", "storages"];
+
+/// Storages to (de)serialize and the key identifying each of them.
+// the key is what ties serialized data to a storage, it has to stay the same between saving and loading
+#[derive(Clone, Default)]
+pub(crate) struct SerdeRegistry {
+    entries: Vec<RegistryEntry>,
+}
+
+type SerializeFn =
+    for<'a> fn(&'a Storage) -> Result<Box<dyn erased_serde::Serialize + 'a>, error::GetStorage>;
+// the index count is the one of the loaded Entities, when they're known
+type DeserializeFn = for<'de> fn(
+    &mut dyn erased_serde::Deserializer<'de>,
+    Option<usize>,
+) -> Result<Box<dyn Any>, erased_serde::Error>;
+
+type AddFn = fn(
+    &mut AllStorages,
+    Box<dyn Any>,
+    &EntityMap,
+    &mut Vec<(EntityId, TypeId)>,
+) -> Result<(), error::GetStorage>;
+
+#[derive(Clone, Copy)]
+struct RegistryEntry {
+    key: &'static str,
+    type_id: TypeId,
+    serialize: SerializeFn,
+    deserialize: DeserializeFn,
+    load: fn(&mut AllStorages, Box<dyn Any>) -> Result<(), error::GetStorage>,
+    // unique storages can't be added
+    add: Option<AddFn>,
+}
+
+impl SerdeRegistry {
+    fn insert<T: 'static>(&mut self, entry: RegistryEntry) -> Result<(), error::Register> {
+        if self.entries.iter().any(|other| other.key == entry.key) {
+            Err(error::Register::KeyAlreadyUsed(entry.key))
+        } else if self
+            .entries
+            .iter()
+            .any(|other| other.type_id == entry.type_id)
+        {
+            Err(error::Register::TypeAlreadyRegistered(type_name::<T>()))
+        } else {
+            self.entries.push(entry);
+            Ok(())
+        }
+    }
+}
+
+impl AllStorages {
+    /// Registers `T`'s storage under `key`, it'll be part of serialized `World`s.  
+    /// The key is what ties serialized data to a storage, it has to stay the same between saving and loading.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{EntitiesViewMut, UniqueView, ViewMut, World};
+    ///
+    /// let world = World::new();
+    /// world.register_serde::<u32>("u32");
+    /// world.register_unique_serde::<usize>("frame");
+    /// world.add_unique(60usize);
+    /// let entity = world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>| {
+    ///     entities.add_entity(&mut u32s, 0)
+    /// });
+    ///
+    /// let mut json = Vec::new();
+    /// world
+    ///     .serialize(&mut serde_json::Serializer::new(&mut json))
+    ///     .unwrap();
+    ///
+    /// let loaded = World::new();
+    /// loaded.register_serde::<u32>("u32");
+    /// loaded.register_unique_serde::<usize>("frame");
+    /// loaded
+    ///     .deserialize(&mut serde_json::Deserializer::from_slice(&json))
+    ///     .unwrap();
+    ///
+    /// loaded.run(|u32s: ViewMut<u32>, frame: UniqueView<usize>| {
+    ///     assert_eq!(u32s[entity], 0);
+    ///     assert_eq!(*frame, 60);
+    /// });
+    /// ```
+    pub fn try_register_serde<T: 'static + Send + Sync + Serialize + DeserializeOwned>(
+        &mut self,
+        key: &'static str,
+    ) -> Result<(), error::Register> {
+        self.serde_registry.insert::<T>(RegistryEntry {
+            key,
+            type_id: TypeId::of::<T>(),
+            serialize: serialize_sparse_set::<T>,
+            deserialize: deserialize_sparse_set::<T>,
+            load: load_sparse_set::<T>,
+            add: Some(add_sparse_set::<T>),
+        })
+    }
+    /// Registers `T`'s storage under `key`, it'll be part of serialized `World`s.  
+    /// The key is what ties serialized data to a storage, it has to stay the same between saving and loading.  
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn register_serde<T: 'static + Send + Sync + Serialize + DeserializeOwned>(
+        &mut self,
+        key: &'static str,
+    ) {
+        self.try_register_serde::<T>(key).unwrap()
+    }
+    /// Registers `T`'s storage under `key`, it'll be part of serialized `World`s.  
+    /// When entities are given new ids, `T`'s components will be updated using `MapEntities`.
+    pub fn try_register_serde_mapped<
+        T: 'static + Send + Sync + Serialize + DeserializeOwned + MapEntities,
+    >(
+        &mut self,
+        key: &'static str,
+    ) -> Result<(), error::Register> {
+        self.serde_registry.insert::<T>(RegistryEntry {
+            key,
+            type_id: TypeId::of::<T>(),
+            serialize: serialize_sparse_set::<T>,
+            deserialize: deserialize_sparse_set::<T>,
+            load: load_sparse_set::<T>,
+            add: Some(add_mapped_sparse_set::<T>),
+        })
+    }
+    /// Registers `T`'s storage under `key`, it'll be part of serialized `World`s.  
+    /// When entities are given new ids, `T`'s components will be updated using `MapEntities`.  
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn register_serde_mapped<
+        T: 'static + Send + Sync + Serialize + DeserializeOwned + MapEntities,
+    >(
+        &mut self,
+        key: &'static str,
+    ) {
+        self.try_register_serde_mapped::<T>(key).unwrap()
+    }
+    /// Registers `T`'s unique storage under `key`, it'll be part of serialized `World`s.
+    pub fn try_register_unique_serde<T: 'static + Send + Sync + Serialize + DeserializeOwned>(
+        &mut self,
+        key: &'static str,
+    ) -> Result<(), error::Register> {
+        self.serde_registry.insert::<T>(RegistryEntry {
+            key,
+            type_id: TypeId::of::<T>(),
+            serialize: serialize_unique::<T>,
+            deserialize: deserialize_unique::<T>,
+            load: load_unique::<T>,
+            add: None,
+        })
+    }
+    /// Registers `T`'s unique storage under `key`, it'll be part of serialized `World`s.
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn register_unique_serde<T: 'static + Send + Sync + Serialize + DeserializeOwned>(
+        &mut self,
+        key: &'static str,
+    ) {
+        self.try_register_unique_serde::<T>(key).unwrap()
+    }
+}
+
+impl AllStorages {
+    /// Serializes `Entities` and all storages registered with `register_serde`, `register_serde_mapped` or `register_unique_serde`.
+    /// Registered storages that don't exist yet are skipped.
+    pub fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.lock.lock_shared();
+        // SAFE we locked
+        let storages = unsafe { &*self.storages.get() };
+
+        let result = (|| {
+            // AllStorages is always created with Entities so there's no way to not find it
+            let entities = storages[&TypeId::of::<Entities>()]
+                .entities()
+                .map_err(ser::Error::custom)?;
+
+            let mut state = serializer.serialize_struct("World", 2)?;
+            state.serialize_field("entities", &*entities)?;
+            state.serialize_field(
+                "storages",
+                &StoragesSnapshot {
+                    registry: &self.serde_registry,
+                    storages: &*self,
+                },
+            )?;
+            state.end()
+        })();
+
+        self.lock.unlock_shared();
+        result
+    }
+    /// Replaces `Entities` and all storages' content with the serialized ones.
+    /// Storages missing from the serialized data are emptied.
+    /// Packs aren't serialized, they have to be set up before loading.
+    /// Tight and loose packs are rebuilt from the loaded components.
+    /// Loaded components are considered inserted at the tick of the load.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        &mut self,
+        deserializer: D,
+    ) -> Result<(), D::Error> {
+        // loading storages requires a unique access to all storages, including the registry
+        let registry = self.serde_registry.clone();
+        let (entities, loaded) = deserializer.deserialize_struct(
+            "World",
+            FIELDS,
+            WorldVisitor {
+                registry: &registry,
+            },
+        )?;
+
+        self.restore_with(
+            |old_entities| *old_entities = entities,
+            |all_storages| {
+                for (entry, storage) in loaded {
+                    (entry.load)(all_storages, storage).map_err(de::Error::custom)?;
+                }
+
+                Ok(())
+            },
+        )
+    }
+    /// Adds the serialized entities and their components, giving each entity a new id.  
+    /// Returns the id each serialized entity was given.
+    ///
+    /// Components registered with `register_serde_mapped` get their `EntityId`s updated.
+    /// Unique storages are not loaded.
+    pub fn deserialize_additive<'de, D: Deserializer<'de>>(
+        &mut self,
+        deserializer: D,
+    ) -> Result<EntityMap, D::Error> {
+        // adding components requires a unique access to all storages, including the registry
+        let registry = self.serde_registry.clone();
+        let (loaded_entities, loaded) = deserializer.deserialize_struct(
+            "World",
+            FIELDS,
+            WorldVisitor {
+                registry: &registry,
+            },
+        )?;
+
+        let mut entity_map = EntityMap::new();
+        {
+            // we have unique access to all storages so we can unwrap
+            let mut entities = self.storages.get_mut()[&TypeId::of::<Entities>()]
+                .entities_mut()
+                .unwrap();
+
+            for entity in &loaded_entities {
+                entity_map.insert(entity, entities.generate());
+            }
+        }
+
+        let mut new_components = Vec::new();
+        for (entry, storage) in loaded {
+            if let Some(add) = entry.add {
+                add(self, storage, &entity_map, &mut new_components).map_err(de::Error::custom)?;
+            }
+        }
+
+        // entities are given all their components before being packed
+        new_components.sort_unstable();
+        let storages = self.storages.get_mut();
+        let mut types = Vec::new();
+        let mut i = 0;
+        while i < new_components.len() {
+            let entity = new_components[i].0;
+            types.clear();
+            types.extend(
+                new_components[i..]
+                    .iter()
+                    .take_while(|&&(other, _)| other == entity)
+                    .map(|&(_, type_id)| type_id),
+            );
+            i += types.len();
+
+            for type_id in &types {
+                // we have unique access to all storages so we can unwrap
+                storages
+                    .get_mut(type_id)
+                    .unwrap()
+                    .pack(entity, &types)
+                    .unwrap();
+            }
+        }
+
+        Ok(entity_map)
+    }
+}
+
+struct StoragesSnapshot<'a> {
+    registry: &'a SerdeRegistry,
+    storages: &'a AllStorages,
+}
+
+impl Serialize for StoragesSnapshot<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        // SAFE the lock is held by AllStorages::serialize
+        let storages = unsafe { &*self.storages.storages.get() };
+
+        let len = self
+            .registry
+            .entries
+            .iter()
+            .filter(|entry| storages.contains_key(&entry.type_id))
+            .count();
+
+        let mut map = serializer.serialize_map(Some(len))?;
+        for entry in &self.registry.entries {
+            if let Some(storage) = storages.get(&entry.type_id) {
+                let storage = (entry.serialize)(storage).map_err(ser::Error::custom)?;
+                map.serialize_entry(entry.key, &*storage)?;
+            }
+        }
+        map.end()
+    }
+}
+
+struct SparseSetRef<'a, T>(Ref<'a, SparseSet<T>>);
+
+impl<T: Serialize> Serialize for SparseSetRef<'_, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (*self.0).serialize(serializer)
+    }
+}
+
+struct UniqueRef<'a, T>(Ref<'a, T>);
+
+impl<T: Serialize> Serialize for UniqueRef<'_, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (*self.0).serialize(serializer)
+    }
+}
+
+fn serialize_sparse_set<T: 'static + Serialize>(
+    storage: &Storage,
+) -> Result<Box<dyn erased_serde::Serialize + '_>, error::GetStorage> {
+    Ok(Box::new(SparseSetRef(storage.sparse_set::<T>()?)))
+}
+
+fn deserialize_sparse_set<T: 'static + DeserializeOwned>(
+    deserializer: &mut dyn erased_serde::Deserializer<'_>,
+    index_count: Option<usize>,
+) -> Result<Box<dyn Any>, erased_serde::Error> {
+    Ok(Box::new(
+        LoadedSparseSetSeed::<T>::new(index_count).deserialize(deserializer)?,
+    ))
+}
+
+fn load_sparse_set<T: 'static + Send + Sync>(
+    all_storages: &mut AllStorages,
+    loaded: Box<dyn Any>,
+) -> Result<(), error::GetStorage> {
+    // the registry always pairs the deserialize and load functions of the same type
+    let loaded = loaded.downcast::<LoadedSparseSet<T>>().unwrap();
+
+    let mut sparse_set = all_storages
+        .storages
+        .get_mut()
+        .entry(TypeId::of::<T>())
+        .or_insert_with(Storage::new::<T>)
+        .sparse_set_mut::<T>()?;
+
+    loaded.load_into(&mut sparse_set);
+
+    Ok(())
+}
+
+fn add_sparse_set<T: 'static + Send + Sync>(
+    all_storages: &mut AllStorages,
+    loaded: Box<dyn Any>,
+    entity_map: &EntityMap,
+    new_components: &mut Vec<(EntityId, TypeId)>,
+) -> Result<(), error::GetStorage> {
+    add_components::<T>(all_storages, loaded, entity_map, new_components, |_| {})
+}
+
+fn add_mapped_sparse_set<T: 'static + Send + Sync + MapEntities>(
+    all_storages: &mut AllStorages,
+    loaded: Box<dyn Any>,
+    entity_map: &EntityMap,
+    new_components: &mut Vec<(EntityId, TypeId)>,
+) -> Result<(), error::GetStorage> {
+    add_components::<T>(
+        all_storages,
+        loaded,
+        entity_map,
+        new_components,
+        |component| component.map_entities(entity_map),
+    )
+}
+
+fn add_components<T: 'static + Send + Sync>(
+    all_storages: &mut AllStorages,
+    loaded: Box<dyn Any>,
+    entity_map: &EntityMap,
+    new_components: &mut Vec<(EntityId, TypeId)>,
+    map_component: impl FnMut(&mut T),
+) -> Result<(), error::GetStorage> {
+    // the registry always pairs the deserialize and add functions of the same type
+    let loaded = loaded.downcast::<LoadedSparseSet<T>>().unwrap();
+
+    let mut sparse_set = all_storages
+        .storages
+        .get_mut()
+        .entry(TypeId::of::<T>())
+        .or_insert_with(Storage::new::<T>)
+        .sparse_set_mut::<T>()?;
+
+    let type_id = TypeId::of::<T>();
+    new_components.extend(
+        loaded
+            .add_into(&mut sparse_set, entity_map, map_component)
+            .into_iter()
+            .map(|entity| (entity, type_id)),
+    );
+
+    Ok(())
+}
+
+fn serialize_unique<T: 'static + Serialize>(
+    storage: &Storage,
+) -> Result<Box<dyn erased_serde::Serialize + '_>, error::GetStorage> {
+    Ok(Box::new(UniqueRef(storage.unique::<T>()?)))
+}
+
+fn deserialize_unique<T: 'static + DeserializeOwned>(
+    deserializer: &mut dyn erased_serde::Deserializer<'_>,
+    _: Option<usize>,
+) -> Result<Box<dyn Any>, erased_serde::Error> {
+    Ok(Box::new(erased_serde::deserialize::<T>(deserializer)?))
+}
+
+fn load_unique<T: 'static + Send + Sync>(
+    all_storages: &mut AllStorages,
+    loaded: Box<dyn Any>,
+) -> Result<(), error::GetStorage> {
+    // the registry always pairs the deserialize and load functions of the same type
+    let unique = *loaded.downcast::<T>().unwrap();
+
+    match all_storages.storages.get_mut().entry(TypeId::of::<T>()) {
+        Entry::Occupied(entry) => *entry.get().unique_mut::<T>()? = unique,
+        Entry::Vacant(entry) => {
+            entry.insert(Storage::new_unique::<T>(unique));
+        }
+    }
+
+    Ok(())
+}
+
+enum Field {
+    Entities,
+    Storages,
+}
+
+impl<'de> Deserialize<'de> for Field {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct FieldVisitor;
+
+        impl<'de> Visitor<'de> for FieldVisitor {
+            type Value = Field;
+
+            fn expecting(&self, formatter: &mut Formatter<'_>) -> core::fmt::Result {
+                formatter.write_str("`entities` or `storages`")
+            }
+            fn visit_str<E>(self, value: &str) -> Result<Field, E>
+            where
+                E: de::Error,
+            {
+                match value {
+                    "entities" => Ok(Field::Entities),
+                    "storages" => Ok(Field::Storages),
+                    _ => Err(de::Error::unknown_field(value, FIELDS)),
+                }
+            }
+        }
+
+        deserializer.deserialize_identifier(FieldVisitor)
+    }
+}
+
+type LoadedStorages<'a> = Vec<(&'a RegistryEntry, Box<dyn Any>)>;
+
+struct WorldVisitor<'a> {
+    registry: &'a SerdeRegistry,
+}
+
+impl<'de, 'a> Visitor<'de> for WorldVisitor<'a> {
+    type Value = (Entities, LoadedStorages<'a>);
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> core::fmt::Result {
+        formatter.write_str("struct World")
+    }
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let entities: Entities = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let storages = seq
+            .next_element_seed(StoragesSeed {
+                registry: self.registry,
+                index_count: Some(entities.index_count()),
+            })?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+        Ok((entities, storages))
+    }
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entities: Option<Entities> = None;
+        let mut storages = None;
+
+        while let Some(key) = map.next_key()? {
+            match key {
+                Field::Entities => {
+                    if entities.is_some() {
+                        return Err(de::Error::duplicate_field("entities"));
+                    }
+                    entities = Some(map.next_value()?);
+                }
+                Field::Storages => {
+                    if storages.is_some() {
+                        return Err(de::Error::duplicate_field("storages"));
+                    }
+                    // when storages come first, shared components can't be checked against the entities
+                    storages = Some(map.next_value_seed(StoragesSeed {
+                        registry: self.registry,
+                        index_count: entities.as_ref().map(Entities::index_count),
+                    })?);
+                }
+            }
+        }
+
+        let entities = entities.ok_or_else(|| de::Error::missing_field("entities"))?;
+
+        Ok((entities, storages.unwrap_or_default()))
+    }
+}
+
+struct StoragesSeed<'a> {
+    registry: &'a SerdeRegistry,
+    index_count: Option<usize>,
+}
+
+impl<'de, 'a> DeserializeSeed<'de> for StoragesSeed<'a> {
+    type Value = LoadedStorages<'a>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, 'a> Visitor<'de> for StoragesSeed<'a> {
+    type Value = LoadedStorages<'a>;
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> core::fmt::Result {
+        formatter.write_str("a map of storages")
+    }
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut storages = Vec::with_capacity(map.size_hint().unwrap_or(0));
+
+        while let Some(entry) = map.next_key_seed(KeySeed {
+            registry: self.registry,
+        })? {
+            let storage = map.next_value_seed(EntrySeed {
+                entry,
+                index_count: self.index_count,
+            })?;
+            storages.push((entry, storage));
+        }
+
+        Ok(storages)
+    }
+}
+
+/// Finds the registry entry matching a key.
+#[derive(Clone, Copy)]
+struct KeySeed<'a> {
+    registry: &'a SerdeRegistry,
+}
+
+impl<'de, 'a> DeserializeSeed<'de> for KeySeed<'a> {
+    type Value = &'a RegistryEntry;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'de, 'a> Visitor<'de> for KeySeed<'a> {
+    type Value = &'a RegistryEntry;
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> core::fmt::Result {
+        formatter.write_str("a registered storage key")
+    }
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.registry
+            .entries
+            .iter()
+            .find(|entry| entry.key == value)
+            .ok_or_else(|| {
+                de::Error::custom(format_args!("no storage registered with key \"{}\"", value))
+            })
+    }
+}
+
+struct EntrySeed<'a> {
+    entry: &'a RegistryEntry,
+    index_count: Option<usize>,
+}
+
+impl<'de> DeserializeSeed<'de> for EntrySeed<'_> {
+    type Value = Box<dyn Any>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut deserializer = <dyn erased_serde::Deserializer<'_>>::erase(deserializer);
+        (self.entry.deserialize)(&mut deserializer, self.index_count).map_err(de::Error::custom)
+    }
+}
@@ -0,0 +1,271 @@
+use super::AllStorages;
+use crate::error;
+use crate::storage::{Entities, Storage};
+use alloc::vec::Vec;
+use core::any::{type_name, TypeId};
+use core::mem::{size_of, size_of_val, MaybeUninit};
+use core::ops::Range;
+use core::ptr;
+use hashbrown::hash_map::Entry;
+use parking_lot::lock_api::RawRwLock as _;
+
+type SaveFn = fn(&Storage, &mut Vec<MaybeUninit<u8>>) -> Result<(), error::GetStorage>;
+type RestoreFn = fn(&mut AllStorages, &mut RawReader<'_>) -> Result<(), error::GetStorage>;
+
+/// Type erased functions to copy a storage registered with `register_snapshot` or `register_unique_snapshot`.
+#[derive(Clone, Copy)]
+pub(crate) struct SnapshotEntry {
+    type_id: TypeId,
+    save: SaveFn,
+    restore: RestoreFn,
+}
+
+/// Raw copy of `Entities` and some storages, made by `AllStorages::snapshot`.
+///
+/// A snapshot can only be restored by the program that made it.
+#[derive(Clone)]
+pub struct Snapshot {
+    // padding bytes are copied too, they can be uninitialized
+    buffer: Vec<MaybeUninit<u8>>,
+    entities: Range<usize>,
+    storages: Vec<(TypeId, RestoreFn, Range<usize>)>,
+}
+
+impl Snapshot {
+    /// Returns the size of the snapshot in bytes.
+    pub fn size(&self) -> usize {
+        self.buffer.len()
+    }
+}
+
+impl AllStorages {
+    /// Registers `T`'s storage, it'll be part of snapshots.  
+    /// Only storages of `Copy` types can be registered, their memory is copied as is.
+    pub fn try_register_snapshot<T: 'static + Send + Sync + Copy>(
+        &mut self,
+    ) -> Result<(), error::Register> {
+        self.insert_snapshot_entry::<T>(SnapshotEntry {
+            type_id: TypeId::of::<T>(),
+            save: save_sparse_set::<T>,
+            restore: restore_sparse_set::<T>,
+        })
+    }
+    /// Registers `T`'s storage, it'll be part of snapshots.  
+    /// Only storages of `Copy` types can be registered, their memory is copied as is.  
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn register_snapshot<T: 'static + Send + Sync + Copy>(&mut self) {
+        self.try_register_snapshot::<T>().unwrap()
+    }
+    /// Registers `T`'s unique storage, it'll be part of snapshots.
+    pub fn try_register_unique_snapshot<T: 'static + Send + Sync + Copy>(
+        &mut self,
+    ) -> Result<(), error::Register> {
+        self.insert_snapshot_entry::<T>(SnapshotEntry {
+            type_id: TypeId::of::<T>(),
+            save: save_unique::<T>,
+            restore: restore_unique::<T>,
+        })
+    }
+    /// Registers `T`'s unique storage, it'll be part of snapshots.
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn register_unique_snapshot<T: 'static + Send + Sync + Copy>(&mut self) {
+        self.try_register_unique_snapshot::<T>().unwrap()
+    }
+    fn insert_snapshot_entry<T: 'static>(
+        &mut self,
+        entry: SnapshotEntry,
+    ) -> Result<(), error::Register> {
+        if self
+            .snapshot_registry
+            .iter()
+            .any(|other| other.type_id == entry.type_id)
+        {
+            Err(error::Register::TypeAlreadyRegistered(type_name::<T>()))
+        } else {
+            self.snapshot_registry.push(entry);
+            Ok(())
+        }
+    }
+    /// Copies `Entities` and all storages registered with `register_snapshot` or `register_unique_snapshot`.
+    /// Registered storages that don't exist yet are skipped.
+    pub fn try_snapshot(&self) -> Result<Snapshot, error::GetStorage> {
+        self.lock.lock_shared();
+        // SAFE we locked
+        let storages = unsafe { &*self.storages.get() };
+
+        let result = (|| {
+            let mut buffer = Vec::new();
+
+            // AllStorages is always created with Entities so there's no way to not find it
+            storages[&TypeId::of::<Entities>()]
+                .entities()
+                .map_err(error::GetStorage::Entities)?
+                .write_snapshot(&mut buffer);
+            let entities = 0..buffer.len();
+
+            let mut saved = Vec::with_capacity(self.snapshot_registry.len());
+            for entry in &self.snapshot_registry {
+                if let Some(storage) = storages.get(&entry.type_id) {
+                    let start = buffer.len();
+                    (entry.save)(storage, &mut buffer)?;
+                    saved.push((entry.type_id, entry.restore, start..buffer.len()));
+                }
+            }
+
+            Ok(Snapshot {
+                buffer,
+                entities,
+                storages: saved,
+            })
+        })();
+
+        self.lock.unlock_shared();
+        result
+    }
+    /// Copies `Entities` and all storages registered with `register_snapshot` or `register_unique_snapshot`.
+    /// Registered storages that don't exist yet are skipped.
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn snapshot(&self) -> Snapshot {
+        self.try_snapshot().unwrap()
+    }
+    /// Brings back `Entities` and all storages to the state saved in `snapshot`.
+    /// Storages that aren't part of the snapshot are emptied.
+    /// Packs aren't part of the snapshot, they have to be set up before restoring.
+    /// Tight and loose packs are rebuilt from the restored components.
+    /// Restored components are considered inserted at the tick of the restore.
+    pub fn try_restore_snapshot(&mut self, snapshot: &Snapshot) -> Result<(), error::GetStorage> {
+        self.restore_with(
+            |entities| {
+                entities.restore_snapshot(&mut RawReader::new(
+                    &snapshot.buffer[snapshot.entities.clone()],
+                ))
+            },
+            |all_storages| {
+                for (_, restore, range) in &snapshot.storages {
+                    restore(
+                        all_storages,
+                        &mut RawReader::new(&snapshot.buffer[range.clone()]),
+                    )?;
+                }
+
+                Ok(())
+            },
+        )
+    }
+    /// Brings back `Entities` and all storages to the state saved in `snapshot`.
+    /// Storages that aren't part of the snapshot are emptied.
+    /// Packs aren't part of the snapshot, they have to be set up before restoring.
+    /// Tight and loose packs are rebuilt from the restored components.
+    /// Restored components are considered inserted at the tick of the restore.
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn restore_snapshot(&mut self, snapshot: &Snapshot) {
+        self.try_restore_snapshot(snapshot).unwrap()
+    }
+}
+
+fn save_sparse_set<T: 'static + Copy>(
+    storage: &Storage,
+    buffer: &mut Vec<MaybeUninit<u8>>,
+) -> Result<(), error::GetStorage> {
+    storage.sparse_set::<T>()?.write_snapshot(buffer);
+    Ok(())
+}
+
+fn restore_sparse_set<T: 'static + Send + Sync + Copy>(
+    all_storages: &mut AllStorages,
+    reader: &mut RawReader<'_>,
+) -> Result<(), error::GetStorage> {
+    all_storages
+        .storages
+        .get_mut()
+        .entry(TypeId::of::<T>())
+        .or_insert_with(Storage::new::<T>)
+        .sparse_set_mut::<T>()?
+        .restore_snapshot(reader);
+
+    Ok(())
+}
+
+fn save_unique<T: 'static + Copy>(
+    storage: &Storage,
+    buffer: &mut Vec<MaybeUninit<u8>>,
+) -> Result<(), error::GetStorage> {
+    write_raw(buffer, core::slice::from_ref(&*storage.unique::<T>()?));
+    Ok(())
+}
+
+fn restore_unique<T: 'static + Send + Sync + Copy>(
+    all_storages: &mut AllStorages,
+    reader: &mut RawReader<'_>,
+) -> Result<(), error::GetStorage> {
+    let unique = reader.read::<T>();
+
+    match all_storages.storages.get_mut().entry(TypeId::of::<T>()) {
+        Entry::Occupied(entry) => *entry.get().unique_mut::<T>()? = unique,
+        Entry::Vacant(entry) => {
+            entry.insert(Storage::new_unique::<T>(unique));
+        }
+    }
+
+    Ok(())
+}
+
+/// Copies `slice`'s memory at the end of `buffer`.
+pub(crate) fn write_raw<T: Copy>(buffer: &mut Vec<MaybeUninit<u8>>, slice: &[T]) {
+    let size = size_of_val(slice);
+    buffer.reserve(size);
+
+    // SAFE we reserved enough space and MaybeUninit<u8> doesn't require initialized memory
+    unsafe {
+        ptr::copy_nonoverlapping(
+            slice.as_ptr() as *const MaybeUninit<u8>,
+            buffer.as_mut_ptr().add(buffer.len()),
+            size,
+        );
+        buffer.set_len(buffer.len() + size);
+    }
+}
+
+/// Reads values written by `write_raw`, in the same order.
+pub(crate) struct RawReader<'a> {
+    buffer: &'a [MaybeUninit<u8>],
+}
+
+impl<'a> RawReader<'a> {
+    pub(crate) fn new(buffer: &'a [MaybeUninit<u8>]) -> Self {
+        RawReader { buffer }
+    }
+    pub(crate) fn read<T: Copy>(&mut self) -> T {
+        assert!(self.buffer.len() >= size_of::<T>());
+
+        // SAFE the buffer is long enough and was written with a T at this position
+        let value = unsafe { ptr::read_unaligned(self.buffer.as_ptr() as *const T) };
+        self.buffer = &self.buffer[size_of::<T>()..];
+        value
+    }
+    pub(crate) fn read_vec<T: Copy>(&mut self, len: usize) -> Vec<T> {
+        let size = size_of::<T>() * len;
+        assert!(self.buffer.len() >= size);
+
+        let mut vec = Vec::with_capacity(len);
+        // SAFE the buffer is long enough and was written with len T at this position
+        unsafe {
+            ptr::copy_nonoverlapping(
+                self.buffer.as_ptr(),
+                vec.as_mut_ptr() as *mut MaybeUninit<u8>,
+                size,
+            );
+            vec.set_len(len);
+        }
+        self.buffer = &self.buffer[size..];
+        vec
+    }
+}
@@ -0,0 +1,105 @@
+use super::EntityId;
+use alloc::vec::Vec;
+
+/// Links `EntityId`s from one source to the ones they were given in a `World`.
+///
+/// Returned when entities are added to a `World` without keeping their original id.
+#[derive(Clone, Default)]
+pub struct EntityMap {
+    // sorted by index, an index is only present once
+    pairs: Vec<(EntityId, EntityId)>,
+}
+
+impl EntityMap {
+    /// Creates an empty `EntityMap`.
+    pub fn new() -> Self {
+        EntityMap::default()
+    }
+    /// Returns the `EntityId` `entity` was given.
+    pub fn get(&self, entity: EntityId) -> Option<EntityId> {
+        self.pairs
+            .binary_search_by_key(&entity.index(), |(old, _)| old.index())
+            .ok()
+            .map(|i| self.pairs[i])
+            .filter(|&(old, _)| old == entity)
+            .map(|(_, new)| new)
+    }
+    /// Returns the `EntityId` `entity` was given or `entity` itself if it isn't part of the map.
+    pub fn map(&self, entity: EntityId) -> EntityId {
+        self.get(entity).unwrap_or(entity)
+    }
+    /// Returns the number of entities in the map.
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+    /// Returns `true` if the map doesn't contain any entity.
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+    /// Iterates over all `(original, new)` pairs.
+    pub fn iter(&self) -> impl Iterator<Item = (EntityId, EntityId)> + '_ {
+        self.pairs.iter().copied()
+    }
+    /// Returns the `EntityId` given to the entity at `index`.
+    #[cfg(feature = "serde")]
+    pub(crate) fn get_index(&self, index: u64) -> Option<EntityId> {
+        self.pairs
+            .binary_search_by_key(&index, |(old, _)| old.index())
+            .ok()
+            .map(|i| self.pairs[i].1)
+    }
+    /// Links `old` to `new`, replacing any entity with the same index as `old`.
+    pub(crate) fn insert(&mut self, old: EntityId, new: EntityId) {
+        match self
+            .pairs
+            .binary_search_by_key(&old.index(), |(old, _)| old.index())
+        {
+            Ok(i) => self.pairs[i] = (old, new),
+            Err(i) => self.pairs.insert(i, (old, new)),
+        }
+    }
+}
+
+/// Rewrites the `EntityId`s a component holds when its entities are given new ids.
+///
+/// ### Example
+/// ```
+/// use shipyard::{EntityId, EntityMap, MapEntities};
+///
+/// struct Parent {
+///     first_child: EntityId,
+///     children: Vec<EntityId>,
+/// }
+///
+/// impl MapEntities for Parent {
+///     fn map_entities(&mut self, entity_map: &EntityMap) {
+///         self.first_child.map_entities(entity_map);
+///         self.children.map_entities(entity_map);
+///     }
+/// }
+/// ```
+pub trait MapEntities {
+    fn map_entities(&mut self, entity_map: &EntityMap);
+}
+
+impl MapEntities for EntityId {
+    fn map_entities(&mut self, entity_map: &EntityMap) {
+        *self = entity_map.map(*self);
+    }
+}
+
+impl<T: MapEntities> MapEntities for Option<T> {
+    fn map_entities(&mut self, entity_map: &EntityMap) {
+        if let Some(value) = self {
+            value.map_entities(entity_map);
+        }
+    }
+}
+
+impl<T: MapEntities> MapEntities for Vec<T> {
+    fn map_entities(&mut self, entity_map: &EntityMap) {
+        for value in self {
+            value.map_entities(entity_map);
+        }
+    }
+}
@@ -1,9 +1,14 @@
 mod add_component;
 mod entity_id;
 mod iterator;
+mod map;
+#[cfg(feature = "serde")]
+mod serde;
+mod snapshot;
 
 pub use entity_id::EntityId;
 pub use iterator::EntitiesIter;
+pub use map::{EntityMap, MapEntities};
 
 use crate::error;
 use crate::sparse_set::ViewAddEntity;
@@ -42,6 +47,46 @@ impl Entities {
             list: None,
         }
     }
+    /// Returns the number of indices given to entities so far, dead or alive.
+    #[cfg(feature = "serde")]
+    pub(crate) fn index_count(&self) -> usize {
+        self.data.len()
+    }
+    /// Copies all entities and the list of removed entities.
+    pub(crate) fn checkpoint(&self) -> Self {
+        Entities {
+            data: self.data.clone(),
+            list: self.list,
+        }
+    }
+    /// Returns the entities alive in `self` but not in `previous` and the ones alive in `previous` but not in `self`.
+    pub(crate) fn delta(&self, previous: &Entities) -> (Vec<EntityId>, Vec<EntityId>) {
+        let mut created = Vec::new();
+        let mut destroyed = Vec::new();
+
+        for index in 0..self.data.len().max(previous.data.len()) {
+            let entity = self
+                .data
+                .get(index)
+                .filter(|entity| entity.uindex() == index);
+            let old_entity = previous
+                .data
+                .get(index)
+                .filter(|entity| entity.uindex() == index);
+
+            if entity != old_entity {
+                created.extend(entity);
+                destroyed.extend(old_entity);
+            }
+        }
+
+        (created, destroyed)
+    }
+    /// Replaces all entities with the ones in `checkpoint`.
+    pub(crate) fn restore_checkpoint(&mut self, checkpoint: &Entities) {
+        self.data.clone_from(&checkpoint.data);
+        self.list = checkpoint.list;
+    }
     pub(super) fn delete(&mut self, entity: EntityId) -> bool {
         self.delete_unchecked(entity)
     }
@@ -192,6 +237,9 @@ impl Entities {
 
 impl UnknownStorage for Entities {
     fn delete(&mut self, _entity: EntityId, _: &mut Vec<TypeId>) {}
+    fn type_name(&self) -> &'static str {
+        core::any::type_name::<Entities>()
+    }
     fn clear(&mut self) {
         if self.data.is_empty() {
             return;
@@ -0,0 +1,132 @@
+use super::{Entities, EntityId};
+use alloc::vec::Vec;
+use core::fmt::Formatter;
+use serde::de::{self, MapAccess, SeqAccess, Visitor};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+const FIELDS: &[&str] = &["data", "list"];
+
+// the free list is kept as is so a deserialized `Entities` will generate the same ids
+impl Serialize for Entities {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Entities", 2)?;
+        state.serialize_field("data", &self.data)?;
+        state.serialize_field("list", &self.list)?;
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Entities {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct("Entities", FIELDS, EntitiesVisitor)
+    }
+}
+
+enum Field {
+    Data,
+    List,
+}
+
+impl<'de> Deserialize<'de> for Field {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct FieldVisitor;
+
+        impl<'de> Visitor<'de> for FieldVisitor {
+            type Value = Field;
+
+            fn expecting(&self, formatter: &mut Formatter<'_>) -> core::fmt::Result {
+                formatter.write_str("`data` or `list`")
+            }
+            fn visit_str<E>(self, value: &str) -> Result<Field, E>
+            where
+                E: de::Error,
+            {
+                match value {
+                    "data" => Ok(Field::Data),
+                    "list" => Ok(Field::List),
+                    _ => Err(de::Error::unknown_field(value, FIELDS)),
+                }
+            }
+        }
+
+        deserializer.deserialize_identifier(FieldVisitor)
+    }
+}
+
+struct EntitiesVisitor;
+
+impl<'de> Visitor<'de> for EntitiesVisitor {
+    type Value = Entities;
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> core::fmt::Result {
+        formatter.write_str("struct Entities")
+    }
+    fn visit_seq<A>(self, mut seq: A) -> Result<Entities, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let data = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let list = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+        new_entities(data, list)
+    }
+    fn visit_map<A>(self, mut map: A) -> Result<Entities, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut data = None;
+        let mut list = None;
+
+        while let Some(key) = map.next_key()? {
+            match key {
+                Field::Data => {
+                    if data.is_some() {
+                        return Err(de::Error::duplicate_field("data"));
+                    }
+                    data = Some(map.next_value()?);
+                }
+                Field::List => {
+                    if list.is_some() {
+                        return Err(de::Error::duplicate_field("list"));
+                    }
+                    list = Some(map.next_value()?);
+                }
+            }
+        }
+
+        let data = data.ok_or_else(|| de::Error::missing_field("data"))?;
+        let list = list.ok_or_else(|| de::Error::missing_field("list"))?;
+
+        new_entities(data, list)
+    }
+}
+
+/// Makes sure the free list only points inside `data` before creating `Entities`.
+fn new_entities<E: de::Error>(
+    data: Vec<EntityId>,
+    list: Option<(usize, usize)>,
+) -> Result<Entities, E> {
+    if let Some((new, old)) = list {
+        if new >= data.len() || old >= data.len() {
+            return Err(de::Error::custom(
+                "the list of removed entities points outside of the entities",
+            ));
+        }
+    }
+
+    Ok(Entities { data, list })
+}
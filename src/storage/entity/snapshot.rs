@@ -0,0 +1,19 @@
+use super::Entities;
+use crate::storage::{write_raw, RawReader};
+use alloc::vec::Vec;
+use core::mem::MaybeUninit;
+
+impl Entities {
+    /// Copies the entities and the list of removed entities at the end of `buffer`.
+    pub(crate) fn write_snapshot(&self, buffer: &mut Vec<MaybeUninit<u8>>) {
+        write_raw(buffer, &[self.data.len()]);
+        write_raw(buffer, &[self.list]);
+        write_raw(buffer, &self.data);
+    }
+    /// Replaces all entities with the ones written by `write_snapshot`.
+    pub(crate) fn restore_snapshot(&mut self, reader: &mut RawReader<'_>) {
+        let len = reader.read::<usize>();
+        self.list = reader.read();
+        self.data = reader.read_vec(len);
+    }
+}
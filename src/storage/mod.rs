@@ -2,10 +2,13 @@ mod all;
 mod entity;
 mod unique;
 
-pub use all::{AllStorages, DeleteAny};
-pub use entity::{Entities, EntitiesIter, EntityId};
+pub use all::{
+    AllStorages, Checkpoint, CommandComponents, Commands, DeleteAny, Delta, Prefab,
+    PrefabComponents, Prefabs, Snapshot, UniqueConflict,
+};
+pub use entity::{Entities, EntitiesIter, EntityId, EntityMap, MapEntities};
 
-pub(crate) use all::TypeIdHasher;
+pub(crate) use all::{write_raw, CommandQueue, RawReader, TypeIdHasher};
 
 use crate::atomic_refcell::{AtomicRefCell, Ref, RefMut};
 use crate::error;
@@ -211,14 +214,76 @@ impl Storage {
         self.0.try_borrow_mut()?.delete(entity, storage_to_unpack);
         Ok(())
     }
+    /// Mutably borrows the container and removes `entity`'s component.
+    pub(crate) fn remove(
+        &mut self,
+        entity: EntityId,
+        storage_to_unpack: &mut Vec<TypeId>,
+    ) -> Result<(), error::Borrow> {
+        self.0.try_borrow_mut()?.remove(entity, storage_to_unpack);
+        Ok(())
+    }
     pub(crate) fn unpack(&mut self, entity: EntityId) -> Result<(), error::Borrow> {
         self.0.try_borrow_mut()?.unpack(entity);
         Ok(())
     }
+    /// Packs `entity` if `components` has all types required by the storage's pack.
+    pub(crate) fn pack(
+        &mut self,
+        entity: EntityId,
+        components: &[TypeId],
+    ) -> Result<(), error::Borrow> {
+        self.0.try_borrow_mut()?.pack(entity, components);
+        Ok(())
+    }
+    /// Empties the storage's tight or loose pack without moving any component.
+    /// Returns the entities that might have to be packed again.
+    pub(crate) fn reset_pack(&mut self) -> Result<Vec<EntityId>, error::Borrow> {
+        Ok(self.0.try_borrow_mut()?.reset_pack())
+    }
+    /// Forgets the change ticks, all components are considered inserted at `tick`.
+    pub(crate) fn reset_ticks(&mut self, tick: u64) -> Result<(), error::Borrow> {
+        self.0.try_borrow_mut()?.reset_ticks(tick);
+        Ok(())
+    }
+    /// Moves `entity`'s component to `other` where it's given to `target`.
+    /// Returns `false` if `other` doesn't store the same type.
+    pub(crate) fn move_component(
+        &mut self,
+        entity: EntityId,
+        other: &mut Storage,
+        target: EntityId,
+        storage_to_unpack: &mut Vec<TypeId>,
+    ) -> Result<bool, error::Borrow> {
+        Ok(self.0.try_borrow_mut()?.move_component(
+            entity,
+            &mut *other.0.try_borrow_mut()?,
+            target,
+            storage_to_unpack,
+        ))
+    }
+    /// Creates an empty storage of the same type and with the same thread restrictions.
+    /// Returns `None` for `Entities` and unique storages.
+    pub(crate) fn new_empty(&self) -> Result<Option<Self>, error::Borrow> {
+        Ok(self.0.try_borrow()?.new_empty(&self.0))
+    }
+    /// Returns the name of the type stored.
+    pub(crate) fn type_name(&self) -> Result<&'static str, error::Borrow> {
+        Ok(self.0.try_borrow()?.type_name())
+    }
+    /// Returns `true` if the storage owns a component for `entity`.
+    pub(crate) fn contains(&self, entity: EntityId) -> Result<bool, error::Borrow> {
+        Ok(self.0.try_borrow()?.contains(entity))
+    }
     pub(crate) fn clear(&mut self) -> Result<(), error::Borrow> {
         self.0.try_borrow_mut()?.clear();
         Ok(())
     }
+    /// Deletes all components without calling any hook.
+    pub(crate) fn actual_clear(&mut self) -> Result<(), error::Borrow> {
+        self.0.try_borrow_mut()?.actual_clear();
+        Ok(())
+    }
 }
 
 #[test]
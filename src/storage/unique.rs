@@ -9,6 +9,9 @@ impl<T: 'static> UnknownStorage for Unique<T> {
     fn delete(&mut self, _: EntityId, _: &mut Vec<TypeId>) {}
     fn clear(&mut self) {}
     fn unpack(&mut self, _: EntityId) {}
+    fn type_name(&self) -> &'static str {
+        core::any::type_name::<T>()
+    }
     fn any(&self) -> &dyn Any {
         &self.0
     }
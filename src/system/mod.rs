@@ -4,6 +4,7 @@ pub use all_storages::AllSystem;
 
 use crate::atomic_refcell::AtomicRefCell;
 use crate::borrow::Borrow;
+use crate::borrow::LocalDefault;
 use crate::borrow::Mutation;
 use crate::error;
 use crate::storage::AllStorages;
@@ -19,9 +20,11 @@ pub trait System<'s, Data, B, R> {
         #[cfg(feature = "parallel")] thread_pool: &'s rayon::ThreadPool,
     ) -> Result<B, error::GetStorage>;
 
-    fn borrow_infos(infos: &mut Vec<(TypeId, Mutation)>);
+    fn borrow_infos(infos: &mut Vec<(TypeId, &'static str, Mutation)>);
 
     fn is_send_sync() -> bool;
+
+    fn local_defaults(_defaults: &mut Vec<(TypeId, LocalDefault)>) {}
 }
 
 // Nothing has to be used and not () to not conflict where A = ()
@@ -39,7 +42,7 @@ where
         Ok(Nothing)
     }
 
-    fn borrow_infos(_: &mut Vec<(TypeId, Mutation)>) {}
+    fn borrow_infos(_: &mut Vec<(TypeId, &'static str, Mutation)>) {}
 
     fn is_send_sync() -> bool {
         true
@@ -61,7 +64,7 @@ where
         Ok(Nothing)
     }
 
-    fn borrow_infos(_: &mut Vec<(TypeId, Mutation)>) {}
+    fn borrow_infos(_: &mut Vec<(TypeId, &'static str, Mutation)>) {}
 
     fn is_send_sync() -> bool {
         true
@@ -87,7 +90,7 @@ macro_rules! impl_system {
                     Ok(($($type::try_borrow(all_storages)?,)+))
                 }
             }
-            fn borrow_infos(infos: &mut Vec<(TypeId, Mutation)>) {
+            fn borrow_infos(infos: &mut Vec<(TypeId, &'static str, Mutation)>) {
                 $(
                     $type::borrow_infos(infos);
                 )+
@@ -97,6 +100,11 @@ macro_rules! impl_system {
                     $type::is_send_sync()
                 )&&+
             }
+            fn local_defaults(defaults: &mut Vec<(TypeId, LocalDefault)>) {
+                $(
+                    $type::local_defaults(defaults);
+                )+
+            }
         }
 
         impl<'s, Data, $($type: Borrow<'s>,)+ R, Func> System<'s, (Data,), ($($type,)+), R> for Func where Func: FnOnce(Data, $($type,)+) -> R {
@@ -116,7 +124,7 @@ macro_rules! impl_system {
                     Ok(($($type::try_borrow(all_storages)?,)+))
                 }
             }
-            fn borrow_infos(infos: &mut Vec<(TypeId, Mutation)>) {
+            fn borrow_infos(infos: &mut Vec<(TypeId, &'static str, Mutation)>) {
                 $(
                     $type::borrow_infos(infos);
                 )+
@@ -126,6 +134,11 @@ macro_rules! impl_system {
                     $type::is_send_sync()
                 )&&+
             }
+            fn local_defaults(defaults: &mut Vec<(TypeId, LocalDefault)>) {
+                $(
+                    $type::local_defaults(defaults);
+                )+
+            }
         }
     }
 }
@@ -1,13 +1,50 @@
+use crate::atomic_refcell::AtomicRefCell;
 use crate::sparse_set::SparseSet;
-use crate::storage::Entities;
-use crate::storage::EntityId;
+use crate::storage::{Entities, EntityId, Storage};
 use alloc::vec::Vec;
 use core::any::{Any, TypeId};
 
 pub(super) trait UnknownStorage {
     fn delete(&mut self, entity: EntityId, storage_to_unpack: &mut Vec<TypeId>);
+    /// Removes `entity`'s component, it's tracked as *removed* and the `on_remove` hook is called.
+    fn remove(&mut self, _entity: EntityId, _storage_to_unpack: &mut Vec<TypeId>) {}
     fn clear(&mut self);
+    /// Deletes all components without calling any hook.
+    fn actual_clear(&mut self) {
+        self.clear();
+    }
     fn unpack(&mut self, entity: EntityId);
+    /// Returns `true` if the storage owns a component for `entity`.
+    fn contains(&self, _entity: EntityId) -> bool {
+        false
+    }
+    /// Packs `entity` if `components` has all types required by the storage's pack.
+    /// `components` has to be sorted.
+    fn pack(&mut self, _entity: EntityId, _components: &[TypeId]) {}
+    /// Empties the storage's tight or loose pack without moving any component.
+    /// Returns the entities that might have to be packed again.
+    fn reset_pack(&mut self) -> Vec<EntityId> {
+        Vec::new()
+    }
+    /// Forgets the change ticks, all components are considered inserted at `tick`.
+    fn reset_ticks(&mut self, _tick: u64) {}
+    /// Moves `entity`'s component to `other` where it's given to `target`.
+    /// Returns `false` if `other` doesn't store the same type.
+    fn move_component(
+        &mut self,
+        _entity: EntityId,
+        _other: &mut dyn UnknownStorage,
+        _target: EntityId,
+        _storage_to_unpack: &mut Vec<TypeId>,
+    ) -> bool {
+        false
+    }
+    /// Creates an empty storage of the same type, `like` is the cell containing `self`.
+    fn new_empty(&self, _like: &AtomicRefCell<dyn UnknownStorage>) -> Option<Storage> {
+        None
+    }
+    /// Returns the name of the type stored.
+    fn type_name(&self) -> &'static str;
     fn any(&self) -> &dyn Any;
     fn any_mut(&mut self) -> &mut dyn Any;
 }
@@ -1,7 +1,7 @@
 use crate::atomic_refcell::{AtomicRefCell, Borrow};
 use crate::atomic_refcell::{Ref, RefMut};
 use crate::error;
-use crate::sparse_set::{SparseSet, Window};
+use crate::sparse_set::{Changes, SparseSet, Window};
 use crate::{AllStorages, Entities};
 use core::convert::TryFrom;
 use core::ops::{Deref, DerefMut};
@@ -262,6 +262,59 @@ impl<'a, T: 'static> View<'a, T> {
     }
 }
 
+#[cfg(feature = "std")]
+impl<T> View<'_, T> {
+    /// Returns the tick of the previous run of the system borrowing this view.  
+    /// It's 0 during the system's first run and outside of workloads.  
+    /// `inserted`, `modified` and `inserted_or_modified` return the components changed after it,
+    /// it can also be passed to `inserted_since` and `modified_since`.
+    pub fn last_run(&self) -> u64 {
+        last_run()
+    }
+}
+
+impl<'a, T> View<'a, T> {
+    /// Returns the components inserted since the system borrowing this view last ran.  
+    /// If the storage doesn't track changes, returns the *inserted* section of the update pack instead.
+    pub fn try_inserted(&self) -> Result<Changes<'a, T>, error::Inserted> {
+        Changes::try_inserted(self.window, last_run())
+    }
+    /// Returns the components inserted since the system borrowing this view last ran.  
+    /// If the storage doesn't track changes, returns the *inserted* section of the update pack instead.  
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn inserted(&self) -> Changes<'a, T> {
+        self.try_inserted().unwrap()
+    }
+    /// Returns the components modified since the system borrowing this view last ran, except the ones inserted since then.  
+    /// If the storage doesn't track changes, returns the *modified* section of the update pack instead.
+    pub fn try_modified(&self) -> Result<Changes<'a, T>, error::Modified> {
+        Changes::try_modified(self.window, last_run())
+    }
+    /// Returns the components modified since the system borrowing this view last ran, except the ones inserted since then.  
+    /// If the storage doesn't track changes, returns the *modified* section of the update pack instead.  
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn modified(&self) -> Changes<'a, T> {
+        self.try_modified().unwrap()
+    }
+    /// Returns the components inserted or modified since the system borrowing this view last ran.  
+    /// If the storage doesn't track changes, returns the *inserted* and *modified* sections of the update pack instead.
+    pub fn try_inserted_or_modified(&self) -> Result<Changes<'a, T>, error::InsertedOrModified> {
+        Changes::try_inserted_or_modified(self.window, last_run())
+    }
+    /// Returns the components inserted or modified since the system borrowing this view last ran.  
+    /// If the storage doesn't track changes, returns the *inserted* and *modified* sections of the update pack instead.  
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn inserted_or_modified(&self) -> Changes<'a, T> {
+        self.try_inserted_or_modified().unwrap()
+    }
+}
+
 impl<'a, T> Deref for View<'a, T> {
     type Target = Window<'a, T>;
     fn deref(&self) -> &Self::Target {
@@ -275,6 +328,16 @@ impl<'a, T> AsRef<Window<'a, T>> for View<'a, T> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for View<'_, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.window.parts().serialize(serializer)
+    }
+}
+
 /// Exclusive view over a component storage.
 pub struct ViewMut<'a, T> {
     sparse_set: RefMut<'a, SparseSet<T>>,
@@ -369,6 +432,59 @@ impl<'a, T: 'static> ViewMut<'a, T> {
     }
 }
 
+#[cfg(feature = "std")]
+impl<T> ViewMut<'_, T> {
+    /// Returns the tick of the previous run of the system borrowing this view.  
+    /// It's 0 during the system's first run and outside of workloads.  
+    /// `inserted`, `modified` and `inserted_or_modified` return the components changed after it,
+    /// it can also be passed to `inserted_since` and `modified_since`.
+    pub fn last_run(&self) -> u64 {
+        last_run()
+    }
+}
+
+impl<T> ViewMut<'_, T> {
+    /// Returns the components inserted since the system borrowing this view last ran.  
+    /// If the storage doesn't track changes, returns the *inserted* section of the update pack instead.
+    pub fn try_inserted(&self) -> Result<Changes<'_, T>, error::Inserted> {
+        Changes::try_inserted(self.sparse_set.window(), last_run())
+    }
+    /// Returns the components inserted since the system borrowing this view last ran.  
+    /// If the storage doesn't track changes, returns the *inserted* section of the update pack instead.  
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn inserted(&self) -> Changes<'_, T> {
+        self.try_inserted().unwrap()
+    }
+    /// Returns the components modified since the system borrowing this view last ran, except the ones inserted since then.  
+    /// If the storage doesn't track changes, returns the *modified* section of the update pack instead.
+    pub fn try_modified(&self) -> Result<Changes<'_, T>, error::Modified> {
+        Changes::try_modified(self.sparse_set.window(), last_run())
+    }
+    /// Returns the components modified since the system borrowing this view last ran, except the ones inserted since then.  
+    /// If the storage doesn't track changes, returns the *modified* section of the update pack instead.  
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn modified(&self) -> Changes<'_, T> {
+        self.try_modified().unwrap()
+    }
+    /// Returns the components inserted or modified since the system borrowing this view last ran.  
+    /// If the storage doesn't track changes, returns the *inserted* and *modified* sections of the update pack instead.
+    pub fn try_inserted_or_modified(&self) -> Result<Changes<'_, T>, error::InsertedOrModified> {
+        Changes::try_inserted_or_modified(self.sparse_set.window(), last_run())
+    }
+    /// Returns the components inserted or modified since the system borrowing this view last ran.  
+    /// If the storage doesn't track changes, returns the *inserted* and *modified* sections of the update pack instead.  
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn inserted_or_modified(&self) -> Changes<'_, T> {
+        self.try_inserted_or_modified().unwrap()
+    }
+}
+
 impl<T> Deref for ViewMut<'_, T> {
     type Target = SparseSet<T>;
     fn deref(&self) -> &Self::Target {
@@ -394,6 +510,43 @@ impl<'a, T> AsMut<SparseSet<T>> for ViewMut<'a, T> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for ViewMut<'_, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.sparse_set.serialize(serializer)
+    }
+}
+
+/// Replaces all components in the storage with the deserialized ones.
+///
+/// The storage stays in the same pack. When it's tight or loose packed, all storages in the pack should be loaded from the same save.
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::de::DeserializeSeed<'de> for &mut ViewMut<'_, T> {
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        (&mut *self.sparse_set).deserialize(deserializer)
+    }
+}
+
+/// Returns the tick of the previous run of the system running on this thread, 0 outside of workloads.
+fn last_run() -> u64 {
+    #[cfg(feature = "std")]
+    {
+        crate::borrow::system_ticks().map_or(0, |(last_run, _)| last_run)
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        0
+    }
+}
+
 /// Shared view over a unique component storage.
 pub struct UniqueView<'a, T> {
     unique: Ref<'a, T>,
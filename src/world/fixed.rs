@@ -0,0 +1,206 @@
+use super::World;
+use crate::error;
+use core::time::Duration;
+
+/// Unique storage updated by `World::run_workload_fixed` before running the workload.
+///
+/// It holds the values of the last fixed workload run.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct FixedTime {
+    step: Duration,
+    steps: u32,
+    alpha: f32,
+}
+
+impl FixedTime {
+    /// Returns the time simulated by each run of the workload.
+    pub fn step(&self) -> Duration {
+        self.step
+    }
+    /// Returns how many times the workload runs for this call.
+    pub fn steps(&self) -> u32 {
+        self.steps
+    }
+    /// Returns how far the time left in the accumulator is into the next step, between `0.0` and `1.0`.  
+    /// It can be used to interpolate between the last two simulated states.
+    pub fn alpha(&self) -> f32 {
+        self.alpha
+    }
+}
+
+impl World {
+    /// Adds `elapsed` to the accumulator of the `name` workload and runs it once for each `step` it contains, at most `max_steps` times.  
+    /// The workload can run zero or more times, `FixedTime` is updated before it runs.  
+    /// Each workload has its own accumulator, workloads can run at different rates.  
+    /// Steps above `max_steps` are dropped from the accumulator, a workload slower than `step` doesn't keep falling behind.  
+    /// A zero `step` runs the workload once per call.
+    ///
+    /// ### Example
+    /// ```
+    /// use core::time::Duration;
+    /// use shipyard::{system, FixedTime, UniqueView, UniqueViewMut, World};
+    ///
+    /// fn physics(mut ticks: UniqueViewMut<u32>) {
+    ///     *ticks += 1;
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.add_unique(0u32);
+    ///
+    /// world
+    ///     .add_workload("Physics")
+    ///     .with_system(system!(physics))
+    ///     .build();
+    ///
+    /// let step = Duration::from_millis(10);
+    /// world.run_workload_fixed("Physics", step, Duration::from_millis(25), 5);
+    ///
+    /// world.run(|ticks: UniqueView<u32>, fixed_time: UniqueView<FixedTime>| {
+    ///     assert_eq!(*ticks, 2);
+    ///     assert!((fixed_time.alpha() - 0.5).abs() < 0.01);
+    /// });
+    /// ```
+    pub fn try_run_workload_fixed(
+        &self,
+        name: impl AsRef<str> + Sync,
+        step: Duration,
+        elapsed: Duration,
+        max_steps: u32,
+    ) -> Result<(), error::RunWorkload> {
+        let name = name.as_ref();
+
+        let fixed_time = {
+            let mut scheduler = self
+                .scheduler
+                .try_borrow_mut()
+                .map_err(|_| error::RunWorkload::Scheduler)?;
+            let name = scheduler
+                .workloads
+                .get_key_value(name)
+                .ok_or(error::RunWorkload::MissingWorkload)?
+                .0
+                .clone();
+
+            if step == Duration::from_secs(0) {
+                FixedTime {
+                    step,
+                    steps: 1,
+                    alpha: 0.0,
+                }
+            } else {
+                let accumulator = scheduler.accumulators.entry(name).or_default();
+                *accumulator += elapsed;
+
+                let accumulated = accumulator.as_nanos();
+                let steps = (accumulated / step.as_nanos()).min(max_steps as u128) as u32;
+                // only the time since the last whole step is kept, skipped steps are dropped
+                let left = accumulated % step.as_nanos();
+                *accumulator =
+                    Duration::new((left / 1_000_000_000) as u64, (left % 1_000_000_000) as u32);
+
+                FixedTime {
+                    step,
+                    steps,
+                    alpha: accumulator.as_secs_f32() / step.as_secs_f32(),
+                }
+            }
+        };
+
+        {
+            let all_storages = self.all_storages.try_borrow().map_err(|err| {
+                error::RunWorkload::Storage(error::GetStorage::AllStoragesBorrow(err))
+            })?;
+            all_storages.register_unique(FixedTime::default());
+            *all_storages
+                .unique_mut::<FixedTime>()
+                .map_err(error::RunWorkload::Storage)? = fixed_time;
+        }
+
+        for _ in 0..fixed_time.steps {
+            self.try_run_workload(name)?;
+        }
+
+        Ok(())
+    }
+    /// Adds `elapsed` to the accumulator of the `name` workload and runs it once for each `step` it contains, at most `max_steps` times.  
+    /// The workload can run zero or more times, `FixedTime` is updated before it runs.  
+    /// Each workload has its own accumulator, workloads can run at different rates.  
+    /// Steps above `max_steps` are dropped from the accumulator, a workload slower than `step` doesn't keep falling behind.  
+    /// A zero `step` runs the workload once per call.  
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn run_workload_fixed(
+        &self,
+        name: impl AsRef<str> + Sync,
+        step: Duration,
+        elapsed: Duration,
+        max_steps: u32,
+    ) {
+        self.try_run_workload_fixed(name, step, elapsed, max_steps)
+            .unwrap()
+    }
+    /// Runs the `name` workload the first time it's called then once every `every` calls.  
+    /// It can be used to run a workload at a fraction of the rate of another one.  
+    /// An `every` of zero or one runs the workload on every call.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{system, UniqueView, UniqueViewMut, World};
+    ///
+    /// fn ai(mut ticks: UniqueViewMut<u32>) {
+    ///     *ticks += 1;
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.add_unique(0u32);
+    ///
+    /// world.add_workload("AI").with_system(system!(ai)).build();
+    ///
+    /// for _ in 0..8 {
+    ///     world.run_workload_every("AI", 4);
+    /// }
+    ///
+    /// world.run(|ticks: UniqueView<u32>| assert_eq!(*ticks, 2));
+    /// ```
+    pub fn try_run_workload_every(
+        &self,
+        name: impl AsRef<str> + Sync,
+        every: u32,
+    ) -> Result<(), error::RunWorkload> {
+        let name = name.as_ref();
+
+        let run = {
+            let mut scheduler = self
+                .scheduler
+                .try_borrow_mut()
+                .map_err(|_| error::RunWorkload::Scheduler)?;
+            let name = scheduler
+                .workloads
+                .get_key_value(name)
+                .ok_or(error::RunWorkload::MissingWorkload)?
+                .0
+                .clone();
+
+            let calls = scheduler.calls.entry(name).or_default();
+            let run = *calls == 0;
+            *calls = (*calls + 1) % every.max(1);
+            run
+        };
+
+        if run {
+            self.try_run_workload(name)
+        } else {
+            Ok(())
+        }
+    }
+    /// Runs the `name` workload the first time it's called then once every `every` calls.  
+    /// It can be used to run a workload at a fraction of the rate of another one.  
+    /// An `every` of zero or one runs the workload on every call.  
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn run_workload_every(&self, name: impl AsRef<str> + Sync, every: u32) {
+        self.try_run_workload_every(name, every).unwrap()
+    }
+}
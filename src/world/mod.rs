@@ -1,16 +1,31 @@
+mod fixed;
 mod scheduler;
 
-pub use scheduler::WorkloadBuilder;
+pub use fixed::FixedTime;
+pub use scheduler::{Conflict, ErrorPolicy, SystemInfo, WorkloadBuilder, WorkloadInfo};
+#[cfg(feature = "std")]
+pub use scheduler::{Profiler, SystemTiming, TimingProfiler};
 
 use crate::atomic_refcell::AtomicRefCell;
-use crate::borrow::Borrow;
+use crate::borrow::{Borrow, Mutation};
 use crate::error;
-use crate::storage::AllStorages;
+use crate::event::{update_events, Events};
+#[cfg(feature = "serde")]
+use crate::storage::MapEntities;
+use crate::storage::{
+    AllStorages, Checkpoint, Delta, EntityId, EntityMap, Snapshot, UniqueConflict,
+};
 use alloc::borrow::Cow;
+#[cfg(feature = "std")]
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::any::TypeId;
 use core::ops::Range;
 #[cfg(feature = "parallel")]
 use rayon::{ThreadPool, ThreadPoolBuilder};
 use scheduler::Scheduler;
+#[cfg(feature = "std")]
+use std::panic::AssertUnwindSafe;
 
 /// Holds all components and keeps track of entities and what they own.
 pub struct World {
@@ -85,6 +100,40 @@ impl World {
     pub fn add_unique<T: 'static + Send + Sync>(&self, component: T) {
         self.try_add_unique(component).unwrap();
     }
+    /// Adds an [Events]\<T\> unique storage, updated after each workload run.  
+    /// Events are dropped after the workload run following the one they were sent in.  
+    /// Does nothing if `T` events were already added.
+    ///
+    /// [Events]: struct.Events.html
+    pub fn try_add_event<T: 'static + Send + Sync>(&self) -> Result<(), error::Borrow> {
+        let mut scheduler = self.scheduler.try_borrow_mut()?;
+        self.all_storages
+            .try_borrow()?
+            .register_unique(Events::<T>::new());
+
+        if scheduler
+            .events
+            .iter()
+            .all(|(type_id, _)| *type_id != TypeId::of::<T>())
+        {
+            scheduler
+                .events
+                .push((TypeId::of::<T>(), update_events::<T>));
+        }
+
+        Ok(())
+    }
+    /// Adds an [Events]\<T\> unique storage, updated after each workload run.  
+    /// Events are dropped after the workload run following the one they were sent in.  
+    /// Does nothing if `T` events were already added.  
+    /// Unwraps errors.
+    ///
+    /// [Events]: struct.Events.html
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn add_event<T: 'static + Send + Sync>(&self) {
+        self.try_add_event::<T>().unwrap();
+    }
     /// Adds a new unique storage, unique storages store exactly one `T` at any time.  
     /// To access a unique storage value, use [UniqueView] or [UniqueViewMut].  
     /// Does nothing if the storage already exists.
@@ -208,6 +257,491 @@ impl World {
     pub fn remove_unique<T: 'static>(&self) -> T {
         self.try_remove_unique().unwrap()
     }
+    /// Registers `T`'s storage under `key`, it'll be part of serialized `World`s.  
+    /// The key is what ties serialized data to a storage, it has to stay the same between saving and loading.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn try_register_serde<
+        T: 'static + Send + Sync + serde::Serialize + serde::de::DeserializeOwned,
+    >(
+        &self,
+        key: &'static str,
+    ) -> Result<(), error::Register> {
+        self.all_storages
+            .try_borrow_mut()
+            .map_err(error::Register::AllStoragesBorrow)?
+            .try_register_serde::<T>(key)
+    }
+    /// Registers `T`'s storage under `key`, it'll be part of serialized `World`s.  
+    /// The key is what ties serialized data to a storage, it has to stay the same between saving and loading.  
+    /// Unwraps errors.
+    #[cfg(all(feature = "serde", feature = "panic"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "serde", feature = "panic"))))]
+    pub fn register_serde<
+        T: 'static + Send + Sync + serde::Serialize + serde::de::DeserializeOwned,
+    >(
+        &self,
+        key: &'static str,
+    ) {
+        self.try_register_serde::<T>(key).unwrap()
+    }
+    /// Registers `T`'s storage under `key`, it'll be part of serialized `World`s.  
+    /// When entities are given new ids, `T`'s components will be updated using `MapEntities`.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn try_register_serde_mapped<
+        T: 'static + Send + Sync + serde::Serialize + serde::de::DeserializeOwned + MapEntities,
+    >(
+        &self,
+        key: &'static str,
+    ) -> Result<(), error::Register> {
+        self.all_storages
+            .try_borrow_mut()
+            .map_err(error::Register::AllStoragesBorrow)?
+            .try_register_serde_mapped::<T>(key)
+    }
+    /// Registers `T`'s storage under `key`, it'll be part of serialized `World`s.  
+    /// When entities are given new ids, `T`'s components will be updated using `MapEntities`.  
+    /// Unwraps errors.
+    #[cfg(all(feature = "serde", feature = "panic"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "serde", feature = "panic"))))]
+    pub fn register_serde_mapped<
+        T: 'static + Send + Sync + serde::Serialize + serde::de::DeserializeOwned + MapEntities,
+    >(
+        &self,
+        key: &'static str,
+    ) {
+        self.try_register_serde_mapped::<T>(key).unwrap()
+    }
+    /// Registers `T`'s unique storage under `key`, it'll be part of serialized `World`s.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn try_register_unique_serde<
+        T: 'static + Send + Sync + serde::Serialize + serde::de::DeserializeOwned,
+    >(
+        &self,
+        key: &'static str,
+    ) -> Result<(), error::Register> {
+        self.all_storages
+            .try_borrow_mut()
+            .map_err(error::Register::AllStoragesBorrow)?
+            .try_register_unique_serde::<T>(key)
+    }
+    /// Registers `T`'s unique storage under `key`, it'll be part of serialized `World`s.
+    /// Unwraps errors.
+    #[cfg(all(feature = "serde", feature = "panic"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "serde", feature = "panic"))))]
+    pub fn register_unique_serde<
+        T: 'static + Send + Sync + serde::Serialize + serde::de::DeserializeOwned,
+    >(
+        &self,
+        key: &'static str,
+    ) {
+        self.try_register_unique_serde::<T>(key).unwrap()
+    }
+    /// Serializes `Entities` and all storages registered with `register_serde`, `register_serde_mapped` or `register_unique_serde`.
+    /// Registered storages that don't exist yet are skipped.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.all_storages
+            .try_borrow()
+            .map_err(serde::ser::Error::custom)?
+            .serialize(serializer)
+    }
+    /// Replaces `Entities` and all storages' content with the serialized ones.
+    /// Storages missing from the serialized data are emptied.  
+    /// Packs aren't serialized, they have to be set up before loading.  
+    /// Tight and loose packs are rebuilt from the loaded components.  
+    /// Loaded components are considered inserted at the tick of the load.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn deserialize<'de, D: serde::Deserializer<'de>>(
+        &self,
+        deserializer: D,
+    ) -> Result<(), D::Error> {
+        self.all_storages
+            .try_borrow_mut()
+            .map_err(serde::de::Error::custom)?
+            .deserialize(deserializer)
+    }
+    /// Adds the serialized entities and their components to the `World`, giving each entity a new id.  
+    /// Returns the id each serialized entity was given.
+    ///
+    /// Components registered with `register_serde_mapped` get their `EntityId`s updated.  
+    /// Unique storages are not loaded.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn deserialize_additive<'de, D: serde::Deserializer<'de>>(
+        &self,
+        deserializer: D,
+    ) -> Result<EntityMap, D::Error> {
+        self.all_storages
+            .try_borrow_mut()
+            .map_err(serde::de::Error::custom)?
+            .deserialize_additive(deserializer)
+    }
+    /// Registers `T`'s storage, it'll be part of snapshots.  
+    /// Only storages of `Copy` types can be registered, their memory is copied as is.
+    pub fn try_register_snapshot<T: 'static + Send + Sync + Copy>(
+        &self,
+    ) -> Result<(), error::Register> {
+        self.all_storages
+            .try_borrow_mut()
+            .map_err(error::Register::AllStoragesBorrow)?
+            .try_register_snapshot::<T>()
+    }
+    /// Registers `T`'s storage, it'll be part of snapshots.  
+    /// Only storages of `Copy` types can be registered, their memory is copied as is.  
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn register_snapshot<T: 'static + Send + Sync + Copy>(&self) {
+        self.try_register_snapshot::<T>().unwrap()
+    }
+    /// Registers `T`'s unique storage, it'll be part of snapshots.
+    pub fn try_register_unique_snapshot<T: 'static + Send + Sync + Copy>(
+        &self,
+    ) -> Result<(), error::Register> {
+        self.all_storages
+            .try_borrow_mut()
+            .map_err(error::Register::AllStoragesBorrow)?
+            .try_register_unique_snapshot::<T>()
+    }
+    /// Registers `T`'s unique storage, it'll be part of snapshots.
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn register_unique_snapshot<T: 'static + Send + Sync + Copy>(&self) {
+        self.try_register_unique_snapshot::<T>().unwrap()
+    }
+    /// Copies `Entities` and all storages registered with `register_snapshot` or `register_unique_snapshot`.
+    /// Registered storages that don't exist yet are skipped.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{EntitiesViewMut, View, ViewMut, World};
+    ///
+    /// let world = World::new();
+    /// world.register_snapshot::<u32>();
+    ///
+    /// let entity = world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>| {
+    ///     entities.add_entity(&mut u32s, 0)
+    /// });
+    ///
+    /// let snapshot = world.snapshot();
+    ///
+    /// world.run(|mut u32s: ViewMut<u32>| u32s[entity] = 1);
+    ///
+    /// world.restore_snapshot(&snapshot);
+    ///
+    /// world.run(|u32s: View<u32>| assert_eq!(u32s[entity], 0));
+    /// ```
+    pub fn try_snapshot(&self) -> Result<Snapshot, error::GetStorage> {
+        self.all_storages
+            .try_borrow()
+            .map_err(error::GetStorage::AllStoragesBorrow)?
+            .try_snapshot()
+    }
+    /// Copies `Entities` and all storages registered with `register_snapshot` or `register_unique_snapshot`.
+    /// Registered storages that don't exist yet are skipped.
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn snapshot(&self) -> Snapshot {
+        self.try_snapshot().unwrap()
+    }
+    /// Brings back `Entities` and all storages to the state saved in `snapshot`.
+    /// Storages that aren't part of the snapshot are emptied.
+    /// Packs aren't part of the snapshot, they have to be set up before restoring.
+    /// Tight and loose packs are rebuilt from the restored components.
+    /// Restored components are considered inserted at the tick of the restore.
+    pub fn try_restore_snapshot(&self, snapshot: &Snapshot) -> Result<(), error::GetStorage> {
+        self.all_storages
+            .try_borrow_mut()
+            .map_err(error::GetStorage::AllStoragesBorrow)?
+            .try_restore_snapshot(snapshot)
+    }
+    /// Brings back `Entities` and all storages to the state saved in `snapshot`.
+    /// Storages that aren't part of the snapshot are emptied.
+    /// Packs aren't part of the snapshot, they have to be set up before restoring.
+    /// Tight and loose packs are rebuilt from the restored components.
+    /// Restored components are considered inserted at the tick of the restore.
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn restore_snapshot(&self, snapshot: &Snapshot) {
+        self.try_restore_snapshot(snapshot).unwrap()
+    }
+    /// Registers `T`'s storage as cloneable, it'll be part of checkpoints.
+    pub fn try_register_clone<T: 'static + Send + Sync + Clone>(
+        &self,
+    ) -> Result<(), error::Register> {
+        self.all_storages
+            .try_borrow_mut()
+            .map_err(error::Register::AllStoragesBorrow)?
+            .try_register_clone::<T>()
+    }
+    /// Registers `T`'s storage as cloneable, it'll be part of checkpoints.
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn register_clone<T: 'static + Send + Sync + Clone>(&self) {
+        self.try_register_clone::<T>().unwrap()
+    }
+    /// Registers `T`'s unique storage as cloneable, it'll be part of checkpoints.
+    pub fn try_register_unique_clone<T: 'static + Send + Sync + Clone>(
+        &self,
+    ) -> Result<(), error::Register> {
+        self.all_storages
+            .try_borrow_mut()
+            .map_err(error::Register::AllStoragesBorrow)?
+            .try_register_unique_clone::<T>()
+    }
+    /// Registers `T`'s unique storage as cloneable, it'll be part of checkpoints.
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn register_unique_clone<T: 'static + Send + Sync + Clone>(&self) {
+        self.try_register_unique_clone::<T>().unwrap()
+    }
+    /// Copies `Entities` and all storages registered as cloneable.
+    /// Registered storages that don't exist yet are skipped.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{EntitiesViewMut, ViewMut, World};
+    ///
+    /// let world = World::new();
+    /// world.register_clone::<String>();
+    ///
+    /// let entity = world.run(|mut entities: EntitiesViewMut, mut strings: ViewMut<String>| {
+    ///     entities.add_entity(&mut strings, String::from("before"))
+    /// });
+    ///
+    /// let checkpoint = world.checkpoint();
+    ///
+    /// world.run(|mut strings: ViewMut<String>| strings[entity].push_str(" and after"));
+    ///
+    /// world.restore(&checkpoint);
+    ///
+    /// world.run(|strings: ViewMut<String>| assert_eq!(strings[entity], "before"));
+    /// ```
+    pub fn try_checkpoint(&self) -> Result<Checkpoint, error::GetStorage> {
+        self.all_storages
+            .try_borrow()
+            .map_err(error::GetStorage::AllStoragesBorrow)?
+            .try_checkpoint()
+    }
+    /// Copies `Entities` and all storages registered as cloneable.
+    /// Registered storages that don't exist yet are skipped.
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn checkpoint(&self) -> Checkpoint {
+        self.try_checkpoint().unwrap()
+    }
+    /// Brings back `Entities` and all storages to the state saved in `checkpoint`.
+    /// Storages that aren't part of the checkpoint are emptied, unique storages are left as is.
+    /// Packs aren't part of the checkpoint, they have to be set up before restoring.
+    /// Tight and loose packs are rebuilt from the restored components.
+    /// Restored components are considered inserted at the tick of the restore.
+    pub fn try_restore(&self, checkpoint: &Checkpoint) -> Result<(), error::GetStorage> {
+        self.all_storages
+            .try_borrow_mut()
+            .map_err(error::GetStorage::AllStoragesBorrow)?
+            .try_restore(checkpoint)
+    }
+    /// Brings back `Entities` and all storages to the state saved in `checkpoint`.
+    /// Storages that aren't part of the checkpoint are emptied, unique storages are left as is.
+    /// Packs aren't part of the checkpoint, they have to be set up before restoring.
+    /// Tight and loose packs are rebuilt from the restored components.
+    /// Restored components are considered inserted at the tick of the restore.
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn restore(&self, checkpoint: &Checkpoint) {
+        self.try_restore(checkpoint).unwrap()
+    }
+    /// Registers `T`'s storage as cloneable, it'll be part of checkpoints and deltas.
+    pub fn try_register_delta<T: 'static + Send + Sync + Clone + PartialEq>(
+        &self,
+    ) -> Result<(), error::Register> {
+        self.all_storages
+            .try_borrow_mut()
+            .map_err(error::Register::AllStoragesBorrow)?
+            .try_register_delta::<T>()
+    }
+    /// Registers `T`'s storage as cloneable, it'll be part of checkpoints and deltas.
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn register_delta<T: 'static + Send + Sync + Clone + PartialEq>(&self) {
+        self.try_register_delta::<T>().unwrap()
+    }
+    /// Lists the entities and components that changed since `previous` was made.
+    /// Components are compared with `PartialEq`.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{EntitiesViewMut, View, ViewMut, World};
+    ///
+    /// let world = World::new();
+    /// world.register_delta::<u32>();
+    /// let replica = World::new();
+    /// replica.register_delta::<u32>();
+    ///
+    /// let checkpoint = world.checkpoint();
+    ///
+    /// let entity = world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>| {
+    ///     entities.add_entity(&mut u32s, 0)
+    /// });
+    ///
+    /// let delta = world.delta(&checkpoint);
+    /// assert_eq!(delta.created(), &[entity]);
+    /// assert_eq!(delta.components::<u32>().unwrap().added(), &[(entity, 0)]);
+    ///
+    /// replica.apply_delta(&delta);
+    ///
+    /// replica.run(|u32s: View<u32>| assert_eq!(u32s[entity], 0));
+    /// ```
+    pub fn try_delta(&self, previous: &Checkpoint) -> Result<Delta, error::GetStorage> {
+        self.all_storages
+            .try_borrow()
+            .map_err(error::GetStorage::AllStoragesBorrow)?
+            .try_delta(previous)
+    }
+    /// Lists the entities and components that changed since `previous` was made.
+    /// Components are compared with `PartialEq`.
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn delta(&self, previous: &Checkpoint) -> Delta {
+        self.try_delta(previous).unwrap()
+    }
+    /// Applies the changes listed in `delta`.
+    /// `Entities` and the storages are expected to be in the state of the checkpoint `delta` was made from.
+    /// Added and changed components are considered inserted or modified at the tick `delta` is applied.
+    pub fn try_apply_delta(&self, delta: &Delta) -> Result<(), error::GetStorage> {
+        self.all_storages
+            .try_borrow_mut()
+            .map_err(error::GetStorage::AllStoragesBorrow)?
+            .try_apply_delta(delta)
+    }
+    /// Applies the changes listed in `delta`.
+    /// `Entities` and the storages are expected to be in the state of the checkpoint `delta` was made from.
+    /// Added and changed components are considered inserted or modified at the tick `delta` is applied.
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn apply_delta(&self, delta: &Delta) {
+        self.try_apply_delta(delta).unwrap()
+    }
+    /// Moves `entities` and all their components to `other`.
+    /// They're deleted from this `World` and given new ids in `other`, the returned `EntityMap` links both.
+    /// Dead entities are ignored and shared components are not moved.
+    ///
+    /// `EntityId`s held by components are not modified, `MapEntities` can be used with the returned `EntityMap` to update them.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{EntitiesViewMut, View, ViewMut, World};
+    ///
+    /// let loading = World::new();
+    /// let world = World::new();
+    ///
+    /// let entity = loading.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>| {
+    ///     entities.add_entity(&mut u32s, 0)
+    /// });
+    ///
+    /// let entity_map = loading.move_entities(&world, &[entity]);
+    ///
+    /// world.run(|u32s: View<u32>| {
+    ///     assert_eq!(u32s[entity_map.get(entity).unwrap()], 0);
+    /// });
+    /// ```
+    pub fn try_move_entities(
+        &self,
+        other: &World,
+        entities: &[EntityId],
+    ) -> Result<EntityMap, error::GetStorage> {
+        let mut all_storages = self
+            .all_storages
+            .try_borrow_mut()
+            .map_err(error::GetStorage::AllStoragesBorrow)?;
+        let mut other = other
+            .all_storages
+            .try_borrow_mut()
+            .map_err(error::GetStorage::AllStoragesBorrow)?;
+
+        all_storages.try_move_entities(&mut other, entities)
+    }
+    /// Moves `entities` and all their components to `other`.
+    /// They're deleted from this `World` and given new ids in `other`, the returned `EntityMap` links both.
+    /// Dead entities are ignored and shared components are not moved.
+    ///
+    /// `EntityId`s held by components are not modified, `MapEntities` can be used with the returned `EntityMap` to update them.
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn move_entities(&self, other: &World, entities: &[EntityId]) -> EntityMap {
+        self.try_move_entities(other, entities).unwrap()
+    }
+    /// Moves all entities, components and unique storages of `other` to this `World`.
+    /// Entities are given new ids, the returned `EntityMap` links both.
+    /// `conflict` decides what happens to unique storages present in both `World`s, workloads are not modified.
+    ///
+    /// Shared components are not moved and `EntityId`s held by components are not modified,
+    /// `MapEntities` can be used with the returned `EntityMap` to update them.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{EntitiesViewMut, UniqueConflict, UniqueView, View, ViewMut, World};
+    ///
+    /// let level = World::new();
+    /// level.add_unique(1u32);
+    /// let entity = level.run(|mut entities: EntitiesViewMut, mut usizes: ViewMut<usize>| {
+    ///     entities.add_entity(&mut usizes, 0)
+    /// });
+    ///
+    /// let world = World::new();
+    /// world.add_unique(0u32);
+    ///
+    /// let entity_map = world.merge(&level, UniqueConflict::Replace);
+    ///
+    /// world.run(|usizes: View<usize>, level: UniqueView<u32>| {
+    ///     assert_eq!(usizes[entity_map.get(entity).unwrap()], 0);
+    ///     assert_eq!(*level, 1);
+    /// });
+    /// ```
+    pub fn try_merge(
+        &self,
+        other: &World,
+        conflict: UniqueConflict,
+    ) -> Result<EntityMap, error::Merge> {
+        let mut all_storages = self
+            .all_storages
+            .try_borrow_mut()
+            .map_err(error::Merge::AllStoragesBorrow)?;
+        let mut other = other
+            .all_storages
+            .try_borrow_mut()
+            .map_err(error::Merge::AllStoragesBorrow)?;
+
+        all_storages.try_merge(&mut other, conflict)
+    }
+    /// Moves all entities, components and unique storages of `other` to this `World`.
+    /// Entities are given new ids, the returned `EntityMap` links both.
+    /// `conflict` decides what happens to unique storages present in both `World`s, workloads are not modified.
+    ///
+    /// Shared components are not moved and `EntityId`s held by components are not modified,
+    /// `MapEntities` can be used with the returned `EntityMap` to update them.
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn merge(&self, other: &World, conflict: UniqueConflict) -> EntityMap {
+        self.try_merge(other, conflict).unwrap()
+    }
     #[doc = "Borrows the requested storage(s), if it doesn't exist it'll get created.  
 You can use a tuple to get multiple storages at once.
 
@@ -987,8 +1521,9 @@ let i = world.run(sys1);
         name: impl Into<Cow<'static, str>>,
     ) -> Result<(), error::SetDefaultWorkload> {
         if let Ok(mut scheduler) = self.scheduler.try_borrow_mut() {
-            if let Some(workload) = scheduler.workloads.get(&name.into()) {
-                scheduler.default = workload.clone();
+            let name = name.into();
+            if scheduler.workloads.contains_key(&name) {
+                scheduler.default = Some(name);
                 Ok(())
             } else {
                 Err(error::SetDefaultWorkload::MissingWorkload)
@@ -1105,6 +1640,286 @@ let i = world.run(sys1);
     pub fn add_workload(&self, name: impl Into<Cow<'static, str>>) -> WorkloadBuilder<'_> {
         self.try_add_workload(name).unwrap()
     }
+    /// Removes the `name` workload.  
+    /// Systems not used by any other workload are removed from the `World`.  
+    /// If it was the default workload, the default workload will be empty until `set_default_workload` is called.
+    pub fn try_remove_workload(&self, name: impl AsRef<str>) -> Result<(), error::RemoveWorkload> {
+        let mut scheduler = self
+            .scheduler
+            .try_borrow_mut()
+            .map_err(|_| error::RemoveWorkload::Borrow)?;
+
+        if scheduler.remove_workload(name.as_ref()) {
+            Ok(())
+        } else {
+            Err(error::RemoveWorkload::MissingWorkload)
+        }
+    }
+    /// Removes the `name` workload.  
+    /// Systems not used by any other workload are removed from the `World`.  
+    /// If it was the default workload, the default workload will be empty until `set_default_workload` is called.  
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn remove_workload(&self, name: impl AsRef<str>) {
+        self.try_remove_workload(name).unwrap()
+    }
+    /// Starts creating a new workload that replaces the `name` workload if present.  
+    /// The old workload is removed once the new one is built, it's kept if building fails.  
+    /// If the old workload was the default one, the new one will be too.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{system, UniqueViewMut, World};
+    ///
+    /// fn increment(mut count: UniqueViewMut<u32>) {
+    ///     *count += 1;
+    /// }
+    ///
+    /// fn reset(mut count: UniqueViewMut<u32>) {
+    ///     *count = 0;
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.add_unique(0u32);
+    ///
+    /// world
+    ///     .add_workload("Update")
+    ///     .with_system(system!(increment))
+    ///     .build();
+    ///
+    /// world
+    ///     .replace_workload("Update")
+    ///     .with_system(system!(reset))
+    ///     .build();
+    ///
+    /// world.run_default();
+    /// ```
+    pub fn try_replace_workload(
+        &self,
+        name: impl Into<Cow<'static, str>>,
+    ) -> Result<WorkloadBuilder<'_>, error::AddWorkload> {
+        let scheduler = self
+            .scheduler
+            .try_borrow_mut()
+            .map_err(|_| error::AddWorkload::Borrow)?;
+        Ok(WorkloadBuilder::with_replace(scheduler, name.into(), true))
+    }
+    /// Starts creating a new workload that replaces the `name` workload if present.  
+    /// The old workload is removed once the new one is built, it's kept if building fails.  
+    /// If the old workload was the default one, the new one will be too.  
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn replace_workload(&self, name: impl Into<Cow<'static, str>>) -> WorkloadBuilder<'_> {
+        self.try_replace_workload(name).unwrap()
+    }
+    /// Enables all systems named `name`, in all workloads.  
+    /// `name` can be the full name of the system or the name of its function.  
+    /// Systems are enabled by default.
+    pub fn try_enable_system(&self, name: impl AsRef<str>) -> Result<(), error::EnableSystem> {
+        self.set_system_enabled(name.as_ref(), true)
+    }
+    /// Enables all systems named `name`, in all workloads.  
+    /// `name` can be the full name of the system or the name of its function.  
+    /// Systems are enabled by default.  
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn enable_system(&self, name: impl AsRef<str>) {
+        self.try_enable_system(name).unwrap()
+    }
+    /// Disables all systems named `name`, in all workloads. Disabled systems are skipped when their workload runs.  
+    /// `name` can be the full name of the system or the name of its function.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{system, UniqueView, UniqueViewMut, World};
+    ///
+    /// fn increment(mut count: UniqueViewMut<u32>) {
+    ///     *count += 1;
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.add_unique(0u32);
+    ///
+    /// world
+    ///     .add_workload("Update")
+    ///     .with_system(system!(increment))
+    ///     .build();
+    ///
+    /// world.disable_system("increment");
+    /// world.run_default();
+    /// world.run(|count: UniqueView<u32>| assert_eq!(*count, 0));
+    ///
+    /// world.enable_system("increment");
+    /// world.run_default();
+    /// world.run(|count: UniqueView<u32>| assert_eq!(*count, 1));
+    /// ```
+    pub fn try_disable_system(&self, name: impl AsRef<str>) -> Result<(), error::EnableSystem> {
+        self.set_system_enabled(name.as_ref(), false)
+    }
+    /// Disables all systems named `name`, in all workloads. Disabled systems are skipped when their workload runs.  
+    /// `name` can be the full name of the system or the name of its function.  
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn disable_system(&self, name: impl AsRef<str>) {
+        self.try_disable_system(name).unwrap()
+    }
+    /// Returns the batches the scheduler built for the `name` workload.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{system, View, ViewMut, World};
+    ///
+    /// fn read(_: View<u32>) {}
+    /// fn write(_: ViewMut<u32>) {}
+    ///
+    /// let world = World::new();
+    ///
+    /// world
+    ///     .add_workload("Update")
+    ///     .with_system(system!(read))
+    ///     .with_system(system!(write))
+    ///     .build();
+    ///
+    /// let info = world.workload_info("Update");
+    /// assert_eq!(info.batches().len(), 2);
+    /// let conflict = info.batches()[1][0].conflict().unwrap();
+    /// assert!(conflict.system().ends_with("read"));
+    ///
+    /// println!("{}", info.to_dot());
+    /// ```
+    pub fn try_workload_info(
+        &self,
+        name: impl AsRef<str>,
+    ) -> Result<WorkloadInfo, error::WorkloadInfo> {
+        let scheduler = self
+            .scheduler
+            .try_borrow()
+            .map_err(|_| error::WorkloadInfo::Borrow)?;
+
+        let (name, workload) = scheduler
+            .workloads
+            .get_key_value(name.as_ref())
+            .ok_or(error::WorkloadInfo::MissingWorkload)?;
+
+        Ok(scheduler.workload_info(name.clone(), workload.clone()))
+    }
+    /// Returns the batches the scheduler built for the `name` workload.  
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn workload_info(&self, name: impl AsRef<str>) -> WorkloadInfo {
+        self.try_workload_info(name).unwrap()
+    }
+    /// Returns the batches the scheduler built for all workloads, in the order they were added.
+    pub fn try_workloads_info(&self) -> Result<Vec<WorkloadInfo>, error::WorkloadInfo> {
+        let scheduler = self
+            .scheduler
+            .try_borrow()
+            .map_err(|_| error::WorkloadInfo::Borrow)?;
+
+        let mut workloads: Vec<_> = scheduler.workloads.iter().collect();
+        workloads.sort_unstable_by_key(|(_, workload)| (workload.start, workload.end));
+
+        Ok(workloads
+            .into_iter()
+            .map(|(name, workload)| scheduler.workload_info(name.clone(), workload.clone()))
+            .collect())
+    }
+    /// Returns the batches the scheduler built for all workloads, in the order they were added.  
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn workloads_info(&self) -> Vec<WorkloadInfo> {
+        self.try_workloads_info().unwrap()
+    }
+    /// Sets the `Profiler` called when workloads run, replacing the previous one.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn try_set_profiler<P: Profiler + 'static>(
+        &self,
+        profiler: Arc<P>,
+    ) -> Result<(), error::Borrow> {
+        self.scheduler.try_borrow_mut()?.profiler = Some(profiler);
+        Ok(())
+    }
+    /// Sets the `Profiler` called when workloads run, replacing the previous one.  
+    /// Unwraps errors.
+    #[cfg(all(feature = "std", feature = "panic"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "std", feature = "panic"))))]
+    pub fn set_profiler<P: Profiler + 'static>(&self, profiler: Arc<P>) {
+        self.try_set_profiler(profiler).unwrap()
+    }
+    /// Removes the `Profiler` called when workloads run and returns it.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn try_remove_profiler(&self) -> Result<Option<Arc<dyn Profiler>>, error::Borrow> {
+        Ok(self.scheduler.try_borrow_mut()?.profiler.take())
+    }
+    /// Removes the `Profiler` called when workloads run and returns it.  
+    /// Unwraps errors.
+    #[cfg(all(feature = "std", feature = "panic"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "std", feature = "panic"))))]
+    pub fn remove_profiler(&self) -> Option<Arc<dyn Profiler>> {
+        self.try_remove_profiler().unwrap()
+    }
+    /// When `catch` is `true`, panics in systems are caught and returned as `error::Run::Panic` instead of unwinding through `run_workload`.  
+    /// Storages modified by the system before it panicked are not restored.  
+    /// The panic hook is still called.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{error, system, World};
+    ///
+    /// fn broken() {
+    ///     panic!("oops");
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.catch_panics(true);
+    ///
+    /// world
+    ///     .add_workload("Broken")
+    ///     .with_system(system!(broken))
+    ///     .build();
+    ///
+    /// match world.try_run_default() {
+    ///     Err(error::RunWorkload::Run((_, error::Run::Panic((name, _))))) => {
+    ///         assert!(name.ends_with("broken"))
+    ///     }
+    ///     _ => panic!(),
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn try_catch_panics(&self, catch: bool) -> Result<(), error::Borrow> {
+        self.scheduler.try_borrow_mut()?.catch_panics = catch;
+        Ok(())
+    }
+    /// When `catch` is `true`, panics in systems are caught and returned as `error::Run::Panic` instead of unwinding through `run_workload`.  
+    /// Storages modified by the system before it panicked are not restored.  
+    /// The panic hook is still called.  
+    /// Unwraps errors.
+    #[cfg(all(feature = "std", feature = "panic"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "std", feature = "panic"))))]
+    pub fn catch_panics(&self, catch: bool) {
+        self.try_catch_panics(catch).unwrap()
+    }
+    fn set_system_enabled(&self, name: &str, enabled: bool) -> Result<(), error::EnableSystem> {
+        let mut scheduler = self
+            .scheduler
+            .try_borrow_mut()
+            .map_err(|_| error::EnableSystem::Borrow)?;
+
+        if scheduler.set_enabled(name, enabled) {
+            Ok(())
+        } else {
+            Err(error::EnableSystem::MissingSystem)
+        }
+    }
     /// Runs the `name` workload.
     pub fn try_run_workload(&self, name: impl AsRef<str> + Sync) -> Result<(), error::RunWorkload> {
         let scheduler = self
@@ -1112,7 +1927,7 @@ let i = world.run(sys1);
             .try_borrow()
             .map_err(|_| error::RunWorkload::Scheduler)?;
         if let Some(range) = scheduler.workloads.get(name.as_ref()) {
-            self.try_run_workload_index(&*scheduler, range.clone())
+            self.try_run_workload_index(&*scheduler, name.as_ref(), range.clone())
         } else {
             Err(error::RunWorkload::MissingWorkload)
         }
@@ -1127,37 +1942,162 @@ let i = world.run(sys1);
     fn try_run_workload_index(
         &self,
         scheduler: &Scheduler,
+        name: &str,
         workload: Range<usize>,
     ) -> Result<(), error::RunWorkload> {
-        for batch in &scheduler.batch[workload] {
-            if batch.len() == 1 {
-                scheduler.systems[batch[0]](self).map_err(|err| {
-                    error::RunWorkload::Run((scheduler.system_names[batch[0]], err))
-                })?;
+        if let Some(run_if) = scheduler.workload_conditions.get(name) {
+            if !run_if(self) {
+                return Ok(());
+            }
+        }
+
+        let policy = scheduler.error_policy(name);
+        let mut errors = Vec::new();
+        // storages borrowed mutably by failed systems
+        let mut failed_borrows: Vec<TypeId> = Vec::new();
+
+        for (batch_index, (batch, conditions)) in scheduler.batch[workload.clone()]
+            .iter()
+            .zip(&scheduler.conditions[workload])
+            .enumerate()
+        {
+            // disabled systems and dependents of failed systems are skipped, run conditions are checked before any system of the batch runs
+            let selected: Vec<usize>;
+            let batch: &[usize] = if conditions.iter().any(Option::is_some)
+                || batch.iter().any(|&index| !scheduler.enabled[index])
+                || !failed_borrows.is_empty()
+            {
+                selected = batch
+                    .iter()
+                    .zip(conditions.iter())
+                    .filter(|(&index, run_if)| {
+                        !depends_on(scheduler, index, &failed_borrows)
+                            && scheduler.enabled[index]
+                            && match run_if {
+                                Some(run_if) => run_if(self),
+                                None => true,
+                            }
+                    })
+                    .map(|(&index, _)| index)
+                    .collect();
+                &selected
             } else {
+                batch
+            };
+
+            #[cfg(feature = "std")]
+            if let Some(profiler) = &scheduler.profiler {
+                profiler.batch_start(batch_index);
+            }
+            #[cfg(not(feature = "std"))]
+            let _ = batch_index;
+
+            let run = |index: usize| {
+                self.run_system(scheduler, index)
+                    .map_err(|err| error::RunWorkload::Run((scheduler.system_names[index], err)))
+            };
+
+            let result = if policy == ErrorPolicy::Stop {
+                if batch.len() == 1 {
+                    run(batch[0])
+                } else {
+                    #[cfg(feature = "parallel")]
+                    {
+                        use rayon::prelude::*;
+
+                        self.thread_pool
+                            .install(|| batch.into_par_iter().try_for_each(|&index| run(index)))
+                    }
+                    #[cfg(not(feature = "parallel"))]
+                    {
+                        batch.iter().try_for_each(|&index| run(index))
+                    }
+                }
+            } else {
+                let failed = |&index: &usize| {
+                    self.run_system(scheduler, index)
+                        .err()
+                        .map(|err| (index, err))
+                };
+
                 #[cfg(feature = "parallel")]
-                {
+                let batch_errors: Vec<(usize, error::Run)> = {
                     use rayon::prelude::*;
 
-                    self.thread_pool.install(|| {
-                        batch.into_par_iter().try_for_each(|&index| {
-                            (scheduler.systems[index])(self).map_err(|err| {
-                                error::RunWorkload::Run((scheduler.system_names[index], err))
-                            })
-                        })
-                    })?
-                }
+                    self.thread_pool
+                        .install(|| batch.into_par_iter().filter_map(failed).collect())
+                };
                 #[cfg(not(feature = "parallel"))]
-                {
-                    batch.iter().try_for_each(|&index| {
-                        (scheduler.systems[index])(self).map_err(|err| {
-                            error::RunWorkload::Run((scheduler.system_names[index], err))
-                        })
-                    })?
+                let batch_errors: Vec<(usize, error::Run)> =
+                    batch.iter().filter_map(failed).collect();
+
+                for (index, err) in batch_errors {
+                    if policy == ErrorPolicy::SkipDependents {
+                        add_unique_borrows(scheduler, index, &mut failed_borrows);
+                    }
+                    errors.push((scheduler.system_names[index], err));
                 }
+
+                Ok(())
+            };
+
+            #[cfg(feature = "std")]
+            if let Some(profiler) = &scheduler.profiler {
+                profiler.batch_end(batch_index);
             }
+
+            result?;
+        }
+
+        if !scheduler.events.is_empty() {
+            let all_storages = self.all_storages.try_borrow().map_err(|err| {
+                error::RunWorkload::Storage(error::GetStorage::AllStoragesBorrow(err))
+            })?;
+            for (_, update) in &scheduler.events {
+                update(&all_storages).map_err(error::RunWorkload::Storage)?;
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(error::RunWorkload::Errors(errors))
+        }
+    }
+    fn run_system(&self, scheduler: &Scheduler, index: usize) -> Result<(), error::Run> {
+        #[cfg(feature = "std")]
+        let this_run = self
+            .all_storages
+            .try_borrow()
+            .map_err(|err| error::Run::GetStorage(error::GetStorage::AllStoragesBorrow(err)))?
+            .new_change_tick();
+
+        // system_end is called when the guard is dropped, even if the system panics
+        #[cfg(feature = "std")]
+        let _profiler = scheduler
+            .profiler
+            .as_deref()
+            .map(|profiler| scheduler::enter_profiler(profiler, scheduler.system_names[index]));
+
+        #[cfg(feature = "std")]
+        let _locals = crate::borrow::enter_locals(&scheduler.locals[index], this_run);
+
+        #[cfg(feature = "std")]
+        {
+            if scheduler.catch_panics {
+                // borrows are released while unwinding so the World stays usable
+                std::panic::catch_unwind(AssertUnwindSafe(|| (scheduler.systems[index])(self)))
+                    .unwrap_or_else(|payload| {
+                        Err(error::Run::Panic((scheduler.system_names[index], payload)))
+                    })
+            } else {
+                (scheduler.systems[index])(self)
+            }
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            (scheduler.systems[index])(self)
         }
-        Ok(())
     }
     /// Run the default workload.
     pub fn try_run_default(&self) -> Result<(), error::RunWorkload> {
@@ -1165,8 +2105,9 @@ let i = world.run(sys1);
             .scheduler
             .try_borrow()
             .map_err(|_| error::RunWorkload::Scheduler)?;
-        if !scheduler.batch.is_empty() {
-            self.try_run_workload_index(&scheduler, scheduler.default.clone())?
+        if let Some(name) = &scheduler.default {
+            let range = scheduler.workloads[name].clone();
+            self.try_run_workload_index(&scheduler, name, range)?
         }
         Ok(())
     }
@@ -1178,3 +2119,28 @@ let i = world.run(sys1);
         self.try_run_default().unwrap();
     }
 }
+
+/// Returns `true` if the system borrows a storage in `failed_borrows`.
+fn depends_on(scheduler: &Scheduler, index: usize, failed_borrows: &[TypeId]) -> bool {
+    if failed_borrows.is_empty() {
+        return false;
+    }
+
+    scheduler.borrow_info[index]
+        .0
+        .iter()
+        .any(|(type_id, _, _)| {
+            *type_id == TypeId::of::<AllStorages>()
+                || failed_borrows.contains(type_id)
+                || failed_borrows.contains(&TypeId::of::<AllStorages>())
+        })
+}
+
+/// Adds the storages the system borrows mutably to `failed_borrows`.
+fn add_unique_borrows(scheduler: &Scheduler, index: usize, failed_borrows: &mut Vec<TypeId>) {
+    for &(type_id, _, mutation) in scheduler.borrow_info[index].0.iter() {
+        if mutation == Mutation::Unique && !failed_borrows.contains(&type_id) {
+            failed_borrows.push(type_id);
+        }
+    }
+}
@@ -1,12 +1,14 @@
-use super::Scheduler;
+use super::{Condition, ErrorPolicy, Scheduler};
 use crate::atomic_refcell::RefMut;
-use crate::borrow::Mutation;
+use crate::borrow::{LocalDefault, Locals, Mutation};
 use crate::error;
 use crate::storage::AllStorages;
 use crate::system::System;
+use crate::view::AllStoragesViewMut;
 use crate::world::World;
 use alloc::borrow::Cow;
 use alloc::boxed::Box;
+use alloc::sync::Arc;
 use alloc::vec;
 use alloc::vec::Vec;
 use core::any::type_name;
@@ -19,24 +21,63 @@ use hashbrown::hash_map::Entry;
 #[must_use]
 pub struct WorkloadBuilder<'a> {
     scheduler: RefMut<'a, Scheduler>,
-    systems: Vec<(
+    systems: Vec<(&'static str, Range<usize>, bool, WorkloadSystem)>,
+    // labels, ordering constraints and run condition of each system
+    infos: Vec<SystemInfo>,
+    // systems added by the last call to with_system or with_workload
+    last: Range<usize>,
+    run_if: Option<Condition>,
+    error_policy: ErrorPolicy,
+    borrow_info: Vec<(TypeId, &'static str, Mutation)>,
+    name: Cow<'static, str>,
+    // the workload will be the default one once built
+    set_default: bool,
+    // the workload with the same name is removed once this one is ordered
+    replace: bool,
+}
+
+#[derive(Default)]
+struct SystemInfo {
+    labels: Vec<Cow<'static, str>>,
+    before: Vec<Cow<'static, str>>,
+    after: Vec<Cow<'static, str>>,
+    run_if: Option<Condition>,
+    // systems of the same builder that have to run first
+    predecessors: Vec<usize>,
+}
+
+#[allow(clippy::type_complexity)]
+enum WorkloadSystem {
+    New(
         TypeId,
-        &'static str,
-        Range<usize>,
-        bool,
         Box<dyn Fn(&World) -> Result<(), error::Run> + Send + Sync + 'static>,
-    )>,
-    borrow_info: Vec<(TypeId, Mutation)>,
-    name: Cow<'static, str>,
+        // created when the workload is built
+        Vec<(TypeId, LocalDefault)>,
+    ),
+    // index of a system already in the scheduler
+    Registered(usize),
 }
 
 impl<'a> WorkloadBuilder<'a> {
     pub(crate) fn new(scheduler: RefMut<'a, Scheduler>, name: Cow<'static, str>) -> Self {
+        WorkloadBuilder::with_replace(scheduler, name, false)
+    }
+    pub(crate) fn with_replace(
+        scheduler: RefMut<'a, Scheduler>,
+        name: Cow<'static, str>,
+        replace: bool,
+    ) -> Self {
         WorkloadBuilder {
             scheduler,
             systems: Vec::new(),
+            infos: Vec::new(),
+            last: 0..0,
+            run_if: None,
+            error_policy: ErrorPolicy::Stop,
             borrow_info: Vec::new(),
             name,
+            set_default: false,
+            replace,
         }
     }
 }
@@ -100,14 +141,17 @@ impl<'a> WorkloadBuilder<'a> {
 
         let borrows = &self.borrow_info[old_len..];
 
-        if borrows.contains(&(TypeId::of::<AllStorages>(), Mutation::Unique)) && borrows.len() > 1 {
+        if borrows.iter().any(|&(type_id, _, mutation)| {
+            type_id == TypeId::of::<AllStorages>() && mutation == Mutation::Unique
+        }) && borrows.len() > 1
+        {
             return Err(error::InvalidSystem::AllStorages);
         }
 
         let mid = borrows.len() / 2 + (borrows.len() % 2 != 0) as usize;
 
-        for (a_type_id, a_borrow) in &borrows[..mid] {
-            for (b_type_id, b_borrow) in &borrows[mid..] {
+        for (a_type_id, _, a_borrow) in &borrows[..mid] {
+            for (b_type_id, _, b_borrow) in &borrows[mid..] {
                 if a_type_id == b_type_id {
                     match (a_borrow, b_borrow) {
                         (Mutation::Unique, Mutation::Unique) => {
@@ -123,13 +167,29 @@ impl<'a> WorkloadBuilder<'a> {
             }
         }
 
+        let mut locals = Vec::new();
+        F::local_defaults(&mut locals);
+
+        for (index, (type_id, _)) in locals.iter().enumerate() {
+            if locals[index + 1..]
+                .iter()
+                .any(|(other_type_id, _)| other_type_id == type_id)
+            {
+                return Err(error::InvalidSystem::MultipleViewsMut);
+            }
+        }
+
         let is_send_sync = F::is_send_sync();
+        self.infos.push(SystemInfo {
+            labels: vec![type_name::<F>().rsplit("::").next().unwrap().into()],
+            ..Default::default()
+        });
+        self.last = self.systems.len()..self.systems.len() + 1;
         self.systems.push((
-            core::any::TypeId::of::<S>(),
             type_name::<F>(),
             old_len..self.borrow_info.len(),
             is_send_sync,
-            Box::new(system),
+            WorkloadSystem::New(core::any::TypeId::of::<S>(), Box::new(system), locals),
         ));
         Ok(self)
     }
@@ -189,6 +249,154 @@ impl<'a> WorkloadBuilder<'a> {
     ) -> WorkloadBuilder<'a> {
         self.try_with_system(system).unwrap()
     }
+    /// Adds a system applying the commands recorded by `Commands` to the workload been created.  
+    /// It borrows `AllStorages` so it runs after the systems added before it and before the systems added after it.  
+    /// The system is labelled `flush_commands`.
+    ///
+    /// ### Example:
+    /// ```
+    /// use shipyard::{system, Commands, View, World};
+    ///
+    /// fn spawn(commands: Commands) {
+    ///     commands.spawn((0u32,));
+    /// }
+    ///
+    /// fn count(u32s: View<u32>) {
+    ///     assert_eq!(u32s.len(), 1);
+    /// }
+    ///
+    /// let world = World::new();
+    ///
+    /// world
+    ///     .add_workload("Spawn")
+    ///     .with_system(system!(spawn))
+    ///     .with_flush()
+    ///     .with_system(system!(count))
+    ///     .build();
+    ///
+    /// world.run_default();
+    /// ```
+    pub fn with_flush(self) -> WorkloadBuilder<'a> {
+        match self.try_with_system((
+            |world: &World| {
+                world
+                    .try_run(flush_commands)?
+                    .map_err(error::Run::GetStorage)
+            },
+            flush_commands,
+        )) {
+            Ok(builder) => builder,
+            // a system only borrowing AllStorages is always valid
+            Err(_) => unreachable!(),
+        }
+    }
+    /// Adds all systems of the `name` workload to the workload been created.  
+    /// The systems are batched again with the other systems of this workload, they keep running in the same order relative to each other.  
+    /// They're labelled with `name`, `before` and `after` can then be used to order the whole workload.  
+    /// The run conditions of the included workload and of its systems are checked for each of its systems.  
+    /// Its error policy isn't included, errors of its systems are handled with the policy of this workload.  
+    /// Changes made to `name` after this call don't affect this workload.
+    ///
+    /// ### Example:
+    /// ```
+    /// use shipyard::{system, UniqueView, UniqueViewMut, World};
+    ///
+    /// fn gravity(mut speed: UniqueViewMut<i32>) {
+    ///     *speed -= 1;
+    /// }
+    ///
+    /// fn movement(speed: UniqueView<i32>, mut position: UniqueViewMut<i64>) {
+    ///     *position += *speed as i64;
+    /// }
+    ///
+    /// fn render(position: UniqueView<i64>) {
+    ///     assert_eq!(*position, -1);
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.add_unique(0i32);
+    /// world.add_unique(0i64);
+    ///
+    /// world
+    ///     .add_workload("Physics")
+    ///     .with_system(system!(gravity))
+    ///     .with_system(system!(movement))
+    ///     .build();
+    ///
+    /// world
+    ///     .add_workload("Frame")
+    ///     .with_system(system!(render))
+    ///     .after("Physics")
+    ///     .with_workload("Physics")
+    ///     .build();
+    ///
+    /// world.run_workload("Frame");
+    /// ```
+    pub fn try_with_workload(
+        mut self,
+        name: impl Into<Cow<'static, str>>,
+    ) -> Result<WorkloadBuilder<'a>, error::WithWorkload> {
+        let name = name.into();
+        let range = self
+            .scheduler
+            .workloads
+            .get(&name)
+            .ok_or(error::WithWorkload::MissingWorkload)?
+            .clone();
+        let workload_condition = self.scheduler.workload_conditions.get(&name).cloned();
+
+        let start = self.systems.len();
+        let mut previous_batch = Vec::new();
+        for batch_index in range {
+            let mut batch = Vec::new();
+
+            for (&system, run_if) in self.scheduler.batch[batch_index]
+                .iter()
+                .zip(self.scheduler.conditions[batch_index].iter())
+            {
+                let system_name = self.scheduler.system_names[system];
+                let (borrows, is_send_sync) = &self.scheduler.borrow_info[system];
+
+                let old_len = self.borrow_info.len();
+                self.borrow_info.extend_from_slice(borrows);
+
+                batch.push(self.systems.len());
+                self.infos.push(SystemInfo {
+                    labels: vec![
+                        system_name.rsplit("::").next().unwrap().into(),
+                        name.clone(),
+                    ],
+                    run_if: both(run_if.clone(), workload_condition.clone()),
+                    // systems keep running after the ones of the previous batches
+                    predecessors: previous_batch.clone(),
+                    ..Default::default()
+                });
+                self.systems.push((
+                    system_name,
+                    old_len..self.borrow_info.len(),
+                    *is_send_sync,
+                    WorkloadSystem::Registered(system),
+                ));
+            }
+
+            previous_batch = batch;
+        }
+        self.last = start..self.systems.len();
+
+        Ok(self)
+    }
+    /// Adds all systems of the `name` workload to the workload been created.  
+    /// The systems are batched again with the other systems of this workload, they keep running in the same order relative to each other.  
+    /// They're labelled with `name`, `before` and `after` can then be used to order the whole workload.  
+    /// The run conditions of the included workload and of its systems are checked for each of its systems.  
+    /// Its error policy isn't included, errors of its systems are handled with the policy of this workload.  
+    /// Changes made to `name` after this call don't affect this workload.  
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn with_workload(self, name: impl Into<Cow<'static, str>>) -> WorkloadBuilder<'a> {
+        self.try_with_workload(name).unwrap()
+    }
     /// Calls the given function on the builder.
     ///
     /// Can be useful to chain calls to functions that modify a `WorkloadBuilder`.
@@ -229,57 +437,211 @@ impl<'a> WorkloadBuilder<'a> {
     {
         f(self)
     }
-    /// Finishes the workload creation and store it in the `World`.
-    pub fn build(mut self) {
+    /// Adds a label to the systems of the last `with_system` or `with_workload` call, they can be ordered relative to it with `before` and `after`.  
+    /// Systems are labelled with the name of their function by default.  
+    /// Does nothing if no system was added yet.
+    pub fn label(mut self, label: impl Into<Cow<'static, str>>) -> Self {
+        let label = label.into();
+        for info in &mut self.infos[self.last.clone()] {
+            info.labels.push(label.clone());
+        }
+        self
+    }
+    /// The systems of the last `with_system` or `with_workload` call will run before all systems with `label` in this workload, even if their borrows don't conflict.  
+    /// Does nothing if no system was added yet.
+    ///
+    /// ### Example:
+    /// ```
+    /// use shipyard::{system, View, World};
+    ///
+    /// fn physics(_: View<u32>) {}
+    /// fn render(_: View<u32>) {}
+    ///
+    /// let world = World::new();
+    ///
+    /// world
+    ///     .add_workload("Frame")
+    ///     .with_system(system!(render))
+    ///     .after("physics")
+    ///     .with_system(system!(physics))
+    ///     .build();
+    ///
+    /// world.run_default();
+    /// ```
+    pub fn before(mut self, label: impl Into<Cow<'static, str>>) -> Self {
+        let label = label.into();
+        for info in &mut self.infos[self.last.clone()] {
+            info.before.push(label.clone());
+        }
+        self
+    }
+    /// The systems of the last `with_system` or `with_workload` call will run after all systems with `label` in this workload, even if their borrows don't conflict.  
+    /// Does nothing if no system was added yet.
+    pub fn after(mut self, label: impl Into<Cow<'static, str>>) -> Self {
+        let label = label.into();
+        for info in &mut self.infos[self.last.clone()] {
+            info.after.push(label.clone());
+        }
+        self
+    }
+    /// Only runs the systems of the last `with_system` or `with_workload` call when `condition` returns `true`.  
+    /// The condition is checked each time the workload reaches the system's batch, before any system of the batch runs.  
+    /// When the system doesn't run, its storages are not borrowed.  
+    /// Does nothing if no system was added yet.
+    ///
+    /// ### Example:
+    /// ```
+    /// use shipyard::{system, UniqueView, UniqueViewMut, World};
+    ///
+    /// struct Paused(bool);
+    ///
+    /// fn is_paused(paused: UniqueView<Paused>) -> bool {
+    ///     paused.0
+    /// }
+    ///
+    /// fn pause_menu(mut count: UniqueViewMut<u32>) {
+    ///     *count += 1;
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.add_unique(Paused(false));
+    /// world.add_unique(0u32);
+    ///
+    /// world
+    ///     .add_workload("Menu")
+    ///     .with_system(system!(pause_menu))
+    ///     .run_if(|world: &World| world.run(is_paused))
+    ///     .build();
+    ///
+    /// world.run_default();
+    /// world.run(|count: UniqueView<u32>| assert_eq!(*count, 0));
+    ///
+    /// world.run(|mut paused: UniqueViewMut<Paused>| paused.0 = true);
+    /// world.run_default();
+    /// world.run(|count: UniqueView<u32>| assert_eq!(*count, 1));
+    /// ```
+    pub fn run_if<C: Fn(&World) -> bool + Send + Sync + 'static>(mut self, condition: C) -> Self {
+        let condition: Condition = Arc::new(condition);
+        for info in &mut self.infos[self.last.clone()] {
+            info.run_if = both(Some(condition.clone()), info.run_if.take());
+        }
+        self
+    }
+    /// Only runs the workload when `condition` returns `true`.  
+    /// The condition is checked once each time the workload runs.
+    pub fn workload_run_if<C: Fn(&World) -> bool + Send + Sync + 'static>(
+        mut self,
+        condition: C,
+    ) -> Self {
+        self.run_if = Some(Arc::new(condition));
+        self
+    }
+    /// Sets what the workload does when one of its systems returns an error, it stops by default.  
+    /// The policy applies to all systems of the workload, including the ones added with `with_workload`.
+    ///
+    /// ### Example:
+    /// ```
+    /// use shipyard::{error, system, try_system, ErrorPolicy, UniqueViewMut, World};
+    ///
+    /// fn fail(_: UniqueViewMut<u32>) -> Result<(), error::NotUpdatePack> {
+    ///     Err(error::NotUpdatePack)
+    /// }
+    ///
+    /// fn count(mut count: UniqueViewMut<usize>) {
+    ///     *count += 1;
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.add_unique(0u32);
+    /// world.add_unique(0usize);
+    ///
+    /// world
+    ///     .add_workload("Server")
+    ///     .with_system(try_system!(fail))
+    ///     .with_system(system!(count))
+    ///     .on_error(ErrorPolicy::Continue)
+    ///     .build();
+    ///
+    /// match world.try_run_default() {
+    ///     Err(error::RunWorkload::Errors(errors)) => assert_eq!(errors.len(), 1),
+    ///     _ => panic!(),
+    /// }
+    /// world.run(|count: UniqueViewMut<usize>| assert_eq!(*count, 1));
+    /// ```
+    pub fn on_error(mut self, policy: ErrorPolicy) -> Self {
+        self.error_policy = policy;
+        self
+    }
+    /// Finishes the workload creation and store it in the `World`.  
+    /// Returns an error if the ordering constraints between systems form a cycle.
+    pub fn try_build(mut self) -> Result<(), error::Build> {
         if self.systems.len() == 1 {
-            let (type_id, system_name, _, _, system) = self.systems.pop().unwrap();
+            self.remove_replaced();
+
+            let (system_name, info_range, is_send_sync, system) = self.systems.pop().unwrap();
 
             let mut name = "".into();
             core::mem::swap(&mut name, &mut self.name);
 
             let range = self.scheduler.batch.len()..(self.scheduler.batch.len() + 1);
-            if self.scheduler.workloads.is_empty() {
-                self.scheduler.default = range.clone();
+            if self.scheduler.workloads.is_empty() || self.set_default {
+                self.scheduler.default = Some(name.clone());
+            }
+            if let Some(run_if) = self.run_if.take() {
+                self.scheduler
+                    .workload_conditions
+                    .insert(name.clone(), run_if);
+            }
+            if self.error_policy != ErrorPolicy::Stop {
+                self.scheduler
+                    .error_policies
+                    .insert(name.clone(), self.error_policy);
             }
             self.scheduler.workloads.insert(name, range);
 
-            let len = self.scheduler.systems.len();
-            let system_index = match self.scheduler.lookup_table.entry(type_id) {
-                Entry::Vacant(vacant) => {
-                    vacant.insert(len);
-                    self.scheduler.systems.push(system);
-                    self.scheduler.system_names.push(system_name);
-                    self.scheduler.systems.len() - 1
-                }
-                Entry::Occupied(occupied) => *occupied.get(),
-            };
+            let system_index = self.register(system, system_name, info_range, is_send_sync);
 
             self.scheduler.batch.push(Box::new([system_index]));
+            self.scheduler
+                .conditions
+                .push(Box::new([self.infos.pop().unwrap().run_if]));
         } else {
+            let (order, predecessors) = self.order()?;
+
+            self.remove_replaced();
+
             let batch_start = self.scheduler.batch.len();
             let mut new_batch = vec![Vec::new()];
+            let mut new_conditions = vec![Vec::new()];
             let mut batch_info = vec![Vec::new()];
-
-            for (type_id, name, info_range, is_send_sync, system) in self.systems.drain(..) {
-                let len = self.scheduler.systems.len();
-                let system_index = match self.scheduler.lookup_table.entry(type_id) {
-                    Entry::Vacant(vacant) => {
-                        vacant.insert(len);
-                        self.scheduler.systems.push(system);
-                        self.scheduler.system_names.push(name);
-                        self.scheduler.systems.len() - 1
-                    }
-                    Entry::Occupied(occupied) => *occupied.get(),
-                };
+            // batch each system was added to
+            let mut placed = vec![0; self.systems.len()];
+
+            let mut systems: Vec<_> = self.systems.drain(..).map(Some).collect();
+            for index in order {
+                let (name, info_range, is_send_sync, system) = systems[index].take().unwrap();
+                let run_if = self.infos[index].run_if.take();
+                // systems can't be added before the batches of the systems they run after
+                let min_batch = predecessors[index]
+                    .iter()
+                    .map(|&predecessor| placed[predecessor] + 1)
+                    .max()
+                    .unwrap_or(0);
+
+                let system_index = self.register(system, name, info_range.clone(), is_send_sync);
 
                 if is_send_sync {
                     let mut batch_index = new_batch.len();
                     for batch in batch_info.iter().rev() {
+                        if batch_index == min_batch {
+                            break;
+                        }
+
                         let mut conflict = false;
-                        for &(type_id, mutation) in &self.borrow_info[info_range.clone()] {
+                        for &(type_id, _, mutation) in &self.borrow_info[info_range.clone()] {
                             match mutation {
                                 Mutation::Shared => {
-                                    for &(batch_type_id, mutation) in batch.iter() {
+                                    for &(batch_type_id, _, mutation) in batch.iter() {
                                         #[cfg(feature = "parallel")]
                                         {
                                             if type_id == batch_type_id
@@ -305,7 +667,7 @@ impl<'a> WorkloadBuilder<'a> {
                                     }
                                 }
                                 Mutation::Unique => {
-                                    for &(batch_type_id, _) in batch.iter() {
+                                    for &(batch_type_id, _, _) in batch.iter() {
                                         #[cfg(feature = "parallel")]
                                         {
                                             if type_id == batch_type_id
@@ -340,48 +702,186 @@ impl<'a> WorkloadBuilder<'a> {
 
                     if batch_index == batch_info.len() {
                         new_batch.push(vec![system_index]);
+                        new_conditions.push(vec![run_if]);
                         batch_info.push(self.borrow_info[info_range].to_vec());
                     } else {
                         new_batch[batch_index].push(system_index);
+                        new_conditions[batch_index].push(run_if);
                         batch_info[batch_index].extend_from_slice(&self.borrow_info[info_range]);
                     }
+
+                    placed[index] = batch_index;
                 } else {
                     let last = new_batch.last_mut().unwrap();
                     if last.is_empty() {
                         last.push(system_index);
                         new_batch.push(Vec::new());
-                        batch_info
-                            .last_mut()
-                            .unwrap()
-                            .push((TypeId::of::<AllStorages>(), Mutation::Unique));
+                        new_conditions.last_mut().unwrap().push(run_if);
+                        new_conditions.push(Vec::new());
+                        batch_info.last_mut().unwrap().push((
+                            TypeId::of::<AllStorages>(),
+                            type_name::<AllStorages>(),
+                            Mutation::Unique,
+                        ));
                         batch_info.push(Vec::new());
                     } else {
                         new_batch.push(vec![system_index]);
                         new_batch.push(Vec::new());
-                        batch_info.push(vec![(TypeId::of::<AllStorages>(), Mutation::Unique)]);
+                        new_conditions.push(vec![run_if]);
+                        new_conditions.push(Vec::new());
+                        batch_info.push(vec![(
+                            TypeId::of::<AllStorages>(),
+                            type_name::<AllStorages>(),
+                            Mutation::Unique,
+                        )]);
                         batch_info.push(Vec::new());
                     }
+
+                    placed[index] = new_batch.len() - 2;
                 }
             }
 
             if new_batch.last().unwrap().is_empty() {
                 new_batch.pop();
+                new_conditions.pop();
             }
 
             self.scheduler
                 .batch
                 .extend(new_batch.into_iter().map(Vec::into_boxed_slice));
-
-            if self.scheduler.workloads.is_empty() {
-                self.scheduler.default = batch_start..(self.scheduler.batch.len());
-            }
+            self.scheduler
+                .conditions
+                .extend(new_conditions.into_iter().map(Vec::into_boxed_slice));
 
             let mut name = "".into();
             core::mem::swap(&mut name, &mut self.name);
             let len = self.scheduler.batch.len();
+            if self.scheduler.workloads.is_empty() || self.set_default {
+                self.scheduler.default = Some(name.clone());
+            }
+            if let Some(run_if) = self.run_if.take() {
+                self.scheduler
+                    .workload_conditions
+                    .insert(name.clone(), run_if);
+            }
+            if self.error_policy != ErrorPolicy::Stop {
+                self.scheduler
+                    .error_policies
+                    .insert(name.clone(), self.error_policy);
+            }
             self.scheduler.workloads.insert(name, batch_start..len);
         }
+
+        if self.replace {
+            self.scheduler.reclaim_systems();
+        }
+
+        Ok(())
+    }
+    /// Finishes the workload creation and store it in the `World`.  
+    /// Unwraps errors.
+    #[cfg(feature = "panic")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "panic")))]
+    pub fn build(self) {
+        self.try_build().unwrap()
     }
+    /// Removes the batches of the workload this one replaces, taking its place as default workload.  
+    /// Its systems are reclaimed once the new workload is stored, indices of registered systems stay valid until then.
+    fn remove_replaced(&mut self) {
+        if self.replace {
+            if self.scheduler.default.as_ref() == Some(&self.name) {
+                self.set_default = true;
+            }
+
+            self.scheduler.remove_batches(&self.name);
+        }
+    }
+    /// Adds `system` to the scheduler if it isn't already present and returns its index.
+    fn register(
+        &mut self,
+        system: WorkloadSystem,
+        name: &'static str,
+        info_range: Range<usize>,
+        is_send_sync: bool,
+    ) -> usize {
+        match system {
+            WorkloadSystem::New(type_id, system, locals) => {
+                let len = self.scheduler.systems.len();
+                match self.scheduler.lookup_table.entry(type_id) {
+                    Entry::Vacant(vacant) => {
+                        vacant.insert(len);
+                        self.scheduler.systems.push(system);
+                        self.scheduler.system_names.push(name);
+                        let borrows = self.borrow_info[info_range].into();
+                        self.scheduler.borrow_info.push((borrows, is_send_sync));
+                        self.scheduler.enabled.push(true);
+                        self.scheduler.locals.push(Locals::new(&locals));
+                        len
+                    }
+                    Entry::Occupied(occupied) => *occupied.get(),
+                }
+            }
+            WorkloadSystem::Registered(index) => index,
+        }
+    }
+    /// Sorts the systems so each one comes after the ones it has to run after, keeping insertion order when possible.  
+    /// Returns the sorted indices and the systems each one has to run after.
+    fn order(&self) -> Result<(Vec<usize>, Vec<Vec<usize>>), error::Build> {
+        let len = self.infos.len();
+
+        let mut predecessors = vec![Vec::new(); len];
+        for (index, info) in self.infos.iter().enumerate() {
+            predecessors[index].extend_from_slice(&info.predecessors);
+            for (other_index, other) in self.infos.iter().enumerate() {
+                if index != other_index
+                    && (info.after.iter().any(|label| other.labels.contains(label))
+                        || other.before.iter().any(|label| info.labels.contains(label)))
+                {
+                    predecessors[index].push(other_index);
+                }
+            }
+        }
+
+        let mut order = Vec::with_capacity(len);
+        let mut sorted = vec![false; len];
+        while order.len() < len {
+            let next = (0..len).find(|&index| {
+                !sorted[index]
+                    && predecessors[index]
+                        .iter()
+                        .all(|&predecessor| sorted[predecessor])
+            });
+
+            if let Some(index) = next {
+                sorted[index] = true;
+                order.push(index);
+            } else {
+                return Err(error::Build::Cycle(
+                    (0..len)
+                        .filter(|&index| !sorted[index])
+                        .map(|index| self.systems[index].0)
+                        .collect(),
+                ));
+            }
+        }
+
+        Ok((order, predecessors))
+    }
+}
+
+/// Combines two run conditions, both have to return `true` for the system to run.
+fn both(condition: Option<Condition>, other: Option<Condition>) -> Option<Condition> {
+    match (condition, other) {
+        (Some(condition), Some(other)) => Some(Arc::new(move |world: &World| {
+            condition(world) && other(world)
+        })),
+        (condition, None) => condition,
+        (None, other) => other,
+    }
+}
+
+fn flush_commands(mut all_storages: AllStoragesViewMut<'_>) -> Result<(), error::GetStorage> {
+    all_storages.try_flush_commands()
 }
 
 #[test]
@@ -404,7 +904,8 @@ fn single_immutable() {
     WorkloadBuilder::new(scheduler.try_borrow_mut().unwrap(), "System1".into())
         .try_with_system((|world: &World| world.try_run(system1), system1))
         .unwrap()
-        .build();
+        .try_build()
+        .unwrap();
 
     let scheduler = scheduler.try_borrow_mut().unwrap();
     assert_eq!(scheduler.systems.len(), 1);
@@ -412,7 +913,7 @@ fn single_immutable() {
     assert_eq!(&*scheduler.batch[0], &[0]);
     assert_eq!(scheduler.workloads.len(), 1);
     assert_eq!(scheduler.workloads.get("System1"), Some(&(0..1)));
-    assert_eq!(scheduler.default, 0..1);
+    assert_eq!(scheduler.default.as_deref(), Some("System1"));
 }
 #[test]
 fn single_mutable() {
@@ -434,7 +935,8 @@ fn single_mutable() {
     WorkloadBuilder::new(scheduler.try_borrow_mut().unwrap(), "System1".into())
         .try_with_system((|world: &World| world.try_run(system1), system1))
         .unwrap()
-        .build();
+        .try_build()
+        .unwrap();
 
     let scheduler = scheduler.try_borrow_mut().unwrap();
     assert_eq!(scheduler.systems.len(), 1);
@@ -442,7 +944,7 @@ fn single_mutable() {
     assert_eq!(&*scheduler.batch[0], &[0]);
     assert_eq!(scheduler.workloads.len(), 1);
     assert_eq!(scheduler.workloads.get("System1"), Some(&(0..1)));
-    assert_eq!(scheduler.default, 0..1);
+    assert_eq!(scheduler.default.as_deref(), Some("System1"));
 }
 #[test]
 fn multiple_immutable() {
@@ -467,7 +969,8 @@ fn multiple_immutable() {
         .unwrap()
         .try_with_system((|world: &World| world.try_run(system2), system2))
         .unwrap()
-        .build();
+        .try_build()
+        .unwrap();
 
     let scheduler = scheduler.try_borrow_mut().unwrap();
     assert_eq!(scheduler.systems.len(), 2);
@@ -475,7 +978,7 @@ fn multiple_immutable() {
     assert_eq!(&*scheduler.batch[0], &[0, 1]);
     assert_eq!(scheduler.workloads.len(), 1);
     assert_eq!(scheduler.workloads.get("Systems"), Some(&(0..1)));
-    assert_eq!(scheduler.default, 0..1);
+    assert_eq!(scheduler.default.as_deref(), Some("Systems"));
 }
 #[test]
 fn multiple_mutable() {
@@ -500,7 +1003,8 @@ fn multiple_mutable() {
         .unwrap()
         .try_with_system((|world: &World| world.try_run(system2), system2))
         .unwrap()
-        .build();
+        .try_build()
+        .unwrap();
 
     let scheduler = scheduler.try_borrow_mut().unwrap();
     assert_eq!(scheduler.systems.len(), 2);
@@ -509,7 +1013,7 @@ fn multiple_mutable() {
     assert_eq!(&*scheduler.batch[1], &[1]);
     assert_eq!(scheduler.workloads.len(), 1);
     assert_eq!(scheduler.workloads.get("Systems"), Some(&(0..2)));
-    assert_eq!(scheduler.default, 0..2);
+    assert_eq!(scheduler.default.as_deref(), Some("Systems"));
 }
 #[test]
 fn multiple_mixed() {
@@ -534,7 +1038,8 @@ fn multiple_mixed() {
         .unwrap()
         .try_with_system((|world: &World| world.try_run(system2), system2))
         .unwrap()
-        .build();
+        .try_build()
+        .unwrap();
 
     let scheduler = scheduler.try_borrow_mut().unwrap();
     assert_eq!(scheduler.systems.len(), 2);
@@ -543,7 +1048,7 @@ fn multiple_mixed() {
     assert_eq!(&*scheduler.batch[1], &[1]);
     assert_eq!(scheduler.workloads.len(), 1);
     assert_eq!(scheduler.workloads.get("Systems"), Some(&(0..2)));
-    assert_eq!(scheduler.default, 0..2);
+    assert_eq!(scheduler.default.as_deref(), Some("Systems"));
 
     let scheduler = {
         #[cfg(feature = "std")]
@@ -560,7 +1065,8 @@ fn multiple_mixed() {
         .unwrap()
         .try_with_system((|world: &World| world.try_run(system1), system1))
         .unwrap()
-        .build();
+        .try_build()
+        .unwrap();
 
     let scheduler = scheduler.try_borrow_mut().unwrap();
     assert_eq!(scheduler.systems.len(), 2);
@@ -569,7 +1075,7 @@ fn multiple_mixed() {
     assert_eq!(&*scheduler.batch[1], &[1]);
     assert_eq!(scheduler.workloads.len(), 1);
     assert_eq!(scheduler.workloads.get("Systems"), Some(&(0..2)));
-    assert_eq!(scheduler.default, 0..2);
+    assert_eq!(scheduler.default.as_deref(), Some("Systems"));
 }
 #[test]
 fn all_storages() {
@@ -592,7 +1098,8 @@ fn all_storages() {
     WorkloadBuilder::new(scheduler.try_borrow_mut().unwrap(), "Systems".into())
         .try_with_system((|world: &World| world.try_run(system2), system2))
         .unwrap()
-        .build();
+        .try_build()
+        .unwrap();
 
     let scheduler = scheduler.try_borrow_mut().unwrap();
     assert_eq!(scheduler.systems.len(), 1);
@@ -600,7 +1107,7 @@ fn all_storages() {
     assert_eq!(&*scheduler.batch[0], &[0]);
     assert_eq!(scheduler.workloads.len(), 1);
     assert_eq!(scheduler.workloads.get("Systems"), Some(&(0..1)));
-    assert_eq!(scheduler.default, 0..1);
+    assert_eq!(scheduler.default.as_deref(), Some("Systems"));
 
     let scheduler = {
         #[cfg(feature = "std")]
@@ -617,7 +1124,8 @@ fn all_storages() {
         .unwrap()
         .try_with_system((|world: &World| world.try_run(system2), system2))
         .unwrap()
-        .build();
+        .try_build()
+        .unwrap();
 
     let scheduler = scheduler.try_borrow_mut().unwrap();
     assert_eq!(scheduler.systems.len(), 2);
@@ -626,7 +1134,7 @@ fn all_storages() {
     assert_eq!(&*scheduler.batch[0], &[0]);
     assert_eq!(scheduler.workloads.len(), 1);
     assert_eq!(scheduler.workloads.get("Systems"), Some(&(0..2)));
-    assert_eq!(scheduler.default, 0..2);
+    assert_eq!(scheduler.default.as_deref(), Some("Systems"));
 
     let scheduler = {
         #[cfg(feature = "std")]
@@ -643,7 +1151,8 @@ fn all_storages() {
         .unwrap()
         .try_with_system((|world: &World| world.try_run(system2), system2))
         .unwrap()
-        .build();
+        .try_build()
+        .unwrap();
 
     let scheduler = scheduler.try_borrow_mut().unwrap();
     assert_eq!(scheduler.systems.len(), 2);
@@ -652,7 +1161,7 @@ fn all_storages() {
     assert_eq!(&*scheduler.batch[1], &[1]);
     assert_eq!(scheduler.workloads.len(), 1);
     assert_eq!(scheduler.workloads.get("Systems"), Some(&(0..2)));
-    assert_eq!(scheduler.default, 0..2);
+    assert_eq!(scheduler.default.as_deref(), Some("Systems"));
 
     let scheduler = {
         #[cfg(feature = "std")]
@@ -669,7 +1178,8 @@ fn all_storages() {
         .unwrap()
         .try_with_system((|world: &World| world.try_run(system1), system1))
         .unwrap()
-        .build();
+        .try_build()
+        .unwrap();
 
     let scheduler = scheduler.try_borrow_mut().unwrap();
     assert_eq!(scheduler.systems.len(), 2);
@@ -678,7 +1188,7 @@ fn all_storages() {
     assert_eq!(&*scheduler.batch[1], &[1]);
     assert_eq!(scheduler.workloads.len(), 1);
     assert_eq!(scheduler.workloads.get("Systems"), Some(&(0..2)));
-    assert_eq!(scheduler.default, 0..2);
+    assert_eq!(scheduler.default.as_deref(), Some("Systems"));
 }
 
 #[cfg(feature = "non_send")]
@@ -710,7 +1220,8 @@ fn non_send() {
         .unwrap()
         .try_with_system((|world: &World| world.try_run(sys1), sys1))
         .unwrap()
-        .build();
+        .try_build()
+        .unwrap();
 
     let scheduler = scheduler.try_borrow_mut().unwrap();
     assert_eq!(scheduler.systems.len(), 2);
@@ -719,7 +1230,7 @@ fn non_send() {
     assert_eq!(&*scheduler.batch[1], &[1]);
     assert_eq!(scheduler.workloads.len(), 1);
     assert_eq!(scheduler.workloads.get("Test"), Some(&(0..2)));
-    assert_eq!(scheduler.default, 0..2);
+    assert_eq!(scheduler.default.as_deref(), Some("Test"));
 
     let scheduler = {
         #[cfg(feature = "std")]
@@ -736,7 +1247,8 @@ fn non_send() {
         .unwrap()
         .try_with_system((|world: &World| world.try_run(sys2), sys2))
         .unwrap()
-        .build();
+        .try_build()
+        .unwrap();
 
     let scheduler = scheduler.try_borrow_mut().unwrap();
     assert_eq!(scheduler.systems.len(), 2);
@@ -745,7 +1257,7 @@ fn non_send() {
     assert_eq!(&*scheduler.batch[1], &[1]);
     assert_eq!(scheduler.workloads.len(), 1);
     assert_eq!(scheduler.workloads.get("Test"), Some(&(0..2)));
-    assert_eq!(scheduler.default, 0..2);
+    assert_eq!(scheduler.default.as_deref(), Some("Test"));
 
     let scheduler = {
         #[cfg(feature = "std")]
@@ -762,7 +1274,8 @@ fn non_send() {
         .unwrap()
         .try_with_system((|world: &World| world.try_run(sys1), sys1))
         .unwrap()
-        .build();
+        .try_build()
+        .unwrap();
 
     let scheduler = scheduler.try_borrow_mut().unwrap();
     assert_eq!(scheduler.systems.len(), 2);
@@ -771,7 +1284,7 @@ fn non_send() {
     assert_eq!(&*scheduler.batch[1], &[1]);
     assert_eq!(scheduler.workloads.len(), 1);
     assert_eq!(scheduler.workloads.get("Test"), Some(&(0..2)));
-    assert_eq!(scheduler.default, 0..2);
+    assert_eq!(scheduler.default.as_deref(), Some("Test"));
 
     let scheduler = {
         #[cfg(feature = "std")]
@@ -788,7 +1301,8 @@ fn non_send() {
         .unwrap()
         .try_with_system((|world: &World| world.try_run(sys3), sys3))
         .unwrap()
-        .build();
+        .try_build()
+        .unwrap();
 
     let scheduler = scheduler.try_borrow_mut().unwrap();
     assert_eq!(scheduler.systems.len(), 2);
@@ -797,7 +1311,7 @@ fn non_send() {
     assert_eq!(&*scheduler.batch[1], &[1]);
     assert_eq!(scheduler.workloads.len(), 1);
     assert_eq!(scheduler.workloads.get("Test"), Some(&(0..2)));
-    assert_eq!(scheduler.default, 0..2);
+    assert_eq!(scheduler.default.as_deref(), Some("Test"));
 
     let scheduler = {
         #[cfg(feature = "std")]
@@ -814,7 +1328,8 @@ fn non_send() {
         .unwrap()
         .try_with_system((|world: &World| world.try_run(sys4), sys4))
         .unwrap()
-        .build();
+        .try_build()
+        .unwrap();
 
     let scheduler = scheduler.try_borrow_mut().unwrap();
     assert_eq!(scheduler.systems.len(), 2);
@@ -823,7 +1338,7 @@ fn non_send() {
     assert_eq!(&*scheduler.batch[1], &[1]);
     assert_eq!(scheduler.workloads.len(), 1);
     assert_eq!(scheduler.workloads.get("Test"), Some(&(0..2)));
-    assert_eq!(scheduler.default, 0..2);
+    assert_eq!(scheduler.default.as_deref(), Some("Test"));
 }
 
 #[test]
@@ -853,7 +1368,8 @@ fn fake_borrow() {
         .unwrap()
         .try_with_system((|world: &World| world.try_run(system1), system1))
         .unwrap()
-        .build();
+        .try_build()
+        .unwrap();
 
     let scheduler = scheduler.try_borrow_mut().unwrap();
     assert_eq!(scheduler.systems.len(), 3);
@@ -863,5 +1379,197 @@ fn fake_borrow() {
     assert_eq!(&*scheduler.batch[2], &[2]);
     assert_eq!(scheduler.workloads.len(), 1);
     assert_eq!(scheduler.workloads.get("Systems"), Some(&(0..3)));
-    assert_eq!(scheduler.default, 0..3);
+    assert_eq!(scheduler.default.as_deref(), Some("Systems"));
+}
+
+#[test]
+fn ordering() {
+    use crate::atomic_refcell::AtomicRefCell;
+    use crate::{View, World};
+
+    fn system1(_: View<'_, usize>) {}
+    fn system2(_: View<'_, usize>) {}
+    fn system3(_: View<'_, usize>) {}
+
+    let scheduler = {
+        #[cfg(feature = "std")]
+        {
+            AtomicRefCell::new(Scheduler::default(), None, true)
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            AtomicRefCell::new(Scheduler::default())
+        }
+    };
+    WorkloadBuilder::new(scheduler.try_borrow_mut().unwrap(), "Systems".into())
+        .try_with_system((|world: &World| world.try_run(system1), system1))
+        .unwrap()
+        .after("system2")
+        .try_with_system((|world: &World| world.try_run(system2), system2))
+        .unwrap()
+        .label("last")
+        .try_with_system((|world: &World| world.try_run(system3), system3))
+        .unwrap()
+        .before("last")
+        .try_build()
+        .unwrap();
+
+    let scheduler = scheduler.try_borrow_mut().unwrap();
+    assert_eq!(scheduler.systems.len(), 3);
+    assert_eq!(scheduler.batch.len(), 3);
+    assert_eq!(&*scheduler.batch[0], &[0]);
+    assert_eq!(&*scheduler.batch[1], &[1]);
+    assert_eq!(&*scheduler.batch[2], &[2]);
+    assert!(scheduler.system_names[0].ends_with("system3"));
+    assert!(scheduler.system_names[1].ends_with("system2"));
+    assert!(scheduler.system_names[2].ends_with("system1"));
+    assert_eq!(scheduler.workloads.get("Systems"), Some(&(0..3)));
+}
+
+#[test]
+fn ordering_cycle() {
+    use crate::atomic_refcell::AtomicRefCell;
+    use crate::{View, World};
+
+    fn system1(_: View<'_, usize>) {}
+    fn system2(_: View<'_, usize>) {}
+    fn system3(_: View<'_, usize>) {}
+
+    let scheduler = {
+        #[cfg(feature = "std")]
+        {
+            AtomicRefCell::new(Scheduler::default(), None, true)
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            AtomicRefCell::new(Scheduler::default())
+        }
+    };
+    let result = WorkloadBuilder::new(scheduler.try_borrow_mut().unwrap(), "Systems".into())
+        .try_with_system((|world: &World| world.try_run(system1), system1))
+        .unwrap()
+        .try_with_system((|world: &World| world.try_run(system2), system2))
+        .unwrap()
+        .after("system3")
+        .try_with_system((|world: &World| world.try_run(system3), system3))
+        .unwrap()
+        .after("system2")
+        .try_build();
+
+    match result {
+        Err(error::Build::Cycle(names)) => {
+            assert_eq!(names.len(), 2);
+            assert!(names[0].ends_with("system2"));
+            assert!(names[1].ends_with("system3"));
+        }
+        Ok(_) => panic!(),
+    }
+
+    let scheduler = scheduler.try_borrow_mut().unwrap();
+    assert_eq!(scheduler.systems.len(), 0);
+    assert!(scheduler.workloads.is_empty());
+}
+
+#[test]
+fn with_workload() {
+    use crate::atomic_refcell::AtomicRefCell;
+    use crate::{View, ViewMut, World};
+
+    fn system1(_: View<'_, usize>) {}
+    fn system2(_: ViewMut<'_, usize>) {}
+    fn system3(_: View<'_, u32>) {}
+
+    let scheduler = {
+        #[cfg(feature = "std")]
+        {
+            AtomicRefCell::new(Scheduler::default(), None, true)
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            AtomicRefCell::new(Scheduler::default())
+        }
+    };
+    WorkloadBuilder::new(scheduler.try_borrow_mut().unwrap(), "Inner".into())
+        .try_with_system((|world: &World| world.try_run(system1), system1))
+        .unwrap()
+        .try_with_system((|world: &World| world.try_run(system2), system2))
+        .unwrap()
+        .try_build()
+        .unwrap();
+    WorkloadBuilder::new(scheduler.try_borrow_mut().unwrap(), "Outer".into())
+        .try_with_system((|world: &World| world.try_run(system3), system3))
+        .unwrap()
+        .try_with_workload("Inner")
+        .unwrap()
+        .try_build()
+        .unwrap();
+
+    let scheduler = scheduler.try_borrow_mut().unwrap();
+    assert_eq!(scheduler.systems.len(), 3);
+    assert_eq!(scheduler.batch.len(), 4);
+    assert_eq!(&*scheduler.batch[0], &[0]);
+    assert_eq!(&*scheduler.batch[1], &[1]);
+    assert_eq!(&*scheduler.batch[2], &[2, 0]);
+    assert_eq!(&*scheduler.batch[3], &[1]);
+    assert_eq!(scheduler.workloads.get("Inner"), Some(&(0..2)));
+    assert_eq!(scheduler.workloads.get("Outer"), Some(&(2..4)));
+    assert_eq!(scheduler.default.as_deref(), Some("Inner"));
+
+    assert_eq!(
+        WorkloadBuilder::new(scheduler, "Missing".into())
+            .try_with_workload("Missing")
+            .err(),
+        Some(error::WithWorkload::MissingWorkload)
+    );
+}
+
+#[test]
+fn remove_workload() {
+    use crate::atomic_refcell::AtomicRefCell;
+    use crate::{View, ViewMut, World};
+
+    fn system1(_: View<'_, usize>) {}
+    fn system2(_: ViewMut<'_, usize>) {}
+    fn system3(_: View<'_, u32>) {}
+    fn run_system2(world: &World) -> Result<(), error::Run> {
+        world.try_run(system2)
+    }
+
+    let scheduler = {
+        #[cfg(feature = "std")]
+        {
+            AtomicRefCell::new(Scheduler::default(), None, true)
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            AtomicRefCell::new(Scheduler::default())
+        }
+    };
+    WorkloadBuilder::new(scheduler.try_borrow_mut().unwrap(), "First".into())
+        .try_with_system((|world: &World| world.try_run(system1), system1))
+        .unwrap()
+        .try_with_system((run_system2, system2))
+        .unwrap()
+        .try_build()
+        .unwrap();
+    WorkloadBuilder::new(scheduler.try_borrow_mut().unwrap(), "Second".into())
+        .try_with_system((|world: &World| world.try_run(system3), system3))
+        .unwrap()
+        .try_with_system((run_system2, system2))
+        .unwrap()
+        .try_build()
+        .unwrap();
+
+    let mut scheduler = scheduler.try_borrow_mut().unwrap();
+    assert!(scheduler.remove_workload("First"));
+    assert!(!scheduler.remove_workload("First"));
+
+    assert_eq!(scheduler.systems.len(), 2);
+    assert_eq!(scheduler.lookup_table.len(), 2);
+    assert!(scheduler.system_names[0].ends_with("system2"));
+    assert!(scheduler.system_names[1].ends_with("system3"));
+    assert_eq!(scheduler.batch.len(), 1);
+    assert_eq!(&*scheduler.batch[0], &[1, 0]);
+    assert_eq!(scheduler.workloads.get("Second"), Some(&(0..1)));
+    assert_eq!(scheduler.default, None);
 }
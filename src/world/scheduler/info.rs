@@ -0,0 +1,235 @@
+use super::Scheduler;
+use crate::borrow::Mutation;
+use crate::storage::AllStorages;
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::any::TypeId;
+use core::fmt::Write;
+use core::ops::Range;
+
+/// Batches of a workload, as built by the scheduler.
+#[derive(Clone, Debug)]
+pub struct WorkloadInfo {
+    name: Cow<'static, str>,
+    batches: Vec<Vec<SystemInfo>>,
+}
+
+impl WorkloadInfo {
+    /// Returns the name of the workload.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// Returns the systems of each batch, in the order the batches run.
+    /// Systems of the same batch run in parallel.
+    pub fn batches(&self) -> &[Vec<SystemInfo>] {
+        &self.batches
+    }
+    /// Returns the workload in Graphviz's dot format.
+    /// Each batch is a cluster and edges link systems to the system of the previous batch they conflict with.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+
+        // writing to a String can't fail
+        writeln!(dot, "digraph \"{}\" {{", escape(&self.name)).unwrap();
+        writeln!(dot, "    rankdir = LR;").unwrap();
+        writeln!(dot, "    node [shape = box];").unwrap();
+
+        for (batch_index, batch) in self.batches.iter().enumerate() {
+            writeln!(dot, "    subgraph cluster_{} {{", batch_index).unwrap();
+            writeln!(dot, "        label = \"Batch {}\";", batch_index).unwrap();
+            for (system_index, system) in batch.iter().enumerate() {
+                write!(
+                    dot,
+                    "        s{}_{} [label = \"{}\"",
+                    batch_index,
+                    system_index,
+                    escape(system.name)
+                )
+                .unwrap();
+                if !system.enabled {
+                    dot.push_str(", style = dashed");
+                }
+                dot.push_str("];\n");
+            }
+            dot.push_str("    }\n");
+        }
+
+        for (batch_index, batch) in self.batches.iter().enumerate() {
+            for (system_index, system) in batch.iter().enumerate() {
+                if let Some(conflict) = &system.conflict {
+                    writeln!(
+                        dot,
+                        "    s{}_{} -> s{}_{} [label = \"{:?} / {:?} {}\"];",
+                        batch_index - 1,
+                        conflict.system_index,
+                        batch_index,
+                        system_index,
+                        conflict.other_mutation,
+                        conflict.mutation,
+                        escape(conflict.type_name)
+                    )
+                    .unwrap();
+                }
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// A system as seen by the scheduler.
+#[derive(Clone, Debug)]
+pub struct SystemInfo {
+    name: &'static str,
+    borrows: Vec<(TypeId, &'static str, Mutation)>,
+    is_send_sync: bool,
+    enabled: bool,
+    conflict: Option<Conflict>,
+}
+
+impl SystemInfo {
+    /// Returns the name of the system.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+    /// Returns the storages borrowed by the system, with the name of their type.
+    pub fn borrows(&self) -> &[(TypeId, &'static str, Mutation)] {
+        &self.borrows
+    }
+    /// Returns `false` if the system has to run on the thread that owns the `World`.
+    pub fn is_send_sync(&self) -> bool {
+        self.is_send_sync
+    }
+    /// Returns `false` if the system was disabled.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+    /// Returns the conflict that prevents the system from running in the previous batch.
+    /// Systems of the first batch, systems that aren't `Send + Sync` and systems moved by an ordering constraint don't have one.
+    pub fn conflict(&self) -> Option<&Conflict> {
+        self.conflict.as_ref()
+    }
+}
+
+/// Borrow that forced a system into a later batch than a system of the previous batch.
+#[derive(Clone, Debug)]
+pub struct Conflict {
+    system: &'static str,
+    system_index: usize,
+    type_id: TypeId,
+    type_name: &'static str,
+    mutation: Mutation,
+    other_mutation: Mutation,
+}
+
+impl Conflict {
+    /// Returns the name of the system of the previous batch.
+    pub fn system(&self) -> &'static str {
+        self.system
+    }
+    /// Returns the storage both systems borrow.
+    /// Borrowing `AllStorages` conflicts with any other borrow.
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+    /// Returns the name of the type of the storage both systems borrow.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+    /// Returns how the system borrows the storage.
+    pub fn mutation(&self) -> Mutation {
+        self.mutation
+    }
+    /// Returns how the system of the previous batch borrows the storage.
+    pub fn other_mutation(&self) -> Mutation {
+        self.other_mutation
+    }
+}
+
+impl Scheduler {
+    pub(crate) fn workload_info(
+        &self,
+        name: Cow<'static, str>,
+        workload: Range<usize>,
+    ) -> WorkloadInfo {
+        let mut batches: Vec<Vec<SystemInfo>> = Vec::with_capacity(workload.len());
+
+        for batch in &self.batch[workload] {
+            let previous = batches.last();
+            let batch = batch
+                .iter()
+                .map(|&index| {
+                    let (borrow, is_send_sync) = &self.borrow_info[index];
+
+                    SystemInfo {
+                        name: self.system_names[index],
+                        borrows: borrow.to_vec(),
+                        is_send_sync: *is_send_sync,
+                        enabled: self.enabled[index],
+                        conflict: previous.and_then(|previous| {
+                            if !is_send_sync {
+                                return None;
+                            }
+
+                            previous
+                                .iter()
+                                .enumerate()
+                                .filter(|(_, other)| other.is_send_sync)
+                                .find_map(|(system_index, other)| {
+                                    conflict(borrow, &other.borrows).map(
+                                        |(type_id, type_name, mutation, other_mutation)| Conflict {
+                                            system: other.name,
+                                            system_index,
+                                            type_id,
+                                            type_name,
+                                            mutation,
+                                            other_mutation,
+                                        },
+                                    )
+                                })
+                        }),
+                    }
+                })
+                .collect();
+
+            batches.push(batch);
+        }
+
+        WorkloadInfo { name, batches }
+    }
+}
+
+/// Returns the first borrow of `borrow` that can't happen at the same time as `other`.
+fn conflict(
+    borrow: &[(TypeId, &'static str, Mutation)],
+    other: &[(TypeId, &'static str, Mutation)],
+) -> Option<(TypeId, &'static str, Mutation, Mutation)> {
+    for &(type_id, type_name, mutation) in borrow {
+        for &(other_type_id, _, other_mutation) in other {
+            if type_id == TypeId::of::<AllStorages>()
+                || other_type_id == TypeId::of::<AllStorages>()
+            {
+                return Some((
+                    TypeId::of::<AllStorages>(),
+                    core::any::type_name::<AllStorages>(),
+                    mutation,
+                    other_mutation,
+                ));
+            }
+            if type_id == other_type_id
+                && (mutation == Mutation::Unique || other_mutation == Mutation::Unique)
+            {
+                return Some((type_id, type_name, mutation, other_mutation));
+            }
+        }
+    }
+
+    None
+}
+
+/// Escapes `name` to be used in a double quoted string of the dot or trace event format.
+pub(super) fn escape(name: &str) -> String {
+    name.replace('\\', "\\\\").replace('"', "\\\"")
+}
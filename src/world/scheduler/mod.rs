@@ -1,25 +1,74 @@
 mod builder;
+mod info;
+#[cfg(feature = "std")]
+mod profiler;
 
 pub use builder::WorkloadBuilder;
+pub use info::{Conflict, SystemInfo, WorkloadInfo};
+#[cfg(feature = "std")]
+pub(super) use profiler::enter as enter_profiler;
+#[cfg(feature = "std")]
+pub use profiler::{Profiler, SystemTiming, TimingProfiler};
 
+use crate::borrow::{Locals, Mutation};
 use crate::error;
+use crate::storage::AllStorages;
 use crate::World;
 use alloc::borrow::Cow;
 use alloc::boxed::Box;
+use alloc::sync::Arc;
+use alloc::vec;
 use alloc::vec::Vec;
 use core::any::TypeId;
 use core::ops::Range;
+use core::time::Duration;
 use hashbrown::HashMap;
 
+/// What a workload does when one of its systems returns an error.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ErrorPolicy {
+    /// Returns the error right away, the following batches don't run.
+    Stop,
+    /// Keeps running all following systems and returns all errors at the end.
+    Continue,
+    /// Keeps running the following systems, except the ones borrowing a storage a failed system borrowed mutably.  
+    /// All errors are returned at the end.
+    SkipDependents,
+}
+
+pub(super) type Condition = Arc<dyn Fn(&World) -> bool + Send + Sync + 'static>;
+
 #[allow(clippy::type_complexity)]
 pub(crate) struct Scheduler {
     pub(super) systems: Vec<Box<dyn Fn(&World) -> Result<(), error::Run> + Send + Sync + 'static>>,
     pub(super) system_names: Vec<&'static str>,
     pub(super) lookup_table: HashMap<TypeId, usize>,
+    // borrows of each system and if it can run on any thread, used to re-batch included workloads
+    pub(super) borrow_info: Vec<(Box<[(TypeId, &'static str, Mutation)]>, bool)>,
+    // disabled systems don't run, whatever workload they're part of
+    pub(super) enabled: Vec<bool>,
+    // state of each system's Local borrows
+    pub(super) locals: Vec<Locals>,
     // a batch lists systems that can run in parallel
     pub(super) batch: Vec<Box<[usize]>>,
+    // run condition of each system in batch, same layout as batch
+    pub(super) conditions: Vec<Box<[Option<Condition>]>>,
+    pub(super) workload_conditions: HashMap<Cow<'static, str>, Condition>,
+    // only workloads that don't stop at the first error are listed
+    pub(super) error_policies: HashMap<Cow<'static, str>, ErrorPolicy>,
     pub(super) workloads: HashMap<Cow<'static, str>, Range<usize>>,
-    pub(super) default: Range<usize>,
+    // name of the default workload, if any
+    pub(super) default: Option<Cow<'static, str>>,
+    // time left for run_workload_fixed
+    pub(super) accumulators: HashMap<Cow<'static, str>, Duration>,
+    // calls to run_workload_every, modulo its rate
+    pub(super) calls: HashMap<Cow<'static, str>, u32>,
+    // Events storages to update after each workload run
+    pub(super) events: Vec<(TypeId, fn(&AllStorages) -> Result<(), error::GetStorage>)>,
+    #[cfg(feature = "std")]
+    pub(super) profiler: Option<Arc<dyn Profiler>>,
+    #[cfg(feature = "std")]
+    pub(super) catch_panics: bool,
 }
 
 impl Default for Scheduler {
@@ -28,9 +77,143 @@ impl Default for Scheduler {
             systems: Vec::new(),
             system_names: Vec::new(),
             lookup_table: HashMap::new(),
+            borrow_info: Vec::new(),
+            enabled: Vec::new(),
+            locals: Vec::new(),
             batch: Vec::new(),
+            conditions: Vec::new(),
+            workload_conditions: HashMap::new(),
+            error_policies: HashMap::new(),
             workloads: HashMap::new(),
-            default: 0..0,
+            default: None,
+            accumulators: HashMap::new(),
+            calls: HashMap::new(),
+            events: Vec::new(),
+            #[cfg(feature = "std")]
+            profiler: None,
+            #[cfg(feature = "std")]
+            catch_panics: false,
+        }
+    }
+}
+
+impl Scheduler {
+    /// Removes the `name` workload and the systems no other workload uses.  
+    /// Returns `false` if there is no workload with this name.
+    pub(super) fn remove_workload(&mut self, name: &str) -> bool {
+        if self.remove_batches(name) {
+            self.reclaim_systems();
+
+            true
+        } else {
+            false
         }
     }
+    /// Removes the `name` workload's batches, its systems are kept until `reclaim_systems` is called.  
+    /// Returns `false` if there is no workload with this name.
+    pub(super) fn remove_batches(&mut self, name: &str) -> bool {
+        let removed = match self.workloads.remove(name) {
+            Some(removed) => removed,
+            None => return false,
+        };
+        let len = removed.end - removed.start;
+        self.accumulators.remove(name);
+        self.calls.remove(name);
+        self.workload_conditions.remove(name);
+        self.error_policies.remove(name);
+
+        self.batch.drain(removed.clone());
+        self.conditions.drain(removed.clone());
+
+        for workload in self.workloads.values_mut() {
+            if workload.start >= removed.end && len > 0 {
+                workload.start -= len;
+                workload.end -= len;
+            }
+        }
+        if self.default.as_deref() == Some(name) {
+            self.default = None;
+        }
+
+        true
+    }
+    pub(super) fn error_policy(&self, workload: &str) -> ErrorPolicy {
+        self.error_policies
+            .get(workload)
+            .copied()
+            .unwrap_or(ErrorPolicy::Stop)
+    }
+    /// Removes the systems not present in any batch and updates the indices of the others.
+    pub(super) fn reclaim_systems(&mut self) {
+        let mut used = vec![false; self.systems.len()];
+        for batch in &self.batch {
+            for &index in batch.iter() {
+                used[index] = true;
+            }
+        }
+
+        // new index of each system still in use
+        let mut new_indices = vec![0; used.len()];
+        let mut len = 0;
+        for (index, &used) in used.iter().enumerate() {
+            if used {
+                new_indices[index] = len;
+                len += 1;
+            }
+        }
+
+        if len == used.len() {
+            return;
+        }
+
+        let mut index = 0;
+        self.systems.retain(|_| {
+            index += 1;
+            used[index - 1]
+        });
+        let mut index = 0;
+        self.system_names.retain(|_| {
+            index += 1;
+            used[index - 1]
+        });
+        let mut index = 0;
+        self.borrow_info.retain(|_| {
+            index += 1;
+            used[index - 1]
+        });
+        let mut index = 0;
+        self.enabled.retain(|_| {
+            index += 1;
+            used[index - 1]
+        });
+        let mut index = 0;
+        self.locals.retain(|_| {
+            index += 1;
+            used[index - 1]
+        });
+        self.lookup_table.retain(|_, index| {
+            let is_used = used[*index];
+            *index = new_indices[*index];
+            is_used
+        });
+        for batch in &mut self.batch {
+            for index in batch.iter_mut() {
+                *index = new_indices[*index];
+            }
+        }
+    }
+    /// Enables or disables all systems named `name` or whose function is named `name`.  
+    /// Returns `false` if no system matches.
+    pub(super) fn set_enabled(&mut self, name: &str, enabled: bool) -> bool {
+        let mut found = false;
+
+        for (index, system_name) in self.system_names.iter().enumerate() {
+            if *system_name == name || system_name.rsplit("::").next() == Some(name) {
+                self.enabled[index] = enabled;
+                found = true;
+            }
+        }
+
+        found
+    }
 }
@@ -0,0 +1,259 @@
+use super::info::escape;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+use core::time::Duration;
+use hashbrown::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+use std::thread::ThreadId;
+use std::time::Instant;
+
+/// Hooks called by the scheduler when running a workload.
+///
+/// All methods do nothing by default.
+/// Systems of the same batch can run in parallel, the hooks have to handle being called from multiple threads at once.
+pub trait Profiler: Send + Sync {
+    /// Called before the systems of a batch start running.
+    /// `batch` is the index of the batch in its workload.
+    fn batch_start(&self, _batch: usize) {}
+    /// Called after all systems of a batch ran.
+    /// `batch` is the index of the batch in its workload.
+    fn batch_end(&self, _batch: usize) {}
+    /// Called on the system's thread right before it runs.
+    fn system_start(&self, _name: &'static str, _thread: ThreadId) {}
+    /// Called on the system's thread right after it ran, even if it returned an error or panicked.
+    fn system_end(&self, _name: &'static str, _thread: ThreadId) {}
+}
+
+/// Duration statistics of a system.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SystemTiming {
+    name: &'static str,
+    count: u32,
+    min: Duration,
+    max: Duration,
+    total: Duration,
+}
+
+impl SystemTiming {
+    /// Returns the name of the system.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+    /// Returns how many times the system ran.
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+    /// Returns the shortest run.
+    pub fn min(&self) -> Duration {
+        self.min
+    }
+    /// Returns the average duration of a run.
+    pub fn avg(&self) -> Duration {
+        self.total / self.count
+    }
+    /// Returns the longest run.
+    pub fn max(&self) -> Duration {
+        self.max
+    }
+    /// Returns the time spent in all runs.
+    pub fn total(&self) -> Duration {
+        self.total
+    }
+}
+
+/// `Profiler` aggregating the duration of each system.
+///
+/// Only the min, average and max durations are kept by default.
+/// Individual runs are recorded between `start_trace` and `stop_trace` to export them as a Chrome trace.
+///
+/// ### Example
+/// ```
+/// use shipyard::{system, TimingProfiler, View, World};
+/// use std::sync::Arc;
+///
+/// fn physics(_: View<u32>) {}
+///
+/// let world = World::new();
+/// let profiler = Arc::new(TimingProfiler::new());
+/// world.set_profiler(profiler.clone());
+///
+/// world
+///     .add_workload("Frame")
+///     .with_system(system!(physics))
+///     .build();
+///
+/// world.run_default();
+///
+/// profiler.start_trace();
+/// world.run_default();
+/// profiler.stop_trace();
+///
+/// let timings = profiler.timings();
+/// assert_eq!(timings.len(), 1);
+/// assert_eq!(timings[0].count(), 2);
+///
+/// let trace = profiler.chrome_trace();
+/// assert_eq!(trace.matches("physics").count(), 1);
+/// ```
+pub struct TimingProfiler {
+    origin: Instant,
+    state: Mutex<TimingState>,
+}
+
+#[derive(Default)]
+struct TimingState {
+    // start of the systems currently running
+    running: HashMap<(&'static str, ThreadId), Instant>,
+    timings: Vec<SystemTiming>,
+    tracing: bool,
+    // name, thread index, start and duration of each run recorded while tracing
+    events: Vec<(&'static str, usize, Duration, Duration)>,
+    threads: Vec<ThreadId>,
+}
+
+impl Default for TimingProfiler {
+    fn default() -> Self {
+        TimingProfiler::new()
+    }
+}
+
+impl TimingProfiler {
+    /// Creates an empty `TimingProfiler`.
+    pub fn new() -> Self {
+        TimingProfiler {
+            origin: Instant::now(),
+            state: Mutex::new(TimingState::default()),
+        }
+    }
+    /// Returns the statistics of each system, in the order they first finished.
+    pub fn timings(&self) -> Vec<SystemTiming> {
+        self.state.lock().unwrap().timings.clone()
+    }
+    /// Removes all timings and recorded runs.
+    pub fn clear(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.timings.clear();
+        state.events.clear();
+    }
+    /// Starts recording each run, previously recorded runs are removed.
+    pub fn start_trace(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.tracing = true;
+        state.events.clear();
+    }
+    /// Stops recording runs, the ones already recorded are kept.
+    pub fn stop_trace(&self) {
+        self.state.lock().unwrap().tracing = false;
+    }
+    /// Returns `true` if runs are being recorded.
+    pub fn is_tracing(&self) -> bool {
+        self.state.lock().unwrap().tracing
+    }
+    /// Returns all recorded runs in Chrome's trace event format.
+    /// It can be opened in `chrome://tracing` or Perfetto.
+    pub fn chrome_trace(&self) -> String {
+        let state = self.state.lock().unwrap();
+        let mut trace = String::from("[");
+
+        for (index, (name, thread, start, duration)) in state.events.iter().enumerate() {
+            if index > 0 {
+                trace.push(',');
+            }
+            // writing to a String can't fail
+            write!(
+                trace,
+                "\n{{\"name\":\"{}\",\"ph\":\"X\",\"pid\":0,\"tid\":{},\"ts\":{},\"dur\":{}}}",
+                escape(name),
+                thread,
+                start.as_micros(),
+                duration.as_micros()
+            )
+            .unwrap();
+        }
+
+        trace.push_str("\n]\n");
+        trace
+    }
+    /// Writes all recorded runs in Chrome's trace event format to `path`.
+    pub fn write_chrome_trace(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.chrome_trace())
+    }
+}
+
+impl Profiler for TimingProfiler {
+    fn system_start(&self, name: &'static str, thread: ThreadId) {
+        let now = Instant::now();
+        self.state
+            .lock()
+            .unwrap()
+            .running
+            .insert((name, thread), now);
+    }
+    fn system_end(&self, name: &'static str, thread: ThreadId) {
+        let end = Instant::now();
+        let mut state = self.state.lock().unwrap();
+
+        let start = match state.running.remove(&(name, thread)) {
+            Some(start) => start,
+            None => return,
+        };
+        let duration = end - start;
+
+        if let Some(timing) = state.timings.iter_mut().find(|timing| timing.name == name) {
+            timing.count += 1;
+            timing.min = timing.min.min(duration);
+            timing.max = timing.max.max(duration);
+            timing.total += duration;
+        } else {
+            state.timings.push(SystemTiming {
+                name,
+                count: 1,
+                min: duration,
+                max: duration,
+                total: duration,
+            });
+        }
+
+        if !state.tracing {
+            return;
+        }
+
+        let thread_index = match state.threads.iter().position(|&other| other == thread) {
+            Some(index) => index,
+            None => {
+                state.threads.push(thread);
+                state.threads.len() - 1
+            }
+        };
+        let origin = self.origin;
+        state
+            .events
+            .push((name, thread_index, start - origin, duration));
+    }
+}
+
+/// Calls `system_start` and `system_end` when the guard is dropped, even when unwinding.
+pub(crate) fn enter<'a>(profiler: &'a dyn Profiler, name: &'static str) -> ProfilerGuard<'a> {
+    let thread = std::thread::current().id();
+    profiler.system_start(name, thread);
+
+    ProfilerGuard {
+        profiler,
+        name,
+        thread,
+    }
+}
+
+pub(crate) struct ProfilerGuard<'a> {
+    profiler: &'a dyn Profiler,
+    name: &'static str,
+    thread: ThreadId,
+}
+
+impl Drop for ProfilerGuard<'_> {
+    fn drop(&mut self) {
+        self.profiler.system_end(self.name, self.thread);
+    }
+}
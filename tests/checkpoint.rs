@@ -0,0 +1,201 @@
+use shipyard::error;
+use shipyard::*;
+
+#[test]
+fn restore() {
+    let world = World::new();
+    world.register_clone::<String>();
+    world.register_clone::<u32>();
+    world.register_unique_clone::<Vec<u32>>();
+
+    world.add_unique(vec![0u32]);
+    let (entity0, entity1) = world.run(
+        |mut entities: EntitiesViewMut, mut strings: ViewMut<String>, mut u32s: ViewMut<u32>| {
+            (
+                entities.add_entity(&mut strings, String::from("0")),
+                entities.add_entity((&mut strings, &mut u32s), (String::from("1"), 1)),
+            )
+        },
+    );
+    world.run(|mut all_storages: AllStoragesViewMut| {
+        assert!(all_storages.delete(entity0));
+    });
+
+    let checkpoint = world.checkpoint();
+
+    for _ in 0..2 {
+        let (entity2, entity3) = world.run(
+            |mut entities: EntitiesViewMut,
+             mut strings: ViewMut<String>,
+             mut u32s: ViewMut<u32>,
+             mut i8s: ViewMut<i8>| {
+                strings[entity1].push('1');
+                u32s.remove(entity1);
+                (
+                    entities.add_entity(&mut strings, String::from("2")),
+                    entities.add_entity((&mut u32s, &mut i8s), (3, 3)),
+                )
+            },
+        );
+        world.borrow::<UniqueViewMut<Vec<u32>>>().push(1);
+
+        world.restore(&checkpoint);
+
+        world.run(
+            |entities: EntitiesView, strings: View<String>, u32s: View<u32>, i8s: View<i8>| {
+                assert!(!entities.is_alive(entity0));
+                assert!(entities.is_alive(entity1));
+                assert!(!entities.is_alive(entity2));
+                assert!(!entities.is_alive(entity3));
+                assert_eq!(strings.len(), 1);
+                assert_eq!(strings[entity1], "1");
+                assert_eq!(u32s.len(), 1);
+                assert_eq!(u32s[entity1], 1);
+                assert_eq!(i8s.len(), 0);
+            },
+        );
+        assert_eq!(*world.borrow::<UniqueView<Vec<u32>>>(), &[0]);
+
+        // the free list is restored
+        let new_entity = world.borrow::<EntitiesViewMut>().add_entity((), ());
+        assert_eq!(new_entity, entity2);
+        world.restore(&checkpoint);
+    }
+}
+
+#[test]
+fn update_pack() {
+    let world = World::new();
+    world.register_clone::<usize>();
+
+    let (entity0, entity1) = world.run(
+        |mut entities: EntitiesViewMut, mut usizes: ViewMut<usize>| {
+            usizes.update_pack();
+            let entity0 = entities.add_entity(&mut usizes, 0);
+            let entity1 = entities.add_entity(&mut usizes, 1);
+            usizes.clear_inserted();
+            usizes[entity1] = 10;
+            usizes.delete(entity0);
+            (entity0, entity1)
+        },
+    );
+
+    let checkpoint = world.checkpoint();
+    world.run(|mut usizes: ViewMut<usize>| {
+        usizes.clear_modified();
+        usizes.take_deleted();
+    });
+    world.restore(&checkpoint);
+
+    world.run(|usizes: View<usize>| {
+        assert_eq!(usizes.len(), 1);
+        assert_eq!(usizes.inserted().len(), 0);
+        assert_eq!(usizes.modified().len(), 1);
+        assert_eq!(usizes.deleted(), &[(entity0, 0)]);
+        assert_eq!(usizes[entity1], 10);
+    });
+}
+
+#[test]
+fn removal_tracking() {
+    let world = World::new();
+    world.register_clone::<usize>();
+
+    let entity = world.run(
+        |mut entities: EntitiesViewMut, mut usizes: ViewMut<usize>| {
+            usizes.track_removal();
+            entities.add_entity(&mut usizes, 0)
+        },
+    );
+
+    let checkpoint = world.checkpoint();
+    world.run(|mut usizes: ViewMut<usize>| {
+        usizes.delete(entity);
+    });
+    world.restore(&checkpoint);
+
+    world.run(|usizes: View<usize>| {
+        assert_eq!(usizes[entity], 0);
+        assert!(usizes.removed().is_empty());
+        assert!(usizes.deleted().is_empty());
+    });
+}
+
+#[test]
+fn loose_pack() {
+    let world = World::new();
+    world.register_clone::<u32>();
+    world.register_clone::<usize>();
+
+    world.run(|mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>| {
+        LoosePack::<(u32,)>::loose_pack((&mut u32s, &mut usizes));
+    });
+    let entity = world.run(
+        |mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>| {
+            entities.add_entity(&mut u32s, 0);
+            entities.add_entity((&mut u32s, &mut usizes), (1, 10))
+        },
+    );
+
+    let checkpoint = world.checkpoint();
+    world.run(|mut all_storages: AllStoragesViewMut| {
+        all_storages.delete(entity);
+    });
+    world.restore(&checkpoint);
+
+    world.run(|u32s: View<u32>, usizes: View<usize>| {
+        let mut iter = (&u32s, &usizes).iter();
+        assert_eq!(iter.next(), Some((&1, &10)));
+        assert_eq!(iter.next(), None);
+    });
+}
+
+#[test]
+fn partially_registered_tight_pack() {
+    let world = World::new();
+    world.register_clone::<u32>();
+
+    world.run(|mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>| {
+        (&mut u32s, &mut usizes).tight_pack();
+    });
+    let entity0 = world.run(
+        |mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>| {
+            entities.add_entity((&mut u32s, &mut usizes), (0, 10))
+        },
+    );
+
+    let checkpoint = world.checkpoint();
+    world.restore(&checkpoint);
+
+    world.run(
+        |mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>| {
+            assert_eq!(u32s.get(entity0), Ok(&0));
+            assert_eq!((&u32s, &usizes).iter().count(), 0);
+
+            let entity1 = entities.add_entity((&mut u32s, &mut usizes), (1, 99));
+
+            let mut iter = (&u32s, &usizes).iter().with_id();
+            assert_eq!(iter.next(), Some((entity1, (&1, &99))));
+            assert_eq!(iter.next(), None);
+        },
+    );
+}
+
+#[test]
+fn register_twice() {
+    let world = World::new();
+    world.register_clone::<u32>();
+
+    assert_eq!(
+        world.try_register_clone::<u32>().err(),
+        Some(error::Register::TypeAlreadyRegistered(
+            core::any::type_name::<u32>()
+        ))
+    );
+    assert_eq!(
+        world.try_register_delta::<u32>().err(),
+        Some(error::Register::TypeAlreadyRegistered(
+            core::any::type_name::<u32>()
+        ))
+    );
+}
@@ -0,0 +1,137 @@
+use core::any::type_name;
+use shipyard::error;
+use shipyard::*;
+
+#[test]
+fn clone_entity() {
+    let world = World::new();
+    world.register_clone::<String>();
+    world.register_clone::<u32>();
+
+    let entity = world.run(
+        |mut entities: EntitiesViewMut, mut strings: ViewMut<String>, mut u32s: ViewMut<u32>| {
+            entities.add_entity((&mut strings, &mut u32s), (String::from("0"), 0))
+        },
+    );
+
+    let (clone, skipped) =
+        world.run(|mut all_storages: AllStoragesViewMut| all_storages.clone_entity(entity));
+    assert_ne!(clone, entity);
+    assert!(skipped.is_empty());
+
+    world.run(|mut strings: ViewMut<String>, u32s: View<u32>| {
+        strings[clone].push('1');
+        assert_eq!(strings[entity], "0");
+        assert_eq!(strings[clone], "01");
+        assert_eq!(u32s[clone], 0);
+    });
+}
+
+#[test]
+fn clone_entity_into() {
+    let world = World::new();
+    world.register_clone::<u32>();
+
+    let (entity, target) = world.run(
+        |mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>| {
+            (
+                entities.add_entity(&mut u32s, 0),
+                entities.add_entity((&mut u32s, &mut usizes), (1, 1)),
+            )
+        },
+    );
+
+    world.run(|mut all_storages: AllStoragesViewMut| {
+        assert!(all_storages.clone_entity_into(entity, target).is_empty());
+    });
+
+    world.run(|u32s: View<u32>, usizes: View<usize>| {
+        assert_eq!(u32s[target], 0);
+        assert_eq!(usizes[target], 1);
+    });
+}
+
+#[test]
+fn partial_clone() {
+    let world = World::new();
+    world.register_clone::<u32>();
+
+    let (entity, target, dead) = world.run(
+        |mut entities: EntitiesViewMut,
+         mut u32s: ViewMut<u32>,
+         mut usizes: ViewMut<usize>,
+         mut u64s: ViewMut<u64>| {
+            (
+                entities.add_entity((&mut u32s, &mut usizes, &mut u64s), (0, 0, 0)),
+                entities.add_entity(&mut u64s, 1),
+                entities.add_entity((), ()),
+            )
+        },
+    );
+
+    let mut names = vec![type_name::<usize>(), type_name::<u64>()];
+    names.sort_unstable();
+
+    let clone = world.run(|mut all_storages: AllStoragesViewMut| {
+        all_storages.delete(dead);
+
+        let (clone, skipped) = all_storages.clone_entity(entity);
+        assert_eq!(skipped, names);
+        assert_eq!(all_storages.clone_entity_into(entity, target), names);
+
+        assert_eq!(
+            all_storages.try_clone_entity(dead),
+            Err(error::CloneEntity::EntityIsNotAlive)
+        );
+        assert_eq!(
+            all_storages.try_clone_entity_into(entity, dead),
+            Err(error::CloneEntity::EntityIsNotAlive)
+        );
+
+        clone
+    });
+
+    // only the cloneable component was cloned
+    world.run(|u32s: View<u32>, usizes: View<usize>, u64s: View<u64>| {
+        assert_eq!(u32s[clone], 0);
+        assert_eq!(u32s[target], 0);
+        assert_eq!(usizes.len(), 1);
+        assert!(u64s.get(clone).is_err());
+        assert_eq!(u64s[target], 1);
+    });
+}
+
+#[test]
+fn packs() {
+    let world = World::new();
+    world.register_clone::<u32>();
+    world.register_clone::<usize>();
+    world.register_clone::<u64>();
+
+    world.run(
+        |mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>, mut u64s: ViewMut<u64>| {
+            (&mut u32s, &mut usizes).tight_pack();
+            u64s.update_pack();
+        },
+    );
+
+    let entity = world.run(
+        |mut entities: EntitiesViewMut,
+         mut u32s: ViewMut<u32>,
+         mut usizes: ViewMut<usize>,
+         mut u64s: ViewMut<u64>| {
+            let entity = entities.add_entity((&mut u32s, &mut usizes, &mut u64s), (0, 0, 0));
+            u64s.clear_inserted();
+            entity
+        },
+    );
+
+    let (clone, _) =
+        world.run(|mut all_storages: AllStoragesViewMut| all_storages.clone_entity(entity));
+
+    world.run(|u32s: View<u32>, usizes: View<usize>, u64s: View<u64>| {
+        assert_eq!((&u32s, &usizes).iter().count(), 2);
+        assert_eq!(u64s.inserted().len(), 1);
+        assert_eq!(u64s.inserted()[clone], 0);
+    });
+}
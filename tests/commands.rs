@@ -0,0 +1,167 @@
+use shipyard::error;
+use shipyard::*;
+
+#[test]
+fn flush() {
+    let world = World::new();
+
+    let (entity0, entity1) = world.run(
+        |mut entities: EntitiesViewMut, mut usizes: ViewMut<usize>, mut u32s: ViewMut<u32>| {
+            (
+                entities.add_entity((&mut usizes, &mut u32s), (0usize, 1u32)),
+                entities.add_entity(&mut usizes, 2usize),
+            )
+        },
+    );
+
+    world.run(|commands: Commands| {
+        commands.spawn((3usize, 4u32));
+        commands.insert(entity1, (5u32,));
+        commands.remove::<(usize,)>(entity0);
+        commands.delete(entity1);
+        commands.insert(entity1, (6usize,));
+    });
+
+    world.run(|usizes: View<usize>, u32s: View<u32>| {
+        assert_eq!(usizes.len(), 2);
+        assert_eq!(u32s.len(), 1);
+    });
+
+    world.run(|mut all_storages: AllStoragesViewMut| all_storages.flush_commands());
+
+    world.run(
+        |entities: EntitiesView, usizes: View<usize>, u32s: View<u32>| {
+            assert!(!entities.is_alive(entity1));
+            assert!(usizes.get(entity0).is_err());
+            assert_eq!(u32s[entity0], 1);
+            assert_eq!(usizes.len(), 1);
+            assert_eq!(u32s.len(), 2);
+            assert_eq!((&usizes, &u32s).iter().collect::<Vec<_>>(), vec![(&3, &4)]);
+        },
+    );
+}
+
+#[test]
+fn remove_is_tracked_as_removed() {
+    let world = World::new();
+
+    let entity = world.run(
+        |mut entities: EntitiesViewMut, mut usizes: ViewMut<usize>| {
+            usizes.track_removal();
+            entities.add_entity(&mut usizes, 0)
+        },
+    );
+
+    world.run(|commands: Commands| commands.remove::<(usize,)>(entity));
+    world.run(|mut all_storages: AllStoragesViewMut| all_storages.flush_commands());
+
+    world.run(|usizes: View<usize>| {
+        assert!(usizes.get(entity).is_err());
+        assert_eq!(usizes.removed(), &[entity]);
+        assert!(usizes.deleted().is_empty());
+    });
+}
+
+#[test]
+fn pack() {
+    let world = World::new();
+
+    world.run(|mut usizes: ViewMut<usize>, mut u32s: ViewMut<u32>| {
+        (&mut usizes, &mut u32s).tight_pack();
+    });
+
+    world.run(|commands: Commands| commands.spawn((0usize, 1u32)));
+    world.run(|mut all_storages: AllStoragesViewMut| all_storages.flush_commands());
+
+    world.run(|usizes: View<usize>, u32s: View<u32>| {
+        assert_eq!((&usizes, &u32s).iter().collect::<Vec<_>>(), vec![(&0, &1)]);
+    });
+
+    let entity = world.run(|entities: EntitiesView| entities.iter().next().unwrap());
+
+    world.run(|commands: Commands| commands.remove::<(u32,)>(entity));
+    world.run(|mut all_storages: AllStoragesViewMut| all_storages.flush_commands());
+
+    world.run(|usizes: View<usize>, u32s: View<u32>| {
+        assert_eq!(usizes.len(), 1);
+        assert_eq!(u32s.len(), 0);
+        assert_eq!((&usizes, &u32s).iter().count(), 0);
+    });
+}
+
+#[test]
+fn errors() {
+    let world = World::new();
+    world.add_unique(0u64);
+
+    world.run(|commands: Commands| {
+        commands.spawn((1u64,));
+        commands.spawn((2u32,));
+    });
+
+    world.run(|mut all_storages: AllStoragesViewMut| {
+        assert_eq!(
+            all_storages.try_flush_commands(),
+            Err(error::GetStorage::Unique {
+                name: core::any::type_name::<u64>(),
+                borrow: error::Borrow::Unique
+            })
+        );
+    });
+
+    world.run(|entities: EntitiesView, u32s: View<u32>| {
+        assert_eq!(entities.iter().count(), 0);
+        assert_eq!(u32s.len(), 0);
+    });
+
+    world.run(|mut all_storages: AllStoragesViewMut| all_storages.flush_commands());
+
+    world.run(|u32s: View<u32>| assert_eq!(u32s.len(), 1));
+}
+
+fn spawn_usize(commands: Commands, u32s: View<u32>) {
+    for &value in u32s.iter() {
+        commands.spawn((value as usize,));
+    }
+}
+
+fn spawn_u64(commands: Commands, u32s: View<u32>) {
+    for &value in u32s.iter() {
+        commands.spawn((value as u64,));
+    }
+}
+
+fn check(usizes: View<usize>, u64s: View<u64>) {
+    assert_eq!(usizes.len(), 2);
+    assert_eq!(u64s.len(), 2);
+}
+
+#[test]
+fn workload() {
+    let world = World::new();
+
+    world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>| {
+        entities.add_entity(&mut u32s, 0);
+        entities.add_entity(&mut u32s, 1);
+    });
+
+    world
+        .add_workload("Spawn")
+        .with_system(system!(spawn_usize))
+        .with_system(system!(spawn_u64))
+        .with_flush()
+        .with_system(system!(check))
+        .build();
+
+    let info = world.workload_info("Spawn");
+    let batches: Vec<Vec<_>> = info
+        .batches()
+        .iter()
+        .map(|batch| batch.iter().map(SystemInfo::name).collect())
+        .collect();
+    assert_eq!(batches.len(), 3);
+    assert_eq!(batches[0].len(), 2);
+    assert!(batches[1][0].ends_with("flush_commands"));
+
+    world.run_default();
+}
@@ -0,0 +1,154 @@
+use shipyard::error;
+use shipyard::*;
+
+fn new_world() -> World {
+    let world = World::new();
+    world.register_delta::<u32>();
+    world.register_delta::<usize>();
+    world
+}
+
+#[test]
+fn changes() {
+    let world = new_world();
+
+    let (entity0, entity1, entity2) = world.run(
+        |mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>| {
+            (
+                entities.add_entity(&mut u32s, 0),
+                entities.add_entity((&mut u32s, &mut usizes), (1, 1)),
+                entities.add_entity(&mut usizes, 2),
+            )
+        },
+    );
+
+    let checkpoint = world.checkpoint();
+    assert!(world.delta(&checkpoint).is_empty());
+
+    let entity3 = world.run(|mut all_storages: AllStoragesViewMut| {
+        all_storages.delete(entity0);
+        all_storages.run(
+            |mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>| {
+                u32s[entity1] = 10;
+                usizes.remove(entity1);
+                entities.add_component(&mut u32s, 2, entity2);
+                entities.add_entity(&mut usizes, 3)
+            },
+        )
+    });
+
+    let delta = world.delta(&checkpoint);
+    assert_eq!(delta.created(), &[entity3]);
+    assert_eq!(delta.destroyed(), &[entity0]);
+
+    let u32s = delta.components::<u32>().unwrap();
+    assert_eq!(u32s.added(), &[(entity2, 2)]);
+    assert_eq!(u32s.changed(), &[(entity1, 10)]);
+    assert_eq!(u32s.removed(), &[entity0]);
+
+    let usizes = delta.components::<usize>().unwrap();
+    assert_eq!(usizes.added(), &[(entity3, 3)]);
+    assert_eq!(usizes.changed(), &[]);
+    assert_eq!(usizes.removed(), &[entity1]);
+}
+
+#[test]
+fn apply() {
+    let world = new_world();
+    let replica = new_world();
+
+    let (entity0, entity1) = world.run(
+        |mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>| {
+            (
+                entities.add_entity(&mut u32s, 0),
+                entities.add_entity((&mut u32s, &mut usizes), (1, 1)),
+            )
+        },
+    );
+    let checkpoint = world.checkpoint();
+    replica.restore(&checkpoint);
+
+    let entity2 = world.run(|mut all_storages: AllStoragesViewMut| {
+        all_storages.delete(entity0);
+        all_storages.run(
+            |mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>| {
+                u32s[entity1] = 10;
+                usizes.remove(entity1);
+                entities.add_entity(&mut usizes, 2)
+            },
+        )
+    });
+
+    replica.apply_delta(&world.delta(&checkpoint));
+
+    replica.run(
+        |entities: EntitiesView, u32s: View<u32>, usizes: View<usize>| {
+            assert!(!entities.is_alive(entity0));
+            assert!(entities.is_alive(entity1));
+            assert!(entities.is_alive(entity2));
+            assert_eq!(u32s.len(), 1);
+            assert_eq!(u32s[entity1], 10);
+            assert_eq!(usizes.len(), 1);
+            assert_eq!(usizes[entity2], 2);
+        },
+    );
+
+    // both worlds generate the same ids
+    let new_entity = world.borrow::<EntitiesViewMut>().add_entity((), ());
+    let replica_new_entity = replica.borrow::<EntitiesViewMut>().add_entity((), ());
+    assert_eq!(new_entity, replica_new_entity);
+}
+
+#[test]
+fn apply_tight_pack() {
+    let world = new_world();
+    let replica = new_world();
+    for world in &[&world, &replica] {
+        world.run(|mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>| {
+            (&mut u32s, &mut usizes).tight_pack();
+        });
+    }
+
+    let (entity0, entity1) = world.run(
+        |mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>| {
+            (
+                entities.add_entity((&mut u32s, &mut usizes), (0, 0)),
+                entities.add_entity(&mut u32s, 1),
+            )
+        },
+    );
+    let checkpoint = world.checkpoint();
+    replica.restore(&checkpoint);
+
+    world.run(
+        |entities: EntitiesViewMut, mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>| {
+            entities.add_component((&mut u32s, &mut usizes), (1, 1), entity1);
+            Remove::<(u32,)>::remove((&mut u32s, &mut usizes), entity0);
+        },
+    );
+
+    replica.apply_delta(&world.delta(&checkpoint));
+
+    replica.run(|u32s: View<u32>, usizes: View<usize>| {
+        let mut iter = (&u32s, &usizes).iter();
+        assert_eq!(iter.next(), Some((&1, &1)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(usizes[entity0], 0);
+    });
+}
+
+#[test]
+fn mismatched_checkpoint() {
+    let other = World::new();
+    other.register_unique_clone::<u32>();
+    other.add_unique(0u32);
+    let checkpoint = other.checkpoint();
+
+    let world = new_world();
+    assert_eq!(
+        world.try_delta(&checkpoint).err(),
+        Some(error::GetStorage::MismatchedStorage(
+            core::any::type_name::<u32>()
+        ))
+    );
+}
@@ -0,0 +1,149 @@
+use shipyard::*;
+use std::sync::{Arc, Mutex};
+
+type Calls = Arc<Mutex<Vec<(&'static str, EntityId, u32)>>>;
+
+fn register(world: &World) -> Calls {
+    let calls = Calls::default();
+
+    world.run(|mut u32s: ViewMut<u32>| {
+        let insert = calls.clone();
+        u32s.on_insert(move |entity, &value| {
+            insert.lock().unwrap().push(("insert", entity, value))
+        });
+        let remove = calls.clone();
+        u32s.on_remove(move |entity, &value| {
+            remove.lock().unwrap().push(("remove", entity, value))
+        });
+        let delete = calls.clone();
+        u32s.on_delete(move |entity, &value| {
+            delete.lock().unwrap().push(("delete", entity, value))
+        });
+    });
+
+    calls
+}
+
+#[test]
+fn insert_remove_delete() {
+    let world = World::new();
+    let calls = register(&world);
+
+    let (entity0, entity1) = world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>| {
+        let entity0 = entities.add_entity(&mut u32s, 0);
+        let entity1 = entities.add_entity(&mut u32s, 1);
+        entities.add_component(&mut u32s, 2, entity0);
+        (entity0, entity1)
+    });
+
+    world.run(|mut u32s: ViewMut<u32>| {
+        assert_eq!(
+            Remove::<(u32,)>::remove((&mut u32s,), entity0).0,
+            Some(OldComponent::Owned(2))
+        );
+        u32s.delete(entity1);
+        u32s.delete(entity1);
+    });
+
+    assert_eq!(
+        *calls.lock().unwrap(),
+        vec![
+            ("insert", entity0, 0),
+            ("insert", entity1, 1),
+            ("insert", entity0, 2),
+            ("remove", entity0, 2),
+            ("delete", entity1, 1),
+        ]
+    );
+}
+
+#[test]
+fn all_storages() {
+    let world = World::new();
+    let calls = register(&world);
+
+    let (entity0, entity1, entity2) =
+        world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>| {
+            (
+                entities.add_entity(&mut u32s, 0),
+                entities.add_entity(&mut u32s, 1),
+                entities.add_entity(&mut u32s, 2),
+            )
+        });
+    calls.lock().unwrap().clear();
+
+    world.run(|mut all_storages: AllStoragesViewMut| {
+        all_storages.delete(entity0);
+        all_storages.strip(entity1);
+    });
+    world.run(|mut u32s: ViewMut<u32>| u32s.clear());
+
+    assert_eq!(
+        *calls.lock().unwrap(),
+        vec![
+            ("delete", entity0, 0),
+            ("delete", entity1, 1),
+            ("delete", entity2, 2),
+        ]
+    );
+}
+
+#[test]
+fn clear_hooks() {
+    let world = World::new();
+    let calls = register(&world);
+
+    world.run(|mut u32s: ViewMut<u32>| u32s.clear_hooks());
+    world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>| {
+        let entity = entities.add_entity(&mut u32s, 0);
+        u32s.delete(entity);
+    });
+
+    assert!(calls.lock().unwrap().is_empty());
+}
+
+#[test]
+fn restore() {
+    let world = World::new();
+    world.register_clone::<u32>();
+    world.register_snapshot::<u32>();
+    let calls = register(&world);
+
+    world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>| {
+        entities.add_entity(&mut u32s, 0);
+    });
+    let checkpoint = world.checkpoint();
+    let snapshot = world.snapshot();
+    world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>| {
+        entities.add_entity(&mut u32s, 1);
+    });
+    calls.lock().unwrap().clear();
+
+    // restoring doesn't delete nor insert components through the hooks
+    world.restore(&checkpoint);
+    world.restore_snapshot(&snapshot);
+
+    assert!(calls.lock().unwrap().is_empty());
+    world.run(|u32s: View<u32>| assert_eq!(u32s.len(), 1));
+}
+
+#[test]
+fn move_entities() {
+    let world = World::new();
+    let calls = register(&world);
+    let other = World::new();
+    let other_calls = register(&other);
+
+    let entity = world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>| {
+        entities.add_entity(&mut u32s, 0)
+    });
+    calls.lock().unwrap().clear();
+
+    let entity_map = world.run(|mut all_storages: AllStoragesViewMut| {
+        other.run(|mut other: AllStoragesViewMut| all_storages.move_entities(&mut other, &[entity]))
+    });
+    let target = entity_map.get(entity).unwrap();
+
+    assert_eq!(*calls.lock().unwrap(), vec![("remove", entity, 0)]);
+    assert_eq!(*other_calls.lock().unwrap(), vec![("insert", target, 0)]);
+}
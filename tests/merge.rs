@@ -0,0 +1,106 @@
+use core::any::type_name;
+use shipyard::error;
+use shipyard::*;
+
+fn level() -> (World, EntityId, EntityId) {
+    let level = World::new();
+    level.add_unique(1u32);
+    level.add_unique(String::from("level"));
+
+    let (entity0, entity1) = level.run(
+        |mut entities: EntitiesViewMut, mut usizes: ViewMut<usize>, mut u64s: ViewMut<u64>| {
+            (
+                entities.add_entity((&mut usizes, &mut u64s), (0, 0)),
+                entities.add_entity(&mut usizes, 1),
+            )
+        },
+    );
+
+    (level, entity0, entity1)
+}
+
+#[test]
+fn merge() {
+    let (level, entity0, entity1) = level();
+
+    let world = World::new();
+    world.add_unique(0u32);
+    let entity = world.run(
+        |mut entities: EntitiesViewMut, mut usizes: ViewMut<usize>| {
+            entities.add_entity(&mut usizes, 10)
+        },
+    );
+
+    let entity_map = world.merge(&level, UniqueConflict::Keep);
+    assert_eq!(entity_map.len(), 2);
+    let new0 = entity_map.get(entity0).unwrap();
+    let new1 = entity_map.get(entity1).unwrap();
+
+    world.run(
+        |usizes: View<usize>, u64s: View<u64>, u32: UniqueView<u32>, string: UniqueView<String>| {
+            assert_eq!(usizes.len(), 3);
+            assert_eq!(usizes[entity], 10);
+            assert_eq!(usizes[new0], 0);
+            assert_eq!(usizes[new1], 1);
+            assert_eq!(u64s[new0], 0);
+            assert_eq!(*u32, 0);
+            assert_eq!(*string, "level");
+        },
+    );
+
+    level.run(
+        |entities: EntitiesView, usizes: View<usize>, u32: UniqueView<u32>| {
+            assert_eq!(entities.iter().count(), 0);
+            assert_eq!(usizes.len(), 0);
+            assert_eq!(*u32, 1);
+        },
+    );
+    assert!(level.try_borrow::<UniqueView<String>>().is_err());
+}
+
+#[test]
+fn replace() {
+    let (level, _, _) = level();
+
+    let world = World::new();
+    world.add_unique(0u32);
+
+    world.merge(&level, UniqueConflict::Replace);
+
+    world.run(|u32: UniqueView<u32>| assert_eq!(*u32, 1));
+    assert!(level.try_borrow::<UniqueView<u32>>().is_err());
+}
+
+#[test]
+fn errors() {
+    let (level, entity0, _) = level();
+
+    let world = World::new();
+    world.add_unique(0u32);
+
+    assert_eq!(
+        world.try_merge(&level, UniqueConflict::Error).err(),
+        Some(error::Merge::UniqueConflict(type_name::<u32>()))
+    );
+    assert_eq!(
+        world.try_merge(&world, UniqueConflict::Keep).err(),
+        Some(error::Merge::AllStoragesBorrow(error::Borrow::Unique))
+    );
+
+    let world = World::new();
+    world.add_unique(0usize);
+
+    assert_eq!(
+        world.try_merge(&level, UniqueConflict::Keep).err(),
+        Some(error::Merge::Storage(error::GetStorage::Unique {
+            name: type_name::<usize>(),
+            borrow: error::Borrow::Unique
+        }))
+    );
+
+    // nothing was merged
+    level.run(|usizes: View<usize>, u32: UniqueView<u32>| {
+        assert_eq!(usizes[entity0], 0);
+        assert_eq!(*u32, 1);
+    });
+}
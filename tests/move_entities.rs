@@ -0,0 +1,125 @@
+use core::any::type_name;
+use shipyard::error;
+use shipyard::*;
+
+#[test]
+fn move_entities() {
+    let loading = World::new();
+    let world = World::new();
+
+    world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>| {
+        entities.add_entity(&mut u32s, 10);
+    });
+
+    let (entity0, entity1, entity2) = loading.run(
+        |mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>, mut strings: ViewMut<String>| {
+            (
+                entities.add_entity((&mut u32s, &mut strings), (0, String::from("0"))),
+                entities.add_entity(&mut strings, String::from("1")),
+                entities.add_entity(&mut u32s, 2),
+            )
+        },
+    );
+
+    let entity_map = loading.move_entities(&world, &[entity0, entity1]);
+    assert_eq!(entity_map.len(), 2);
+    let new0 = entity_map.get(entity0).unwrap();
+    let new1 = entity_map.get(entity1).unwrap();
+
+    loading.run(
+        |entities: EntitiesView, u32s: View<u32>, strings: View<String>| {
+            assert!(!entities.is_alive(entity0));
+            assert!(!entities.is_alive(entity1));
+            assert_eq!(u32s.len(), 1);
+            assert_eq!(u32s[entity2], 2);
+            assert_eq!(strings.len(), 0);
+        },
+    );
+
+    world.run(
+        |entities: EntitiesView, u32s: View<u32>, strings: View<String>| {
+            assert!(entities.is_alive(new0));
+            assert!(entities.is_alive(new1));
+            assert_eq!(u32s.len(), 2);
+            assert_eq!(u32s[new0], 0);
+            assert_eq!(strings[new0], "0");
+            assert_eq!(strings[new1], "1");
+        },
+    );
+
+    // dead entities are ignored
+    assert!(loading.move_entities(&world, &[entity0]).is_empty());
+}
+
+#[test]
+fn packs() {
+    let loading = World::new();
+    let world = World::new();
+    for world in &[&loading, &world] {
+        world.run(
+            |mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>, mut u64s: ViewMut<u64>| {
+                (&mut u32s, &mut usizes).tight_pack();
+                u64s.update_pack();
+            },
+        );
+    }
+
+    let (entity0, entity1) = loading.run(
+        |mut entities: EntitiesViewMut,
+         mut u32s: ViewMut<u32>,
+         mut usizes: ViewMut<usize>,
+         mut u64s: ViewMut<u64>| {
+            let entities = (
+                entities.add_entity((&mut u32s, &mut usizes, &mut u64s), (0, 0, 0)),
+                entities.add_entity((&mut u32s, &mut usizes), (1, 1)),
+            );
+            u64s.clear_inserted();
+            entities
+        },
+    );
+
+    let entity_map = loading.move_entities(&world, &[entity0]);
+    let new0 = entity_map.get(entity0).unwrap();
+
+    loading.run(|u32s: View<u32>, usizes: View<usize>, u64s: View<u64>| {
+        let mut iter = (&u32s, &usizes).iter();
+        assert_eq!(iter.next(), Some((&1, &1)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(u64s.removed(), &[entity0]);
+        assert_eq!(u32s[entity1], 1);
+    });
+
+    world.run(|u32s: View<u32>, usizes: View<usize>, u64s: View<u64>| {
+        let mut iter = (&u32s, &usizes).iter();
+        assert_eq!(iter.next(), Some((&0, &0)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(u64s.inserted().len(), 1);
+        assert_eq!(u64s.inserted()[new0], 0);
+    });
+}
+
+#[test]
+fn errors() {
+    let loading = World::new();
+    let world = World::new();
+    world.add_unique(0u32);
+
+    let entity = loading.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>| {
+        entities.add_entity(&mut u32s, 0)
+    });
+
+    assert_eq!(
+        loading.try_move_entities(&world, &[entity]).err(),
+        Some(error::GetStorage::Unique {
+            name: type_name::<u32>(),
+            borrow: error::Borrow::Unique
+        })
+    );
+    assert_eq!(
+        loading.try_move_entities(&loading, &[entity]).err(),
+        Some(error::GetStorage::AllStoragesBorrow(error::Borrow::Unique))
+    );
+
+    // nothing was moved
+    loading.run(|u32s: View<u32>| assert_eq!(u32s[entity], 0));
+}
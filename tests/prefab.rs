@@ -0,0 +1,118 @@
+use core::any::type_name;
+use shipyard::error;
+use shipyard::*;
+
+#[test]
+fn spawn() {
+    let world = World::new();
+    world.add_unique(Prefabs::new());
+
+    world.run(|mut prefabs: UniqueViewMut<Prefabs>| {
+        prefabs.register("goblin", (String::from("goblin"), 10u32));
+        prefabs.register("rock", ());
+    });
+
+    let (goblin0, goblin1, boss, rock) = world.run(|mut all_storages: AllStoragesViewMut| {
+        (
+            all_storages.spawn_prefab("goblin"),
+            all_storages.spawn_prefab("goblin"),
+            all_storages.spawn_prefab_with("goblin", (50u32, 1usize)),
+            all_storages.spawn_prefab("rock"),
+        )
+    });
+
+    world.run(
+        |entities: EntitiesView, strings: View<String>, u32s: View<u32>, usizes: View<usize>| {
+            assert_eq!(strings[goblin0], "goblin");
+            assert_eq!(u32s[goblin0], 10);
+            assert_eq!(strings[goblin1], "goblin");
+            assert_eq!(u32s[goblin1], 10);
+            assert_eq!(strings[boss], "goblin");
+            assert_eq!(u32s[boss], 50);
+            assert_eq!(usizes[boss], 1);
+            assert_eq!(usizes.len(), 1);
+            assert!(entities.is_alive(rock));
+        },
+    );
+}
+
+#[test]
+fn errors() {
+    let world = World::new();
+
+    world.run(|mut all_storages: AllStoragesViewMut| {
+        assert_eq!(
+            all_storages.try_spawn_prefab("goblin"),
+            Err(error::SpawnPrefab::Storage(
+                error::GetStorage::MissingUnique(type_name::<Prefabs>())
+            ))
+        );
+    });
+
+    let mut prefabs = Prefabs::new();
+    prefabs.register("goblin", (0u32,));
+    world.add_unique(prefabs);
+    world.add_unique(0usize);
+
+    world.run(|mut all_storages: AllStoragesViewMut| {
+        assert_eq!(
+            all_storages.try_spawn_prefab("orc"),
+            Err(error::SpawnPrefab::MissingPrefab(String::from("orc")))
+        );
+        assert_eq!(
+            all_storages.try_spawn_prefab_with("goblin", (0usize,)),
+            Err(error::SpawnPrefab::Storage(error::GetStorage::Unique {
+                name: type_name::<usize>(),
+                borrow: error::Borrow::Unique
+            }))
+        );
+    });
+
+    // the entity of the failed spawn is deleted
+    world.run(|entities: EntitiesView, u32s: View<u32>| {
+        assert_eq!(entities.iter().count(), 0);
+        assert_eq!(u32s.len(), 0);
+    });
+}
+
+#[test]
+fn tight_pack() {
+    let world = World::new();
+    let mut prefabs = Prefabs::new();
+    prefabs.register("goblin", (0u32, 0usize));
+    world.add_unique(prefabs);
+
+    world.run(|mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>| {
+        (&mut u32s, &mut usizes).tight_pack();
+    });
+
+    let goblin = world.run(|mut all_storages: AllStoragesViewMut| {
+        all_storages.spawn_prefab_with("goblin", (1u32,))
+    });
+
+    world.run(|u32s: View<u32>, usizes: View<usize>| {
+        let mut iter = (&u32s, &usizes).iter().with_id();
+        assert_eq!(iter.next(), Some((goblin, (&1, &0))));
+        assert_eq!(iter.next(), None);
+    });
+}
+
+#[test]
+fn overrides_insert_once() {
+    let world = World::new();
+    let mut prefabs = Prefabs::new();
+    prefabs.register("goblin", (String::from("goblin"), 10u32));
+    world.add_unique(prefabs);
+
+    let inserted = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    world.run(|mut u32s: ViewMut<u32>| {
+        let inserted = inserted.clone();
+        u32s.on_insert(move |_, &value| inserted.lock().unwrap().push(value));
+    });
+
+    world.run(|mut all_storages: AllStoragesViewMut| {
+        all_storages.spawn_prefab_with("goblin", (50u32,));
+    });
+
+    assert_eq!(*inserted.lock().unwrap(), vec![50]);
+}
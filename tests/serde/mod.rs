@@ -1 +1,3 @@
 mod entity_id;
+mod sparse_set;
+mod world;
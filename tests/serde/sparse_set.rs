@@ -0,0 +1,114 @@
+use serde::de::DeserializeSeed;
+use shipyard::*;
+
+#[test]
+fn pairs() {
+    let world = World::new();
+    let (mut entities, mut u32s) = world.borrow::<(EntitiesViewMut, ViewMut<u32>)>();
+
+    entities.add_entity(&mut u32s, 0);
+    entities.add_entity(&mut u32s, 1);
+
+    let json = serde_json::to_string(&u32s).unwrap();
+    assert_eq!(
+        json,
+        r#"{"components":[[[0,0],0],[[1,0],1]],"shared":[],"pack_len":0,"update":null}"#
+    );
+    drop(u32s);
+
+    let u32s = world.borrow::<View<u32>>();
+    assert_eq!(serde_json::to_string(&u32s).unwrap(), json);
+
+    let sparse_set: SparseSet<u32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(sparse_set.as_slice(), u32s.as_slice());
+}
+
+#[test]
+fn shared() {
+    let world = World::new();
+    let (mut entities, mut u32s) = world.borrow::<(EntitiesViewMut, ViewMut<u32>)>();
+
+    let owned = entities.add_entity(&mut u32s, 0);
+    let shared = entities.add_entity((), ());
+    u32s.share(owned, shared);
+
+    let json = serde_json::to_string(&u32s).unwrap();
+    let sparse_set: SparseSet<u32> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(sparse_set.len(), 1);
+    assert_eq!(sparse_set[owned], 0);
+    assert_eq!(sparse_set[shared], 0);
+    assert!(sparse_set.contains_shared(shared));
+}
+
+#[test]
+fn update_pack() {
+    let world = World::new();
+    let (mut entities, mut usizes) = world.borrow::<(EntitiesViewMut, ViewMut<usize>)>();
+
+    usizes.update_pack();
+    let entity0 = entities.add_entity(&mut usizes, 0);
+    let entity1 = entities.add_entity(&mut usizes, 1);
+    let entity2 = entities.add_entity(&mut usizes, 2);
+    let entity3 = entities.add_entity(&mut usizes, 3);
+    usizes.clear_inserted();
+    usizes[entity1] = 10;
+    entities.add_component(&mut usizes, 4, entity2);
+    usizes.remove(entity0);
+    usizes.delete(entity3);
+
+    let json = serde_json::to_string(&usizes).unwrap();
+    let sparse_set: SparseSet<usize> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(sparse_set.len(), 2);
+    assert_eq!(sparse_set.modified().len(), 2);
+    assert_eq!(sparse_set.inserted().len(), 0);
+    assert_eq!(sparse_set.removed(), &[entity0]);
+    assert_eq!(sparse_set.deleted(), &[(entity3, 3)]);
+    assert_eq!(sparse_set[entity1], 10);
+    assert_eq!(sparse_set[entity2], 4);
+}
+
+#[test]
+fn into_view_mut() {
+    let world = World::new();
+    let (mut entities, mut u32s) = world.borrow::<(EntitiesViewMut, ViewMut<u32>)>();
+
+    let entity0 = entities.add_entity(&mut u32s, 0);
+    let json = serde_json::to_string(&u32s).unwrap();
+
+    let entity1 = entities.add_entity(&mut u32s, 1);
+    u32s[entity0] = 10;
+
+    (&mut u32s)
+        .deserialize(&mut serde_json::Deserializer::from_str(&json))
+        .unwrap();
+
+    assert_eq!(u32s.len(), 1);
+    assert_eq!(u32s[entity0], 0);
+    assert!(!u32s.contains(entity1));
+}
+
+#[test]
+fn duplicate_entity() {
+    let json = r#"{"components":[[[0,0],0],[[0,0],1]],"shared":[],"pack_len":0,"update":null}"#;
+
+    assert!(serde_json::from_str::<SparseSet<u32>>(json).is_err());
+}
+
+#[test]
+fn malformed_shared() {
+    // too far from the entities owning a component
+    let json =
+        r#"{"components":[[[0,0],0]],"shared":[[1000000000000,[0,0]]],"pack_len":0,"update":null}"#;
+    assert!(serde_json::from_str::<SparseSet<u32>>(json).is_err());
+
+    // the observed entity doesn't own a component
+    let json = r#"{"components":[[[0,0],0]],"shared":[[1,[2,0]]],"pack_len":0,"update":null}"#;
+    assert!(serde_json::from_str::<SparseSet<u32>>(json).is_err());
+
+    // the observer already owns a component
+    let json =
+        r#"{"components":[[[0,0],0],[[1,0],1]],"shared":[[1,[0,0]]],"pack_len":0,"update":null}"#;
+    assert!(serde_json::from_str::<SparseSet<u32>>(json).is_err());
+}
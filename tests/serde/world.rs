@@ -0,0 +1,324 @@
+use shipyard::error;
+use shipyard::*;
+
+fn new_world() -> World {
+    let world = World::new();
+    world.register_serde::<u32>("u32");
+    world.register_serde::<usize>("usize");
+    world.register_unique_serde::<u64>("u64");
+    world
+}
+
+#[test]
+fn world_roundtrip() {
+    let world = new_world();
+
+    world.add_unique(5u64);
+    let (entity0, entity1, entity2) = world.run(
+        |mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>| {
+            (
+                entities.add_entity(&mut u32s, 0),
+                entities.add_entity((&mut u32s, &mut usizes), (1, 10)),
+                entities.add_entity(&mut usizes, 20),
+            )
+        },
+    );
+    world.run(|mut all_storages: AllStoragesViewMut| {
+        assert!(all_storages.delete(entity0));
+    });
+
+    let json = save(&world);
+
+    let loaded = new_world();
+    loaded
+        .deserialize(&mut serde_json::Deserializer::from_slice(&json))
+        .unwrap();
+
+    loaded.run(
+        |entities: EntitiesView, u32s: View<u32>, usizes: View<usize>| {
+            assert!(!entities.is_alive(entity0));
+            assert!(entities.is_alive(entity1));
+            assert!(entities.is_alive(entity2));
+            assert_eq!(u32s.len(), 1);
+            assert_eq!(u32s.get(entity1), Ok(&1));
+            assert_eq!(usizes.len(), 2);
+            assert_eq!(usizes.get(entity1), Ok(&10));
+            assert_eq!(usizes.get(entity2), Ok(&20));
+        },
+    );
+    assert_eq!(*loaded.borrow::<UniqueView<u64>>(), 5);
+
+    // both worlds reuse the deleted entity's index with the same generation
+    let new_entity = world.borrow::<EntitiesViewMut>().add_entity((), ());
+    let loaded_new_entity = loaded.borrow::<EntitiesViewMut>().add_entity((), ());
+    assert_eq!(new_entity, loaded_new_entity);
+    assert_eq!(new_entity.index(), entity0.index());
+    assert_eq!(new_entity.gen(), entity0.gen() + 1);
+}
+
+#[test]
+fn replaces_content() {
+    let world = new_world();
+    let entity = world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>| {
+        entities.add_entity(&mut u32s, 0)
+    });
+    let json = save(&world);
+
+    world.run(
+        |mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>, mut i8s: ViewMut<i8>| {
+            entities.add_entity((&mut u32s, &mut i8s), (1, 1));
+        },
+    );
+
+    world
+        .deserialize(&mut serde_json::Deserializer::from_slice(&json))
+        .unwrap();
+
+    world.run(|u32s: View<u32>, i8s: View<i8>| {
+        assert_eq!(u32s.len(), 1);
+        assert_eq!(u32s.get(entity), Ok(&0));
+        assert!(i8s.is_empty());
+    });
+}
+
+#[test]
+fn removal_tracking() {
+    let world = new_world();
+    let entity = world.run(
+        |mut entities: EntitiesViewMut, mut usizes: ViewMut<usize>| {
+            usizes.track_removal();
+            entities.add_entity(&mut usizes, 0)
+        },
+    );
+    let json = save(&world);
+
+    world
+        .deserialize(&mut serde_json::Deserializer::from_slice(&json))
+        .unwrap();
+
+    world.run(|usizes: View<usize>| {
+        assert_eq!(usizes[entity], 0);
+        assert!(usizes.removed().is_empty());
+        assert!(usizes.deleted().is_empty());
+    });
+}
+
+#[test]
+fn tight_pack() {
+    let world = new_world();
+    world.run(|mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>| {
+        (&mut u32s, &mut usizes).tight_pack();
+    });
+    world.run(
+        |mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>| {
+            entities.add_entity(&mut u32s, 0);
+            entities.add_entity((&mut u32s, &mut usizes), (1, 10));
+            entities.add_entity(&mut usizes, 20);
+            entities.add_entity((&mut u32s, &mut usizes), (3, 30));
+        },
+    );
+    let json = save(&world);
+
+    let loaded = new_world();
+    loaded.run(|mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>| {
+        (&mut u32s, &mut usizes).tight_pack();
+    });
+    loaded
+        .deserialize(&mut serde_json::Deserializer::from_slice(&json))
+        .unwrap();
+
+    loaded.run(|u32s: View<u32>, usizes: View<usize>| {
+        let mut iter = (&u32s, &usizes).iter();
+        assert_eq!(iter.next(), Some((&1, &10)));
+        assert_eq!(iter.next(), Some((&3, &30)));
+        assert_eq!(iter.next(), None);
+    });
+}
+
+#[test]
+fn partially_registered_tight_pack() {
+    let world = World::new();
+    world.register_serde::<u32>("u32");
+    world.run(|mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>| {
+        (&mut u32s, &mut usizes).tight_pack();
+    });
+    let entity0 = world.run(
+        |mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>| {
+            entities.add_entity((&mut u32s, &mut usizes), (0, 10))
+        },
+    );
+    let json = save(&world);
+
+    let loaded = World::new();
+    loaded.register_serde::<u32>("u32");
+    loaded.run(|mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>| {
+        (&mut u32s, &mut usizes).tight_pack();
+    });
+    loaded
+        .deserialize(&mut serde_json::Deserializer::from_slice(&json))
+        .unwrap();
+
+    loaded.run(
+        |mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>| {
+            assert_eq!(u32s.get(entity0), Ok(&0));
+            assert_eq!((&u32s, &usizes).iter().count(), 0);
+
+            let entity1 = entities.add_entity((&mut u32s, &mut usizes), (1, 99));
+
+            let mut iter = (&u32s, &usizes).iter().with_id();
+            assert_eq!(iter.next(), Some((entity1, (&1, &99))));
+            assert_eq!(iter.next(), None);
+        },
+    );
+}
+
+#[test]
+fn shared() {
+    let world = new_world();
+    let (owner, observer) = world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>| {
+        let owner = entities.add_entity(&mut u32s, 0);
+        for _ in 0..100 {
+            entities.add_entity((), ());
+        }
+        let observer = entities.add_entity((), ());
+        u32s.share(owner, observer);
+        (owner, observer)
+    });
+    let json = save(&world);
+
+    let loaded = new_world();
+    loaded
+        .deserialize(&mut serde_json::Deserializer::from_slice(&json))
+        .unwrap();
+
+    loaded.run(|u32s: View<u32>| {
+        assert_eq!(u32s.get(owner), Ok(&0));
+        assert_eq!(u32s.get(observer), Ok(&0));
+    });
+}
+
+#[test]
+fn shared_out_of_entities() {
+    let json = r#"{"entities":{"data":[[0,0]],"list":null},"storages":{"u32":{"components":[[[0,0],0]],"shared":[[1,[0,0]]],"pack_len":0,"update":null}}}"#;
+
+    assert!(new_world()
+        .deserialize(&mut serde_json::Deserializer::from_str(json))
+        .is_err());
+}
+
+#[test]
+fn unknown_key() {
+    let world = new_world();
+    world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>| {
+        entities.add_entity(&mut u32s, 0);
+    });
+    let json = save(&world);
+
+    let other = World::new();
+    other.register_serde::<u32>("other");
+
+    assert!(other
+        .deserialize(&mut serde_json::Deserializer::from_slice(&json))
+        .is_err());
+}
+
+#[test]
+fn register_twice() {
+    let world = World::new();
+    world.register_serde::<u32>("u32");
+
+    assert_eq!(
+        world.try_register_serde::<u64>("u32").err(),
+        Some(error::Register::KeyAlreadyUsed("u32"))
+    );
+    assert_eq!(
+        world.try_register_unique_serde::<u32>("unique").err(),
+        Some(error::Register::TypeAlreadyRegistered(
+            core::any::type_name::<u32>()
+        ))
+    );
+}
+
+#[test]
+fn additive() {
+    let source = new_world();
+    source.register_serde_mapped::<EntityId>("parent");
+    source.add_unique(5u64);
+    let (parent, child) = source.run(
+        |mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>, mut parents: ViewMut<EntityId>| {
+            let parent = entities.add_entity(&mut u32s, 0);
+            let child = entities.add_entity((&mut u32s, &mut parents), (1, parent));
+            (parent, child)
+        },
+    );
+    let json = save(&source);
+
+    let world = new_world();
+    world.register_serde_mapped::<EntityId>("parent");
+    let existing = world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>| {
+        entities.add_entity(&mut u32s, 10)
+    });
+
+    let entity_map = world
+        .deserialize_additive(&mut serde_json::Deserializer::from_slice(&json))
+        .unwrap();
+
+    assert_eq!(entity_map.len(), 2);
+    let new_parent = entity_map.get(parent).unwrap();
+    let new_child = entity_map.get(child).unwrap();
+    assert_ne!(new_parent, existing);
+    assert_ne!(new_child, existing);
+    assert_ne!(new_parent, new_child);
+
+    world.run(
+        |entities: EntitiesView, u32s: View<u32>, parents: View<EntityId>| {
+            assert!(entities.is_alive(existing));
+            assert!(entities.is_alive(new_parent));
+            assert!(entities.is_alive(new_child));
+            assert_eq!(u32s.len(), 3);
+            assert_eq!(u32s.get(existing), Ok(&10));
+            assert_eq!(u32s.get(new_parent), Ok(&0));
+            assert_eq!(u32s.get(new_child), Ok(&1));
+            assert_eq!(parents.get(new_child), Ok(&new_parent));
+        },
+    );
+    assert!(world.try_borrow::<UniqueView<u64>>().is_err());
+}
+
+#[test]
+fn additive_tight_pack() {
+    let source = new_world();
+    let entity = source.run(
+        |mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>| {
+            entities.add_entity((&mut u32s, &mut usizes), (0, 1))
+        },
+    );
+    let json = save(&source);
+
+    let world = new_world();
+    world.run(|mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>| {
+        (&mut u32s, &mut usizes).tight_pack();
+    });
+
+    let entity_map = world
+        .deserialize_additive(&mut serde_json::Deserializer::from_slice(&json))
+        .unwrap();
+    let entity = entity_map.get(entity).unwrap();
+
+    world.run(|u32s: View<u32>, usizes: View<usize>| {
+        assert_eq!(u32s.get(entity), Ok(&0));
+        assert_eq!(usizes.get(entity), Ok(&1));
+        // tightly packed iterators only yield packed components
+        let mut iter = (&u32s, &usizes).iter();
+        assert_eq!(iter.next(), Some((&0, &1)));
+        assert_eq!(iter.next(), None);
+    });
+}
+
+fn save(world: &World) -> Vec<u8> {
+    let mut json = Vec::new();
+    world
+        .serialize(&mut serde_json::Serializer::new(&mut json))
+        .unwrap();
+    json
+}
@@ -0,0 +1,249 @@
+use shipyard::error;
+use shipyard::*;
+
+fn new_world() -> World {
+    let world = World::new();
+    world.register_snapshot::<u32>();
+    world.register_snapshot::<usize>();
+    world.register_unique_snapshot::<u64>();
+    world
+}
+
+#[test]
+fn restore() {
+    let world = new_world();
+
+    world.add_unique(0u64);
+    let (entity0, entity1) = world.run(
+        |mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>| {
+            (
+                entities.add_entity(&mut u32s, 0),
+                entities.add_entity((&mut u32s, &mut usizes), (1, 10)),
+            )
+        },
+    );
+    world.run(|mut all_storages: AllStoragesViewMut| {
+        assert!(all_storages.delete(entity0));
+    });
+
+    let snapshot = world.snapshot();
+
+    let (entity2, entity3) = world.run(
+        |mut entities: EntitiesViewMut,
+         mut u32s: ViewMut<u32>,
+         mut usizes: ViewMut<usize>,
+         mut i8s: ViewMut<i8>| {
+            u32s[entity1] = 5;
+            usizes.remove(entity1);
+            (
+                entities.add_entity(&mut u32s, 2),
+                entities.add_entity((&mut usizes, &mut i8s), (3, 3)),
+            )
+        },
+    );
+    *world.borrow::<UniqueViewMut<u64>>() = 1;
+
+    world.restore_snapshot(&snapshot);
+
+    world.run(
+        |entities: EntitiesView, u32s: View<u32>, usizes: View<usize>, i8s: View<i8>| {
+            assert!(!entities.is_alive(entity0));
+            assert!(entities.is_alive(entity1));
+            assert!(!entities.is_alive(entity2));
+            assert!(!entities.is_alive(entity3));
+            assert_eq!(u32s.len(), 1);
+            assert_eq!(u32s.get(entity1), Ok(&1));
+            assert_eq!(usizes.len(), 1);
+            assert_eq!(usizes.get(entity1), Ok(&10));
+            assert_eq!(i8s.len(), 0);
+        },
+    );
+    assert_eq!(*world.borrow::<UniqueView<u64>>(), 0);
+
+    // the free list is restored
+    let new_entity = world.borrow::<EntitiesViewMut>().add_entity((), ());
+    assert_eq!(new_entity, entity2);
+}
+
+#[test]
+fn shared() {
+    let world = new_world();
+
+    let (owned, shared) = world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>| {
+        let owned = entities.add_entity(&mut u32s, 0);
+        let shared = entities.add_entity((), ());
+        u32s.share(owned, shared);
+        (owned, shared)
+    });
+
+    let snapshot = world.snapshot();
+    world.run(|mut u32s: ViewMut<u32>| u32s.unshare(shared));
+    world.restore_snapshot(&snapshot);
+
+    world.run(|u32s: View<u32>| {
+        assert_eq!(u32s.len(), 1);
+        assert_eq!(u32s[owned], 0);
+        assert_eq!(u32s[shared], 0);
+    });
+}
+
+#[test]
+fn update_pack() {
+    let world = new_world();
+
+    let (entity0, entity1) = world.run(
+        |mut entities: EntitiesViewMut, mut usizes: ViewMut<usize>| {
+            usizes.update_pack();
+            let entity0 = entities.add_entity(&mut usizes, 0);
+            let entity1 = entities.add_entity(&mut usizes, 1);
+            usizes.clear_inserted();
+            usizes[entity1] = 10;
+            usizes.delete(entity0);
+            (entity0, entity1)
+        },
+    );
+
+    let snapshot = world.snapshot();
+    world.run(|mut usizes: ViewMut<usize>| {
+        usizes.clear_modified();
+        usizes.take_deleted();
+    });
+    world.restore_snapshot(&snapshot);
+
+    world.run(|usizes: View<usize>| {
+        assert_eq!(usizes.len(), 1);
+        assert_eq!(usizes.inserted().len(), 0);
+        assert_eq!(usizes.modified().len(), 1);
+        assert_eq!(usizes.deleted(), &[(entity0, 0)]);
+        assert_eq!(usizes[entity1], 10);
+    });
+}
+
+#[test]
+fn removal_tracking() {
+    let world = new_world();
+
+    let entity = world.run(
+        |mut entities: EntitiesViewMut, mut usizes: ViewMut<usize>| {
+            usizes.track_removal();
+            entities.add_entity(&mut usizes, 0)
+        },
+    );
+
+    let snapshot = world.snapshot();
+    world.restore_snapshot(&snapshot);
+
+    world.run(|usizes: View<usize>| {
+        assert_eq!(usizes[entity], 0);
+        assert!(usizes.removed().is_empty());
+        assert!(usizes.deleted().is_empty());
+    });
+}
+
+#[test]
+fn update_pack_not_saved() {
+    let world = new_world();
+
+    let (entity0, entity1) = world.run(
+        |mut entities: EntitiesViewMut, mut usizes: ViewMut<usize>| {
+            (
+                entities.add_entity(&mut usizes, 0),
+                entities.add_entity(&mut usizes, 1),
+            )
+        },
+    );
+
+    let snapshot = world.snapshot();
+    world.run(|mut usizes: ViewMut<usize>| {
+        usizes.update_pack();
+        usizes.clear_inserted();
+        usizes.delete(entity0);
+    });
+    world.restore_snapshot(&snapshot);
+
+    world.run(|usizes: View<usize>| {
+        assert_eq!(usizes.len(), 2);
+        assert_eq!(usizes.inserted().len(), 2);
+        assert_eq!(usizes.modified().len(), 0);
+        assert!(usizes.removed().is_empty());
+        assert!(usizes.deleted().is_empty());
+        assert_eq!(usizes[entity1], 1);
+    });
+}
+
+#[test]
+fn tight_pack() {
+    let world = new_world();
+
+    world.run(|mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>| {
+        (&mut u32s, &mut usizes).tight_pack();
+    });
+    let entity = world.run(
+        |mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>| {
+            entities.add_entity(&mut u32s, 0);
+            entities.add_entity((&mut u32s, &mut usizes), (1, 10))
+        },
+    );
+
+    let snapshot = world.snapshot();
+    world.run(|mut all_storages: AllStoragesViewMut| {
+        all_storages.delete(entity);
+    });
+    world.restore_snapshot(&snapshot);
+
+    world.run(|u32s: View<u32>, usizes: View<usize>| {
+        let mut iter = (&u32s, &usizes).iter();
+        assert_eq!(iter.next(), Some((&1, &10)));
+        assert_eq!(iter.next(), None);
+    });
+}
+
+#[test]
+fn partially_registered_tight_pack() {
+    let world = World::new();
+    world.register_snapshot::<u32>();
+
+    world.run(|mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>| {
+        (&mut u32s, &mut usizes).tight_pack();
+    });
+    let entity0 = world.run(
+        |mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>| {
+            entities.add_entity((&mut u32s, &mut usizes), (0, 10))
+        },
+    );
+
+    let snapshot = world.snapshot();
+    world.restore_snapshot(&snapshot);
+
+    world.run(
+        |mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>| {
+            assert_eq!(u32s.get(entity0), Ok(&0));
+            assert_eq!((&u32s, &usizes).iter().count(), 0);
+
+            let entity1 = entities.add_entity((&mut u32s, &mut usizes), (1, 99));
+
+            let mut iter = (&u32s, &usizes).iter().with_id();
+            assert_eq!(iter.next(), Some((entity1, (&1, &99))));
+            assert_eq!(iter.next(), None);
+        },
+    );
+}
+
+#[test]
+fn register_twice() {
+    let world = World::new();
+    world.register_snapshot::<u32>();
+
+    assert_eq!(
+        world.try_register_snapshot::<u32>().err(),
+        Some(error::Register::TypeAlreadyRegistered(
+            core::any::type_name::<u32>()
+        ))
+    );
+    assert_eq!(
+        world.try_register_unique_snapshot::<u32>().err(),
+        Some(error::Register::TypeAlreadyRegistered(
+            core::any::type_name::<u32>()
+        ))
+    );
+}
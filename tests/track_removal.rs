@@ -0,0 +1,104 @@
+use shipyard::error;
+use shipyard::*;
+
+#[test]
+fn not_tracked() {
+    let world = World::new();
+    let (mut entities, mut usizes) = world
+        .try_borrow::<(EntitiesViewMut, ViewMut<usize>)>()
+        .unwrap();
+
+    let entity = entities.add_entity(&mut usizes, 0);
+    usizes.try_remove(entity).unwrap();
+
+    assert!(!usizes.is_tracking_removal());
+    assert_eq!(usizes.try_removed().err(), Some(error::NotUpdatePack));
+    assert_eq!(usizes.try_deleted().err(), Some(error::NotUpdatePack));
+}
+
+#[test]
+fn no_pack() {
+    let world = World::new();
+    let (mut entities, mut usizes) = world
+        .try_borrow::<(EntitiesViewMut, ViewMut<usize>)>()
+        .unwrap();
+
+    usizes.track_removal();
+    assert!(usizes.is_tracking_removal());
+
+    let entity0 = entities.add_entity(&mut usizes, 0);
+    let entity1 = entities.add_entity(&mut usizes, 1);
+    let entity2 = entities.add_entity(&mut usizes, 2);
+
+    usizes.try_remove(entity0).unwrap();
+    usizes.try_delete(entity1).unwrap();
+    usizes.clear();
+
+    assert_eq!(usizes.try_removed().unwrap(), &[entity0]);
+    assert_eq!(
+        usizes.try_take_deleted().unwrap(),
+        vec![(entity1, 1), (entity2, 2)]
+    );
+    assert_eq!(usizes.try_take_removed().unwrap(), vec![entity0]);
+    assert!(usizes.try_deleted().unwrap().is_empty());
+    assert!(usizes.try_removed().unwrap().is_empty());
+}
+
+#[test]
+fn tight() {
+    let world = World::new();
+    let (mut entities, mut usizes, mut u32s) = world
+        .try_borrow::<(EntitiesViewMut, ViewMut<usize>, ViewMut<u32>)>()
+        .unwrap();
+
+    (&mut usizes, &mut u32s).try_tight_pack().unwrap();
+    usizes.track_removal();
+
+    let entity0 = entities.add_entity((&mut usizes, &mut u32s), (0usize, 1u32));
+    let entity1 = entities.add_entity((&mut usizes, &mut u32s), (2usize, 3u32));
+
+    Remove::<(usize,)>::try_remove((&mut usizes, &mut u32s), entity0).unwrap();
+    Delete::<(usize,)>::try_delete((&mut usizes, &mut u32s), entity1).unwrap();
+
+    assert_eq!(usizes.try_removed().unwrap(), &[entity0]);
+    assert_eq!(usizes.try_deleted().unwrap(), &[(entity1, 2)]);
+    assert!(u32s.try_removed().is_err());
+    assert_eq!((&usizes, &u32s).iter().count(), 0);
+}
+
+#[test]
+fn all_storages() {
+    let world = World::new();
+
+    let entity = world.run(
+        |mut entities: EntitiesViewMut, mut usizes: ViewMut<usize>, mut u32s: ViewMut<u32>| {
+            (&mut usizes, &mut u32s).loose_pack();
+            usizes.track_removal();
+            entities.add_entity((&mut usizes, &mut u32s), (0usize, 1u32))
+        },
+    );
+
+    world.run(|mut all_storages: AllStoragesViewMut| {
+        all_storages.delete(entity);
+    });
+
+    world.run(|usizes: View<usize>| {
+        assert_eq!(usizes.try_deleted().unwrap(), &[(entity, 0)]);
+    });
+}
+
+#[test]
+fn update_pack_keeps_tracked() {
+    let world = World::new();
+    let (mut entities, mut usizes) = world
+        .try_borrow::<(EntitiesViewMut, ViewMut<usize>)>()
+        .unwrap();
+
+    usizes.track_removal();
+    let entity = entities.add_entity(&mut usizes, 0);
+    usizes.try_delete(entity).unwrap();
+    usizes.try_update_pack().unwrap();
+
+    assert_eq!(usizes.try_deleted().unwrap(), &[(entity, 0)]);
+    assert!(usizes.try_inserted().unwrap().is_empty());
+}
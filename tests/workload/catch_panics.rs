@@ -0,0 +1,57 @@
+use shipyard::error;
+use shipyard::*;
+
+fn broken(_: ViewMut<u32>) {
+    panic!("broken system");
+}
+
+fn count(_: View<usize>, mut count: UniqueViewMut<u64>) {
+    *count += 1;
+}
+
+#[test]
+fn catch_panics() {
+    let world = World::new();
+    world.add_unique(0u64);
+    world.catch_panics(true);
+
+    world
+        .add_workload("Parallel")
+        .with_system(system!(broken))
+        .with_system(system!(count))
+        .on_error(ErrorPolicy::Continue)
+        .build();
+
+    match world.try_run_default() {
+        Err(error::RunWorkload::Errors(errors)) => {
+            assert_eq!(errors.len(), 1);
+            match &errors[0].1 {
+                error::Run::Panic((name, payload)) => {
+                    assert!(name.ends_with("broken"));
+                    assert_eq!(payload.downcast_ref::<&str>(), Some(&"broken system"));
+                }
+                _ => panic!(),
+            }
+        }
+        _ => panic!(),
+    }
+
+    // the borrows of the panicking system were released
+    world.run(|mut u32s: ViewMut<u32>, count: UniqueView<u64>| {
+        u32s.clear();
+        assert_eq!(*count, 1);
+    });
+}
+
+#[test]
+#[should_panic(expected = "broken system")]
+fn opt_in() {
+    let world = World::new();
+
+    world
+        .add_workload("Broken")
+        .with_system(system!(broken))
+        .build();
+
+    let _ = world.try_run_default();
+}
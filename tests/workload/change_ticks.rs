@@ -0,0 +1,317 @@
+use shipyard::error;
+use shipyard::*;
+
+struct SeenA(Vec<usize>);
+struct SeenB(Vec<usize>);
+struct SeenChanges(Vec<(usize, usize)>);
+
+fn write(mut u32s: ViewMut<u32>) {
+    for value in (&mut u32s).iter() {
+        *value += 1;
+    }
+}
+
+fn read_a(u32s: View<u32>, mut seen: UniqueViewMut<SeenA>) {
+    seen.0
+        .push(u32s.inserted_or_modified_since(u32s.last_run()).count());
+}
+
+fn read_b(u32s: View<u32>, mut seen: UniqueViewMut<SeenB>) {
+    seen.0
+        .push(u32s.inserted_or_modified_since(u32s.last_run()).count());
+}
+
+fn read_changes(u32s: View<u32>, mut seen: UniqueViewMut<SeenChanges>) {
+    seen.0
+        .push((u32s.inserted().len(), u32s.modified().iter().count()));
+}
+
+fn modify_own(mut u32s: ViewMut<u32>, mut seen: UniqueViewMut<SeenA>) {
+    let last_run = u32s.last_run();
+    seen.0
+        .push(u32s.inserted_or_modified_since(last_run).count());
+
+    for value in (&mut u32s).iter() {
+        *value += 1;
+    }
+}
+
+#[test]
+fn independent_systems() {
+    let world = World::new();
+    world.add_unique(SeenA(Vec::new()));
+    world.add_unique(SeenB(Vec::new()));
+
+    world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>| {
+        u32s.track_changes();
+        entities.add_entity(&mut u32s, 0);
+    });
+
+    world
+        .add_workload("Write")
+        .with_system(system!(write))
+        .build();
+    world.add_workload("A").with_system(system!(read_a)).build();
+    world.add_workload("B").with_system(system!(read_b)).build();
+
+    world.run_workload("A");
+    world.run_workload("A");
+    world.run_workload("Write");
+    world.run_workload("B");
+    world.run_workload("A");
+    world.run_workload("A");
+    world.run_workload("B");
+
+    world.run(|seen_a: UniqueView<SeenA>, seen_b: UniqueView<SeenB>| {
+        assert_eq!(seen_a.0, vec![1, 0, 1, 0]);
+        assert_eq!(seen_b.0, vec![1, 0]);
+    });
+}
+
+#[test]
+fn view_changes() {
+    let world = World::new();
+    world.add_unique(SeenB(Vec::new()));
+    world.add_unique(SeenChanges(Vec::new()));
+
+    world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>| {
+        u32s.track_changes();
+        entities.add_entity(&mut u32s, 0);
+    });
+
+    world
+        .add_workload("Write")
+        .with_system(system!(write))
+        .build();
+    world
+        .add_workload("Changes")
+        .with_system(system!(read_changes))
+        .build();
+    world.add_workload("B").with_system(system!(read_b)).build();
+
+    world.run_workload("Changes");
+    world.run_workload("Changes");
+    world.run_workload("Write");
+    world.run_workload("Changes");
+    world.run_workload("B");
+    world.run_workload("Changes");
+
+    world.run(|seen: UniqueView<SeenChanges>, seen_b: UniqueView<SeenB>| {
+        assert_eq!(seen.0, vec![(1, 0), (0, 0), (0, 1), (0, 0)]);
+        assert_eq!(seen_b.0, vec![1]);
+    });
+}
+
+#[test]
+fn own_changes() {
+    let world = World::new();
+    world.add_unique(SeenA(Vec::new()));
+
+    world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>| {
+        u32s.track_changes();
+        entities.add_entity(&mut u32s, 0);
+    });
+
+    world
+        .add_workload("Modify")
+        .with_system(system!(modify_own))
+        .build();
+
+    world.run_default();
+    world.run_default();
+    world.run_default();
+
+    world.run(|seen: UniqueView<SeenA>| assert_eq!(seen.0, vec![1, 0, 0]));
+}
+
+#[test]
+fn outside_workload() {
+    let world = World::new();
+
+    let (entity0, entity1, entity2) =
+        world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>| {
+            assert_eq!(
+                u32s.try_inserted_since(0).err(),
+                Some(error::NotTrackingChanges)
+            );
+            u32s.track_changes();
+
+            (
+                entities.add_entity(&mut u32s, 0),
+                entities.add_entity(&mut u32s, 1),
+                entities.add_entity(&mut u32s, 2),
+            )
+        });
+
+    let tick = world.run(|all_storages: AllStoragesViewMut| all_storages.change_tick());
+
+    world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>| {
+        *(&mut u32s).get(entity1).unwrap() += 10;
+        u32s[entity2] += 10;
+        entities.add_entity(&mut u32s, 3);
+        assert_eq!(u32s.last_run(), 0);
+    });
+
+    world.run(|u32s: View<u32>| {
+        let inserted: Vec<_> = u32s.inserted_since(tick).map(|(_, &value)| value).collect();
+        let mut modified: Vec<_> = u32s.modified_since(tick).map(|(id, _)| id).collect();
+        modified.sort_unstable();
+        assert_eq!(inserted, vec![3]);
+        assert_eq!(modified, vec![entity1, entity2]);
+        assert_eq!(u32s.inserted_or_modified_since(tick).count(), 3);
+        assert_eq!(u32s.inserted_or_modified_since(0).count(), 4);
+        assert!(u32s
+            .inserted_or_modified_since(0)
+            .any(|(id, _)| id == entity0));
+    });
+}
+
+#[test]
+fn tight_iteration() {
+    let world = World::new();
+
+    world.run(
+        |mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>, mut usizes: ViewMut<usize>| {
+            (&mut u32s, &mut usizes).tight_pack();
+            u32s.track_changes();
+            entities.add_entity((&mut u32s, &mut usizes), (0, 0));
+            entities.add_entity(&mut u32s, 1);
+        },
+    );
+
+    let tick = world.run(|all_storages: AllStoragesViewMut| all_storages.change_tick());
+
+    world.run(|mut u32s: ViewMut<u32>, usizes: View<usize>| {
+        for (value, _) in (&mut u32s, &usizes).iter() {
+            *value += 1;
+        }
+    });
+
+    world.run(|u32s: View<u32>| {
+        let modified: Vec<_> = u32s.modified_since(tick).map(|(_, &value)| value).collect();
+        assert_eq!(modified, vec![1]);
+    });
+}
+
+#[test]
+fn view_outside_workload() {
+    let world = World::new();
+
+    let (entity0, entity1) = world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>| {
+        let entity0 = entities.add_entity(&mut u32s, 0);
+        u32s.track_changes();
+        (entity0, entities.add_entity(&mut u32s, 1))
+    });
+
+    world.run(|mut u32s: ViewMut<u32>| {
+        u32s[entity0] += 10;
+
+        let inserted = u32s.inserted();
+        assert_eq!(inserted.len(), 1);
+        assert!(inserted.contains(entity1));
+        assert!(!inserted.contains(entity0));
+        assert_eq!(inserted[entity1], 1);
+
+        let modified = u32s.modified();
+        assert_eq!(modified.iter().collect::<Vec<_>>(), vec![&10]);
+        assert_eq!(u32s.inserted_or_modified().len(), 2);
+    });
+}
+
+#[test]
+fn replace_is_modification() {
+    let world = World::new();
+
+    let entity = world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>| {
+        u32s.track_changes();
+        entities.add_entity(&mut u32s, 0)
+    });
+
+    let tick = world.run(|all_storages: AllStoragesViewMut| all_storages.change_tick());
+
+    world.run(|entities: EntitiesView, mut u32s: ViewMut<u32>| {
+        entities.add_component(&mut u32s, 1, entity);
+    });
+
+    world.run(|u32s: View<u32>| {
+        assert_eq!(u32s.inserted_since(tick).count(), 0);
+        assert_eq!(u32s.modified_since(tick).count(), 1);
+    });
+}
+
+#[test]
+fn restore() {
+    let world = World::new();
+    world.register_clone::<u32>();
+    world.add_unique(SeenB(Vec::new()));
+
+    world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>| {
+        u32s.track_changes();
+        entities.add_entity(&mut u32s, 0);
+    });
+
+    let checkpoint = world.checkpoint();
+
+    world.add_workload("B").with_system(system!(read_b)).build();
+    world.run_workload("B");
+    world.run_workload("B");
+    world.restore(&checkpoint);
+    world.run_workload("B");
+    world.run_workload("B");
+
+    world.run(|seen_b: UniqueView<SeenB>| assert_eq!(seen_b.0, vec![1, 0, 1, 0]));
+}
+
+#[test]
+fn apply_delta() {
+    let world = World::new();
+    world.register_delta::<u32>();
+    let replica = World::new();
+    replica.register_delta::<u32>();
+    replica.add_unique(SeenChanges(Vec::new()));
+
+    let checkpoint = world.checkpoint();
+    let entity = world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>| {
+        entities.add_entity(&mut u32s, 0)
+    });
+    replica.apply_delta(&world.delta(&checkpoint));
+    replica.run(|mut u32s: ViewMut<u32>| u32s.track_changes());
+
+    replica
+        .add_workload("Changes")
+        .with_system(system!(read_changes))
+        .build();
+    replica.run_workload("Changes");
+
+    let checkpoint = world.checkpoint();
+    world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>| {
+        u32s[entity] += 1;
+        entities.add_entity(&mut u32s, 1);
+    });
+    replica.apply_delta(&world.delta(&checkpoint));
+    replica.run_workload("Changes");
+
+    replica.run(|seen: UniqueView<SeenChanges>| assert_eq!(seen.0, vec![(0, 0), (1, 1)]));
+}
+
+#[cfg(feature = "parallel")]
+#[cfg_attr(miri, ignore)]
+#[test]
+fn view_par_iter() {
+    use rayon::prelude::*;
+
+    let world = World::new();
+
+    world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<u32>| {
+        entities.add_entity(&mut u32s, 0);
+        u32s.track_changes();
+        entities.add_entity(&mut u32s, 1);
+        entities.add_entity(&mut u32s, 2);
+    });
+
+    world.run(|u32s: View<u32>| {
+        let mut inserted: Vec<_> = u32s.inserted().par_iter().collect();
+        inserted.sort_unstable();
+        assert_eq!(inserted, vec![&1, &2]);
+    });
+}
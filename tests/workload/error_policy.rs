@@ -0,0 +1,113 @@
+use shipyard::error;
+use shipyard::*;
+
+fn fail(_: ViewMut<u32>) -> Result<(), error::NotUpdatePack> {
+    Err(error::NotUpdatePack)
+}
+
+fn fail_again(_: ViewMut<u64>) -> Result<(), error::NotUpdatePack> {
+    Err(error::NotUpdatePack)
+}
+
+fn read_u32(_: View<u32>, mut count: UniqueViewMut<usize>) {
+    *count += 1;
+}
+
+fn write_u64(_: View<u32>, _: ViewMut<u64>) {}
+
+fn read_u64(_: View<u64>, mut count: UniqueViewMut<usize>) {
+    *count += 10;
+}
+
+fn other(mut count: UniqueViewMut<usize>) {
+    *count += 100;
+}
+
+fn workload(world: &World, policy: ErrorPolicy) {
+    world
+        .add_workload("Server")
+        .with_system(try_system!(fail))
+        .with_system(system!(read_u32))
+        .with_system(system!(write_u64))
+        .with_system(system!(read_u64))
+        .with_system(system!(other))
+        .with_system(try_system!(fail_again))
+        .on_error(policy)
+        .build();
+}
+
+#[test]
+fn stop() {
+    let world = World::new();
+    world.add_unique(0usize);
+    workload(&world, ErrorPolicy::Stop);
+
+    match world.try_run_default() {
+        Err(error::RunWorkload::Run((name, _))) => assert!(name.ends_with("fail")),
+        _ => panic!(),
+    }
+    world.run(|count: UniqueView<usize>| assert_eq!(*count, 0));
+}
+
+#[test]
+fn continue_running() {
+    let world = World::new();
+    world.add_unique(0usize);
+    workload(&world, ErrorPolicy::Continue);
+
+    match world.try_run_default() {
+        Err(error::RunWorkload::Errors(errors)) => {
+            assert_eq!(errors.len(), 2);
+            assert!(errors[0].0.ends_with("fail"));
+            assert!(errors[1].0.ends_with("fail_again"));
+        }
+        _ => panic!(),
+    }
+    world.run(|count: UniqueView<usize>| assert_eq!(*count, 111));
+}
+
+#[test]
+fn skip_dependents() {
+    let world = World::new();
+    world.add_unique(0usize);
+    workload(&world, ErrorPolicy::SkipDependents);
+
+    match world.try_run_default() {
+        Err(error::RunWorkload::Errors(errors)) => {
+            assert_eq!(errors.len(), 2);
+            assert!(errors[0].0.ends_with("fail"));
+            assert!(errors[1].0.ends_with("fail_again"));
+        }
+        _ => panic!(),
+    }
+    // read_u32 and write_u64 depend on fail
+    world.run(|count: UniqueView<usize>| assert_eq!(*count, 110));
+}
+
+#[test]
+fn included_workload() {
+    let world = World::new();
+    world.add_unique(0usize);
+    workload(&world, ErrorPolicy::Continue);
+
+    world.add_workload("Outer").with_workload("Server").build();
+
+    // the policy of the including workload applies
+    match world.try_run_workload("Outer") {
+        Err(error::RunWorkload::Run((name, _))) => assert!(name.ends_with("fail")),
+        _ => panic!(),
+    }
+    world.run(|count: UniqueView<usize>| assert_eq!(*count, 0));
+
+    world
+        .add_workload("Continue")
+        .with_workload("Server")
+        .on_error(ErrorPolicy::Continue)
+        .build();
+
+    match world.try_run_workload("Continue") {
+        Err(error::RunWorkload::Errors(errors)) => assert_eq!(errors.len(), 2),
+        _ => panic!(),
+    }
+    world.run(|count: UniqueView<usize>| assert_eq!(*count, 111));
+}
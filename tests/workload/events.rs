@@ -0,0 +1,105 @@
+use shipyard::*;
+
+fn send(mut events: EventWriter<u32>, mut next: UniqueViewMut<u32>) {
+    events.send(*next);
+    *next += 1;
+}
+
+fn read(mut events: EventReader<u32>, mut read: UniqueViewMut<Vec<u32>>) {
+    read.extend(events.iter().copied());
+}
+
+fn read_again(mut events: EventReader<u32>, mut read: UniqueViewMut<Vec<u64>>) {
+    read.extend(events.iter().map(|&event| event as u64));
+}
+
+#[test]
+fn multiple_readers() {
+    let world = World::new();
+    world.add_event::<u32>();
+    world.add_unique(0u32);
+    world.add_unique(Vec::<u32>::new());
+    world.add_unique(Vec::<u64>::new());
+
+    world
+        .add_workload("Events")
+        .with_system(system!(send))
+        .with_system(system!(read))
+        .with_system(system!(read_again))
+        .build();
+
+    for _ in 0..3 {
+        world.run_default();
+    }
+
+    world.run(
+        |read: UniqueView<Vec<u32>>, read_again: UniqueView<Vec<u64>>| {
+            assert_eq!(*read, vec![0, 1, 2]);
+            assert_eq!(*read_again, vec![0, 1, 2]);
+        },
+    );
+}
+
+#[test]
+fn double_buffer() {
+    let world = World::new();
+    world.add_event::<u32>();
+    world.add_unique(Vec::<u32>::new());
+
+    world
+        .add_workload("Read")
+        .with_system(system!(read))
+        .build();
+    world.add_workload("Empty").build();
+
+    world.run(|mut events: EventWriter<u32>| events.extend(vec![0, 1]));
+
+    world.run_workload("Empty");
+    world.run(|events: UniqueView<Events<u32>>| assert_eq!(events.len(), 2));
+
+    // the events are still there for systems running in the next workload run
+    world.run_workload("Read");
+    world.run(|events: UniqueView<Events<u32>>| assert!(events.is_empty()));
+
+    world.run(|mut events: EventWriter<u32>| events.send(2));
+    world.run_workload("Empty");
+    world.run_workload("Empty");
+    world.run_workload("Read");
+
+    world.run(|read: UniqueView<Vec<u32>>| assert_eq!(*read, vec![0, 1]));
+}
+
+#[test]
+fn cursor() {
+    let world = World::new();
+    world.add_event::<u32>();
+
+    let mut cursor = EventCursor::default();
+
+    world.run(|mut events: EventWriter<u32>| events.extend(vec![0, 1]));
+    world.run(|events: UniqueView<Events<u32>>| {
+        assert_eq!(cursor.read(&events).collect::<Vec<_>>(), vec![&0, &1]);
+        assert_eq!(cursor.read(&events).count(), 0);
+    });
+
+    world.run(|mut events: UniqueViewMut<Events<u32>>| {
+        events.update();
+        events.send(2);
+        events.update();
+        events.send(3);
+    });
+    world.run(|events: UniqueView<Events<u32>>| {
+        assert_eq!(cursor.read(&events).collect::<Vec<_>>(), vec![&2, &3]);
+    });
+}
+
+#[test]
+fn outside_workload() {
+    let world = World::new();
+    world.add_event::<u32>();
+
+    match world.try_run(|_: EventReader<u32>| {}) {
+        Err(error::Run::GetStorage(error::GetStorage::MissingLocal(_))) => {}
+        _ => panic!(),
+    }
+}
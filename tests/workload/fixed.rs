@@ -0,0 +1,117 @@
+use core::time::Duration;
+use shipyard::*;
+
+fn tick(mut ticks: UniqueViewMut<u32>) {
+    *ticks += 1;
+}
+
+fn slow(mut ticks: UniqueViewMut<u64>) {
+    *ticks += 1;
+}
+
+#[test]
+fn run_workload_fixed() {
+    let world = World::new();
+    world.add_unique(0u32);
+    world.add_unique(0u64);
+
+    world
+        .add_workload("Physics")
+        .with_system(system!(tick))
+        .build();
+    world
+        .add_workload("Slow")
+        .with_system(system!(slow))
+        .build();
+
+    let step = Duration::from_millis(10);
+
+    world.run_workload_fixed("Physics", step, Duration::from_millis(5), 5);
+    world.run(
+        |ticks: UniqueView<u32>, fixed_time: UniqueView<FixedTime>| {
+            assert_eq!(*ticks, 0);
+            assert_eq!(fixed_time.steps(), 0);
+            assert_eq!(fixed_time.step(), step);
+            assert!((fixed_time.alpha() - 0.5).abs() < 0.01);
+        },
+    );
+
+    // the accumulator keeps the time left from the previous call
+    world.run_workload_fixed("Physics", step, Duration::from_millis(30), 5);
+    world.run(
+        |ticks: UniqueView<u32>, fixed_time: UniqueView<FixedTime>| {
+            assert_eq!(*ticks, 3);
+            assert_eq!(fixed_time.steps(), 3);
+            assert!((fixed_time.alpha() - 0.5).abs() < 0.01);
+        },
+    );
+
+    // each workload has its own accumulator
+    for _ in 0..4 {
+        world.run_workload_fixed("Physics", step, step, 5);
+        world.run_workload_fixed("Slow", step * 2, step, 5);
+    }
+    world.run(|ticks: UniqueView<u32>, slow: UniqueView<u64>| {
+        assert_eq!(*ticks, 7);
+        assert_eq!(*slow, 2);
+    });
+
+    world.run_workload_fixed("Physics", Duration::from_secs(0), step, 5);
+    world.run(|ticks: UniqueView<u32>| assert_eq!(*ticks, 8));
+
+    assert!(world
+        .try_run_workload_fixed("Render", step, step, 5)
+        .is_err());
+}
+
+#[test]
+fn max_steps() {
+    let world = World::new();
+    world.add_unique(0u32);
+
+    world
+        .add_workload("Physics")
+        .with_system(system!(tick))
+        .build();
+
+    let step = Duration::from_millis(10);
+
+    // a long frame only runs max_steps steps and the skipped ones are dropped
+    world.run_workload_fixed("Physics", step, Duration::from_millis(1005), 3);
+    world.run(
+        |ticks: UniqueView<u32>, fixed_time: UniqueView<FixedTime>| {
+            assert_eq!(*ticks, 3);
+            assert_eq!(fixed_time.steps(), 3);
+            assert!((fixed_time.alpha() - 0.5).abs() < 0.01);
+        },
+    );
+
+    world.run_workload_fixed("Physics", step, Duration::from_millis(5), 3);
+    world.run(|ticks: UniqueView<u32>| assert_eq!(*ticks, 4));
+
+    // more steps than fit in a u32
+    world.run_workload_fixed(
+        "Physics",
+        Duration::from_nanos(1),
+        Duration::from_secs(10),
+        2,
+    );
+    world.run(|ticks: UniqueView<u32>| assert_eq!(*ticks, 6));
+}
+
+#[test]
+fn run_workload_every() {
+    let world = World::new();
+    world.add_unique(0u32);
+
+    world.add_workload("AI").with_system(system!(tick)).build();
+
+    for _ in 0..9 {
+        world.run_workload_every("AI", 3);
+    }
+    world.run(|ticks: UniqueView<u32>| assert_eq!(*ticks, 3));
+
+    world.run_workload_every("AI", 0);
+    world.run_workload_every("AI", 0);
+    world.run(|ticks: UniqueView<u32>| assert_eq!(*ticks, 5));
+}
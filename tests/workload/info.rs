@@ -0,0 +1,80 @@
+use core::any::{type_name, TypeId};
+use shipyard::*;
+
+fn read(_: View<u32>) {}
+fn write(_: ViewMut<u32>) {}
+fn other(_: View<usize>) {}
+fn all(_: AllStoragesViewMut) {}
+
+#[test]
+fn workload_info() {
+    let world = World::new();
+
+    world
+        .add_workload("Update")
+        .with_system(system!(read))
+        .with_system(system!(write))
+        .with_system(system!(other))
+        .with_system(system!(all))
+        .build();
+
+    let info = world.workload_info("Update");
+    assert_eq!(info.name(), "Update");
+
+    let batches = info.batches();
+    assert_eq!(batches.len(), 3);
+    assert_eq!(batches[0].len(), 2);
+    assert!(batches[0][0].name().ends_with("read"));
+    assert!(batches[0][1].name().ends_with("other"));
+    assert_eq!(
+        batches[0][0].borrows(),
+        &[(TypeId::of::<u32>(), type_name::<u32>(), Mutation::Shared)]
+    );
+    assert!(batches[0][0].conflict().is_none());
+
+    let conflict = batches[1][0].conflict().unwrap();
+    assert!(conflict.system().ends_with("read"));
+    assert_eq!(conflict.type_id(), TypeId::of::<u32>());
+    assert_eq!(conflict.type_name(), type_name::<u32>());
+    assert_eq!(conflict.mutation(), Mutation::Unique);
+    assert_eq!(conflict.other_mutation(), Mutation::Shared);
+
+    let conflict = batches[2][0].conflict().unwrap();
+    assert!(conflict.system().ends_with("write"));
+    assert_eq!(conflict.type_id(), TypeId::of::<AllStorages>());
+
+    world.disable_system("other");
+    let dot = world.workload_info("Update").to_dot();
+    assert!(dot.starts_with("digraph \"Update\" {"));
+    assert!(dot.contains("subgraph cluster_2"));
+    assert!(dot.contains("s0_0 -> s1_0 [label = \"Shared / Unique u32\"]"));
+    assert!(dot.contains(type_name::<AllStorages>()));
+    assert!(!dot.contains("TypeId"));
+    assert!(dot.contains("style = dashed"));
+}
+
+#[test]
+fn workloads_info() {
+    let world = World::new();
+
+    world
+        .add_workload("First")
+        .with_system(system!(read))
+        .build();
+    world
+        .add_workload("Second")
+        .with_system(system!(write))
+        .build();
+
+    let names: Vec<_> = world
+        .workloads_info()
+        .iter()
+        .map(|info| info.name().to_string())
+        .collect();
+    assert_eq!(names, ["First", "Second"]);
+
+    assert_eq!(
+        world.try_workload_info("Third").err(),
+        Some(error::WorkloadInfo::MissingWorkload)
+    );
+}
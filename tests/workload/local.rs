@@ -0,0 +1,85 @@
+use shipyard::*;
+
+fn count(mut runs: Local<u32>, mut last: UniqueViewMut<u32>) {
+    *runs += 1;
+    *last = *runs;
+}
+
+fn count_twice(mut runs: Local<u32>, mut last: UniqueViewMut<u64>) {
+    *runs += 2;
+    *last = *runs as u64;
+}
+
+#[test]
+fn keeps_state() {
+    let world = World::new();
+    world.add_unique(0u32);
+    world.add_unique(0u64);
+
+    world
+        .add_workload("Count")
+        .with_system(system!(count))
+        .with_system(system!(count_twice))
+        .build();
+
+    for _ in 0..3 {
+        world.run_default();
+    }
+
+    world.run(|last: UniqueView<u32>, last_twice: UniqueView<u64>| {
+        assert_eq!(*last, 3);
+        assert_eq!(*last_twice, 6);
+    });
+}
+
+#[test]
+fn no_conflict() {
+    let world = World::new();
+
+    world
+        .add_workload("Locals")
+        .with_system(system!(|_: Local<u32>| {}))
+        .with_system(system!(|_: Local<u32>| {}))
+        .build();
+
+    assert_eq!(world.workload_info("Locals").batches().len(), 1);
+}
+
+#[test]
+fn shared_between_workloads() {
+    let world = World::new();
+    world.add_unique(0u32);
+
+    world.add_workload("A").with_system(system!(count)).build();
+    world.add_workload("B").with_workload("A").build();
+
+    world.run_workload("A");
+    world.run_workload("B");
+
+    world.run(|last: UniqueView<u32>| assert_eq!(*last, 2));
+}
+
+#[test]
+fn outside_workload() {
+    let world = World::new();
+
+    match world.try_run(|_: Local<u32>| {}) {
+        Err(error::Run::GetStorage(error::GetStorage::MissingLocal(name))) => {
+            assert_eq!(name, core::any::type_name::<u32>())
+        }
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn multiple_locals() {
+    let world = World::new();
+
+    assert_eq!(
+        world
+            .add_workload("")
+            .try_with_system(system!(|_: Local<u32>, _: Local<u32>| {}))
+            .err(),
+        Some(error::InvalidSystem::MultipleViewsMut)
+    );
+}
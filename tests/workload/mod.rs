@@ -1,5 +1,20 @@
+mod catch_panics;
+#[cfg(feature = "std")]
+mod change_ticks;
+mod error_policy;
+#[cfg(feature = "std")]
+mod events;
+mod fixed;
+mod info;
+#[cfg(feature = "std")]
+mod local;
 #[cfg(all(feature = "non_send", feature = "non_sync"))]
 mod non_send_sync;
+#[cfg(feature = "std")]
+mod profiler;
+mod remove;
+mod run_if;
+mod with_workload;
 
 use shipyard::*;
 
@@ -0,0 +1,114 @@
+use core::sync::atomic::{AtomicUsize, Ordering};
+use shipyard::*;
+use std::sync::Arc;
+use std::thread::ThreadId;
+
+fn read(_: View<u32>) {}
+fn write(_: ViewMut<u32>) {}
+
+#[derive(Default)]
+struct Counter {
+    batches: AtomicUsize,
+    systems: AtomicUsize,
+}
+
+impl Profiler for Counter {
+    fn batch_end(&self, _: usize) {
+        self.batches.fetch_add(1, Ordering::Relaxed);
+    }
+    fn system_end(&self, _: &'static str, _: ThreadId) {
+        self.systems.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn hooks() {
+    let world = World::new();
+    let counter = Arc::new(Counter::default());
+    world.set_profiler(counter.clone());
+
+    world
+        .add_workload("Update")
+        .with_system(system!(read))
+        .with_system(system!(write))
+        .build();
+
+    world.run_default();
+    assert_eq!(counter.batches.load(Ordering::Relaxed), 2);
+    assert_eq!(counter.systems.load(Ordering::Relaxed), 2);
+
+    assert!(world.remove_profiler().is_some());
+    world.run_default();
+    assert_eq!(counter.systems.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn timings() {
+    let world = World::new();
+    let profiler = Arc::new(TimingProfiler::new());
+    world.set_profiler(profiler.clone());
+
+    world
+        .add_workload("Update")
+        .with_system(system!(read))
+        .with_system(system!(write))
+        .build();
+
+    for _ in 0..3 {
+        world.run_default();
+    }
+
+    let timings = profiler.timings();
+    assert_eq!(timings.len(), 2);
+    assert!(timings[0].name().ends_with("read"));
+    assert_eq!(timings[0].count(), 3);
+    assert!(timings[0].min() <= timings[0].avg());
+    assert!(timings[0].avg() <= timings[0].max());
+
+    // runs are only recorded while tracing
+    assert!(!profiler.is_tracing());
+    assert_eq!(profiler.chrome_trace().matches("\"ph\":\"X\"").count(), 0);
+
+    profiler.start_trace();
+    world.run_default();
+    world.run_default();
+    profiler.stop_trace();
+    world.run_default();
+
+    assert_eq!(profiler.timings()[0].count(), 6);
+    let trace = profiler.chrome_trace();
+    assert_eq!(trace.matches("\"ph\":\"X\"").count(), 4);
+
+    let path = std::env::temp_dir().join("shipyard_chrome_trace.json");
+    profiler.write_chrome_trace(&path).unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), trace);
+    std::fs::remove_file(path).unwrap();
+
+    profiler.clear();
+    assert!(profiler.timings().is_empty());
+}
+
+#[test]
+fn panicking_system() {
+    fn panic(_: View<u32>) {
+        panic!()
+    }
+
+    let world = World::new();
+    let profiler = Arc::new(TimingProfiler::new());
+    world.set_profiler(profiler.clone());
+
+    world
+        .add_workload("Update")
+        .with_system(system!(panic))
+        .build();
+
+    assert!(
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| world.run_default())).is_err()
+    );
+
+    // the run still ended
+    let timings = profiler.timings();
+    assert_eq!(timings.len(), 1);
+    assert_eq!(timings[0].count(), 1);
+}
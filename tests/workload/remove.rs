@@ -0,0 +1,144 @@
+use shipyard::error;
+use shipyard::*;
+
+fn increment(mut count: UniqueViewMut<u32>) {
+    *count += 1;
+}
+
+fn double(mut count: UniqueViewMut<u32>) {
+    *count *= 2;
+}
+
+fn reset(mut count: UniqueViewMut<u32>) {
+    *count = 0;
+}
+
+#[test]
+fn remove_workload() {
+    let world = World::new();
+    world.add_unique(0u32);
+
+    world
+        .add_workload("Increment")
+        .with_system(system!(increment))
+        .build();
+    world
+        .add_workload("Double")
+        .with_system(system!(double))
+        .with_system(system!(increment))
+        .build();
+    world
+        .add_workload("Reset")
+        .with_system(system!(reset))
+        .build();
+
+    world.remove_workload("Double");
+    assert_eq!(
+        world.try_remove_workload("Double"),
+        Err(error::RemoveWorkload::MissingWorkload)
+    );
+    assert!(world.try_run_workload("Double").is_err());
+
+    // workloads added after the removed one still run their systems
+    world.run_workload("Increment");
+    world.run(|count: UniqueView<u32>| assert_eq!(*count, 1));
+    world.run_workload("Reset");
+    world.run(|count: UniqueView<u32>| assert_eq!(*count, 0));
+
+    // the name can be used again
+    world
+        .add_workload("Double")
+        .with_system(system!(double))
+        .build();
+
+    world.remove_workload("Increment");
+    world.run_default();
+    world.run(|count: UniqueView<u32>| assert_eq!(*count, 0));
+}
+
+#[test]
+fn replace_workload() {
+    let world = World::new();
+    world.add_unique(0u32);
+
+    world
+        .add_workload("Update")
+        .with_system(system!(increment))
+        .build();
+    world
+        .add_workload("Other")
+        .with_system(system!(double))
+        .build();
+
+    world
+        .replace_workload("Update")
+        .with_system(system!(increment))
+        .with_system(system!(double))
+        .build();
+
+    // the replaced workload is still the default one
+    world.run_default();
+    world.run(|count: UniqueView<u32>| assert_eq!(*count, 2));
+
+    world
+        .replace_workload("New")
+        .with_system(system!(reset))
+        .build();
+    world.run_workload("New");
+    world.run(|count: UniqueView<u32>| assert_eq!(*count, 0));
+}
+
+#[test]
+fn failed_replace_keeps_workload() {
+    let world = World::new();
+    world.add_unique(0u32);
+
+    world
+        .add_workload("Update")
+        .with_system(system!(increment))
+        .build();
+
+    let result = world
+        .replace_workload("Update")
+        .with_system(system!(double))
+        .after("reset")
+        .with_system(system!(reset))
+        .after("double")
+        .try_build();
+    match result {
+        Err(error::Build::Cycle(_)) => {}
+        _ => panic!(),
+    }
+
+    // a dropped builder doesn't remove the workload either
+    drop(world.replace_workload("Update").with_system(system!(reset)));
+
+    world.run_default();
+    world.run_workload("Update");
+    world.run(|count: UniqueView<u32>| assert_eq!(*count, 2));
+}
+
+#[test]
+fn enable_system() {
+    let world = World::new();
+    world.add_unique(1u32);
+
+    world
+        .add_workload("Update")
+        .with_system(system!(increment))
+        .with_system(system!(double))
+        .build();
+
+    world.disable_system("double");
+    world.run_default();
+    world.run(|count: UniqueView<u32>| assert_eq!(*count, 2));
+
+    world.enable_system("double");
+    world.run_default();
+    world.run(|count: UniqueView<u32>| assert_eq!(*count, 6));
+
+    assert_eq!(
+        world.try_disable_system("triple"),
+        Err(error::EnableSystem::MissingSystem)
+    );
+}
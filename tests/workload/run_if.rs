@@ -0,0 +1,78 @@
+use core::sync::atomic::{AtomicUsize, Ordering};
+use shipyard::*;
+use std::sync::Arc;
+
+#[test]
+fn system_condition() {
+    let world = World::new();
+    world.add_unique(false);
+    world.add_unique(0u32);
+    world.add_unique(0usize);
+
+    world
+        .add_workload("Conditions")
+        .with_system(system!(|mut u32: UniqueViewMut<u32>| *u32 += 1))
+        .run_if(|world: &World| world.run(|run: UniqueView<bool>| *run))
+        .with_system(system!(|mut usize: UniqueViewMut<usize>| *usize += 1))
+        .build();
+
+    world.run_default();
+    world.run(|u32: UniqueView<u32>, usize: UniqueView<usize>| {
+        assert_eq!(*u32, 0);
+        assert_eq!(*usize, 1);
+    });
+
+    world.run(|mut run: UniqueViewMut<bool>| *run = true);
+    world.run_default();
+    world.run(|u32: UniqueView<u32>, usize: UniqueView<usize>| {
+        assert_eq!(*u32, 1);
+        assert_eq!(*usize, 2);
+    });
+}
+
+#[test]
+fn workload_condition() {
+    let world = World::new();
+    world.add_unique(0u32);
+
+    let checks = Arc::new(AtomicUsize::new(0));
+    let workload_checks = checks.clone();
+
+    world
+        .add_workload("Every other frame")
+        .with_system(system!(|mut u32: UniqueViewMut<u32>| *u32 += 1))
+        .with_system(system!(|mut u32: UniqueViewMut<u32>| *u32 += 1))
+        .workload_run_if(move |_: &World| workload_checks.fetch_add(1, Ordering::Relaxed) & 1 == 0)
+        .build();
+
+    for _ in 0..4 {
+        world.run_workload("Every other frame");
+    }
+
+    assert_eq!(checks.load(Ordering::Relaxed), 4);
+    world.run(|u32: UniqueView<u32>| assert_eq!(*u32, 4));
+}
+
+#[test]
+fn empty_workloads_condition() {
+    let world = World::new();
+
+    let checks = Arc::new(AtomicUsize::new(0));
+    let workload_checks = checks.clone();
+
+    world
+        .add_workload("Empty")
+        .workload_run_if(move |_: &World| {
+            workload_checks.fetch_add(1, Ordering::Relaxed);
+            true
+        })
+        .build();
+    world.add_workload("Other empty").build();
+
+    world.run_workload("Other empty");
+    assert_eq!(checks.load(Ordering::Relaxed), 0);
+
+    world.remove_workload("Other empty");
+    world.run_workload("Empty");
+    assert_eq!(checks.load(Ordering::Relaxed), 1);
+}
@@ -0,0 +1,84 @@
+use shipyard::*;
+
+#[test]
+fn with_workload() {
+    let world = World::new();
+    world.add_unique(Vec::<&'static str>::new());
+
+    world
+        .add_workload("Physics")
+        .with_system(system!(
+            |mut log: UniqueViewMut<Vec<&'static str>>| log.push("gravity")
+        ))
+        .with_system(system!(
+            |mut log: UniqueViewMut<Vec<&'static str>>| log.push("movement")
+        ))
+        .build();
+
+    world
+        .add_workload("Frame")
+        .with_system(system!(
+            |mut log: UniqueViewMut<Vec<&'static str>>| log.push("render")
+        ))
+        .after("Physics")
+        .with_workload("Physics")
+        .build();
+
+    world.run_workload("Frame");
+    world.run(|log: UniqueView<Vec<&'static str>>| {
+        assert_eq!(&**log, &["gravity", "movement", "render"]);
+    });
+
+    // the included workload is still available on its own
+    world.run(|mut log: UniqueViewMut<Vec<&'static str>>| log.clear());
+    world.run_workload("Physics");
+    world.run(|log: UniqueView<Vec<&'static str>>| {
+        assert_eq!(&**log, &["gravity", "movement"]);
+    });
+}
+
+#[test]
+fn conditions() {
+    let world = World::new();
+    world.add_unique(false);
+    world.add_unique(0u32);
+    world.add_unique(0usize);
+
+    world
+        .add_workload("Physics")
+        .with_system(system!(|mut u32: UniqueViewMut<u32>| *u32 += 1))
+        .workload_run_if(|world: &World| world.run(|run: UniqueView<bool>| *run))
+        .build();
+
+    world
+        .add_workload("Frame")
+        .with_workload("Physics")
+        .with_system(system!(|mut usize: UniqueViewMut<usize>| *usize += 1))
+        .build();
+
+    world.run_workload("Frame");
+    world.run(|u32: UniqueView<u32>, usize: UniqueView<usize>| {
+        assert_eq!(*u32, 0);
+        assert_eq!(*usize, 1);
+    });
+
+    world.run(|mut run: UniqueViewMut<bool>| *run = true);
+    world.run_workload("Frame");
+    world.run(|u32: UniqueView<u32>, usize: UniqueView<usize>| {
+        assert_eq!(*u32, 1);
+        assert_eq!(*usize, 2);
+    });
+}
+
+#[test]
+fn missing_workload() {
+    let world = World::new();
+
+    assert_eq!(
+        world
+            .add_workload("Frame")
+            .try_with_workload("Physics")
+            .err(),
+        Some(error::WithWorkload::MissingWorkload)
+    );
+}